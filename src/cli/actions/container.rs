@@ -7,9 +7,13 @@
 #[derive(Debug)]
 pub enum RunningContainerAction {
     ViewStats,
+    LiveStats,
     ViewLogs,
     ListLedgers,
+    Restart,
+    Pause,
     Stop,
+    Kill,
     StopAndDestroy,
     GoBack,
 }
@@ -19,9 +23,13 @@ impl RunningContainerAction {
     pub fn variants() -> Vec<&'static str> {
         vec![
             "View Container Stats",
+            "Live Stats Dashboard (all running containers)",
             "View Container Logs",
             "List Ledgers",
+            "Restart Container",
+            "Pause Container",
             "Stop Container",
+            "Kill Container (send a signal)",
             "Stop and Destroy Container",
             "Go Back to Container List",
         ]
@@ -31,11 +39,15 @@ impl RunningContainerAction {
     pub fn from_index(index: usize) -> Option<Self> {
         match index {
             0 => Some(Self::ViewStats),
-            1 => Some(Self::ViewLogs),
-            2 => Some(Self::ListLedgers),
-            3 => Some(Self::Stop),
-            4 => Some(Self::StopAndDestroy),
-            5 => Some(Self::GoBack),
+            1 => Some(Self::LiveStats),
+            2 => Some(Self::ViewLogs),
+            3 => Some(Self::ListLedgers),
+            4 => Some(Self::Restart),
+            5 => Some(Self::Pause),
+            6 => Some(Self::Stop),
+            7 => Some(Self::Kill),
+            8 => Some(Self::StopAndDestroy),
+            9 => Some(Self::GoBack),
             _ => None,
         }
     }
@@ -7,6 +7,8 @@
 #[derive(Debug)]
 pub enum LedgerAction {
     ViewDetails,
+    Backup,
+    Copy,
     Delete,
     Return,
     GoBack,
@@ -17,6 +19,8 @@ impl LedgerAction {
     pub fn variants() -> Vec<&'static str> {
         vec![
             "See More Details",
+            "Back Up Ledger",
+            "Copy Ledger to Another Container",
             "Delete Ledger",
             "Return to Ledger List",
             "Go Back to Container Menu",
@@ -27,9 +31,11 @@ impl LedgerAction {
     pub fn from_index(index: usize) -> Option<Self> {
         match index {
             0 => Some(Self::ViewDetails),
-            1 => Some(Self::Delete),
-            2 => Some(Self::Return),
-            3 => Some(Self::GoBack),
+            1 => Some(Self::Backup),
+            2 => Some(Self::Copy),
+            3 => Some(Self::Delete),
+            4 => Some(Self::Return),
+            5 => Some(Self::GoBack),
             _ => None,
         }
     }
@@ -3,13 +3,911 @@
 //! This module handles parsing and validation of command line arguments
 //! using the clap crate.
 
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+use super::logs::{parse_time_bound, LogLevel};
+use super::output::OutputFormat;
+use crate::config::{parse_network_name, parse_size};
+use crate::docker::{
+    ports::DEFAULT_PORT, readiness::DEFAULT_READY_TIMEOUT_SECS, runtime::validate_repository,
+    CommandOptions, NetworkAttachment, NetworkOptions, PortMapping, ResourceLimits, RestartPolicy,
+    Runtime, DEFAULT_BIND_ADDRESS, DEFAULT_KILL_SIGNAL,
+};
+use crate::FlockerError;
 
 /// Command line arguments for Flocker
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
     /// Enable verbose output for detailed processing information
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub verbose: bool,
+
+    /// Docker daemon to connect to (e.g. unix:///var/run/docker.sock or tcp://host:2376)
+    #[arg(short = 'H', long, global = true)]
+    pub host: Option<String>,
+
+    /// Container runtime to use (detected from the endpoint if omitted)
+    #[arg(long, value_enum, global = true)]
+    pub runtime: Option<Runtime>,
+
+    /// Image repository to use instead of the configured default (e.g. registry.example.com/team/fluree)
+    #[arg(long, global = true, value_parser = parse_repository)]
+    pub repository: Option<String>,
+
+    /// Output format for non-interactive commands
+    #[arg(short, long, value_enum, global = true, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+
+    /// Command to run non-interactively (starts the interactive menu if omitted)
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+}
+
+/// Options for waiting until a started container accepts requests
+#[derive(Args, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitArgs {
+    /// Seconds to wait for Fluree to accept requests after starting
+    #[arg(long, value_name = "SECONDS", default_value_t = DEFAULT_READY_TIMEOUT_SECS)]
+    pub ready_timeout: u64,
+    /// Return as soon as the container has started, without waiting for Fluree
+    #[arg(long, conflicts_with = "ready_timeout")]
+    pub no_wait: bool,
+}
+
+impl Default for WaitArgs {
+    fn default() -> Self {
+        Self {
+            ready_timeout: DEFAULT_READY_TIMEOUT_SECS,
+            no_wait: false,
+        }
+    }
+}
+
+/// Host port for a new container
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortChoice {
+    /// The first free port from 8090
+    Auto,
+    Port(u16),
+}
+
+impl std::str::FromStr for PortChoice {
+    type Err = FlockerError;
+
+    fn from_str(value: &str) -> crate::Result<Self> {
+        if value.eq_ignore_ascii_case("auto") {
+            return Ok(PortChoice::Auto);
+        }
+        match value.parse() {
+            Ok(port) if port > 0 => Ok(PortChoice::Port(port)),
+            _ => Err(FlockerError::UserInput(format!(
+                "Invalid port '{}': expected a port number or auto",
+                value
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for PortChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PortChoice::Auto => write!(f, "auto"),
+            PortChoice::Port(port) => write!(f, "{}", port),
+        }
+    }
+}
+
+/// Memory, CPU and Java heap limits for a new container
+#[derive(Args, Debug, Clone, Default, PartialEq)]
+pub struct ResourceArgs {
+    /// Memory limit (e.g. 512m or 2g)
+    #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+    pub memory: Option<u64>,
+    /// Number of CPUs the container may use (e.g. 1.5)
+    #[arg(long)]
+    pub cpus: Option<f64>,
+    /// Maximum Java heap size (e.g. 1g), passed to the JVM as -Xmx
+    #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+    pub heap: Option<u64>,
+    /// Environment variable to pass JVM options in [default: JAVA_OPTS]
+    #[arg(long, value_name = "NAME", requires = "heap")]
+    pub java_opts_var: Option<String>,
+}
+
+impl From<ResourceArgs> for ResourceLimits {
+    fn from(args: ResourceArgs) -> Self {
+        Self {
+            memory: args.memory,
+            cpus: args.cpus,
+            java_heap: args.heap,
+            java_opts_var: args.java_opts_var,
+        }
+    }
+}
+
+/// Environment, server arguments and entrypoint for a new container
+#[derive(Args, Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandArgs {
+    /// Environment variable to set, repeatable (NAME alone copies it from this shell)
+    #[arg(short, long, value_name = "NAME=VALUE", value_parser = parse_env)]
+    pub env: Vec<String>,
+    /// Program to run instead of the image's entrypoint
+    #[arg(long, value_name = "PROGRAM")]
+    pub entrypoint: Option<String>,
+    /// Extra arguments for the Fluree server, given after `--`
+    #[arg(last = true, value_name = "SERVER_ARGS")]
+    pub args: Vec<String>,
+}
+
+impl From<CommandArgs> for CommandOptions {
+    fn from(args: CommandArgs) -> Self {
+        Self {
+            env: args.env,
+            args: args.args,
+            entrypoint: args.entrypoint,
+        }
+    }
+}
+
+/// Bind address, Docker networks and extra ports for a new container
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct NetworkArgs {
+    /// Host address to publish ports on (0.0.0.0 exposes Fluree to other machines)
+    #[arg(long, value_name = "ADDRESS", default_value_t = DEFAULT_BIND_ADDRESS)]
+    pub bind: IpAddr,
+    /// Docker network to join, repeatable (created if missing)
+    #[arg(long = "network", value_name = "NAME", value_parser = parse_network_name)]
+    pub networks: Vec<String>,
+    /// Extra name other containers can reach this one by on its networks, repeatable
+    #[arg(
+        long = "network-alias",
+        value_name = "ALIAS",
+        value_parser = parse_network_name,
+        requires = "networks"
+    )]
+    pub aliases: Vec<String>,
+    /// Extra container port to publish, repeatable
+    #[arg(short = 'p', long = "publish", value_name = "HOST_PORT:CONTAINER_PORT")]
+    pub ports: Vec<PortMapping>,
+}
+
+impl Default for NetworkArgs {
+    fn default() -> Self {
+        Self {
+            bind: DEFAULT_BIND_ADDRESS,
+            networks: Vec::new(),
+            aliases: Vec::new(),
+            ports: Vec::new(),
+        }
+    }
+}
+
+impl From<NetworkArgs> for NetworkOptions {
+    fn from(args: NetworkArgs) -> Self {
+        Self {
+            bind_address: args.bind,
+            networks: args
+                .networks
+                .into_iter()
+                .map(|name| NetworkAttachment {
+                    name,
+                    aliases: args.aliases.clone(),
+                })
+                .collect(),
+            ports: args.ports,
+        }
+    }
+}
+
+/// Non-interactive commands, suitable for scripts and CI
+#[derive(Subcommand, Debug)]
+pub enum Commands {
+    /// List known containers and their status
+    Ls,
+    /// List local Fluree images
+    Images,
+    /// Create and start a new container
+    Create {
+        /// Image tag to run (e.g. "latest"), pulled if not available locally
+        #[arg(long)]
+        image: String,
+        /// Name for the new container
+        #[arg(long)]
+        name: String,
+        /// Host port to map to container port 8090, or "auto" for the next free one
+        #[arg(long, default_value_t = PortChoice::Port(DEFAULT_PORT))]
+        port: PortChoice,
+        /// Local directory to mount for data persistence (created if missing)
+        #[arg(long)]
+        data: Option<PathBuf>,
+        /// Fluree server configuration file to use
+        #[arg(long)]
+        config: Option<PathBuf>,
+        #[command(flatten)]
+        resources: ResourceArgs,
+        #[command(flatten)]
+        command: CommandArgs,
+        #[command(flatten)]
+        network: Box<NetworkArgs>,
+        /// When the daemon restarts the container: no, on-failure[:N], unless-stopped or always
+        #[arg(long, value_name = "POLICY", default_value_t = RestartPolicy::No)]
+        restart: RestartPolicy,
+        /// Start the container with `flocker up`
+        #[arg(long)]
+        autostart: bool,
+        #[command(flatten)]
+        wait: WaitArgs,
+    },
+    /// Start a stopped container
+    Start {
+        /// Container name or ID
+        name: String,
+        #[command(flatten)]
+        wait: WaitArgs,
+    },
+    /// Stop a running container
+    Stop {
+        /// Container name or ID
+        name: String,
+        /// Seconds to wait for a graceful shutdown before killing the container
+        #[arg(short, long, value_name = "SECONDS")]
+        timeout: Option<u64>,
+    },
+    /// Stop a container and start it again, e.g. after editing its configuration
+    Restart {
+        /// Container name or ID
+        name: String,
+        /// Seconds to wait for a graceful shutdown before killing the container
+        #[arg(short, long, value_name = "SECONDS")]
+        timeout: Option<u64>,
+        #[command(flatten)]
+        wait: WaitArgs,
+    },
+    /// Suspend all processes in a running container
+    Pause {
+        /// Container name or ID
+        name: String,
+    },
+    /// Resume a paused container
+    Unpause {
+        /// Container name or ID
+        name: String,
+    },
+    /// Send a signal to a container, e.g. when Fluree hangs on shutdown
+    Kill {
+        /// Container name or ID
+        name: String,
+        /// Signal to send, by name (SIGKILL, TERM) or number (9)
+        #[arg(short, long, default_value = DEFAULT_KILL_SIGNAL, value_parser = parse_signal)]
+        signal: String,
+    },
+    /// Start every autostart container and wait until each accepts requests
+    Up {
+        #[command(flatten)]
+        wait: WaitArgs,
+    },
+    /// Mark a container to be started by `flocker up`
+    Autostart {
+        /// Container name or ID
+        name: String,
+        /// Stop starting the container with `flocker up`
+        #[arg(long)]
+        disable: bool,
+    },
+    /// Attach a container to a Docker network, creating the network if needed
+    Connect {
+        /// Container name or ID
+        name: String,
+        /// Network to join
+        #[arg(value_parser = parse_network_name)]
+        network: String,
+        /// Extra name other containers can reach this one by on the network, repeatable
+        #[arg(long = "alias", value_name = "ALIAS", value_parser = parse_network_name)]
+        aliases: Vec<String>,
+    },
+    /// Remove a container
+    Rm {
+        /// Container name or ID
+        name: String,
+    },
+    /// Print container logs
+    Logs {
+        /// Container name or ID
+        name: String,
+        /// Number of lines to show from the end of the logs, or "all"
+        #[arg(long, default_value = "1000")]
+        tail: String,
+        /// Keep streaming new log lines until interrupted
+        #[arg(short, long)]
+        follow: bool,
+        /// Only show logs after this time (e.g. 2024-02-08T21:56:23Z or 15m)
+        #[arg(long, value_parser = parse_time_bound)]
+        since: Option<i64>,
+        /// Only show logs before this time (e.g. 2024-02-08T22:00:00Z or 5m)
+        #[arg(long, value_parser = parse_time_bound)]
+        until: Option<i64>,
+        /// Only show lines at this level or more severe
+        #[arg(long, value_enum)]
+        level: Option<LogLevel>,
+    },
+    /// List ledgers in a running container
+    Ledgers {
+        /// Container name or ID
+        name: String,
+    },
+    /// Show the details of a ledger in a running container
+    Ledger {
+        /// Container name or ID
+        name: String,
+        /// Ledger alias
+        alias: String,
+    },
+    /// Create an empty ledger in a running container
+    CreateLedger {
+        /// Container name or ID
+        name: String,
+        /// Alias for the new ledger
+        alias: String,
+    },
+    /// Drop a ledger in a running container and delete its data
+    DropLedger {
+        /// Container name or ID
+        name: String,
+        /// Ledger alias
+        alias: String,
+    },
+    /// Back up a ledger to a .tar.gz archive
+    Backup {
+        /// Container name or ID
+        name: String,
+        /// Ledger alias
+        alias: String,
+        /// Archive to write (default: <alias>-<time>.tar.gz)
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+    },
+    /// Restore a ledger from a backup archive into a container
+    Restore {
+        /// Container name or ID
+        name: String,
+        /// Archive written by `flocker backup`
+        archive: PathBuf,
+    },
+    /// Copy a ledger to another container, or within one under a new alias
+    CopyLedger {
+        /// Container name or ID to copy from
+        name: String,
+        /// Ledger alias
+        alias: String,
+        /// Container name or ID to copy to
+        target: String,
+    },
+    /// Show resource usage of a running container
+    Stats {
+        /// Container name or ID
+        name: String,
+    },
+    /// Show a live stats dashboard for all running containers
+    Top,
+    /// Show or set the default image repository
+    Repository {
+        /// New default repository (e.g. registry.example.com/team/fluree)
+        #[arg(value_parser = parse_repository)]
+        name: Option<String>,
+        /// Reset the default to fluree/server
+        #[arg(long, conflicts_with = "name")]
+        reset: bool,
+    },
+}
+
+/// Parse an image repository name, rejecting tags and invalid characters
+fn parse_repository(value: &str) -> crate::Result<String> {
+    validate_repository(value)?;
+    Ok(value.to_string())
+}
+
+/// Parse a `NAME=VALUE` environment variable, reading the value of a bare
+/// `NAME` from the current environment
+fn parse_env(value: &str) -> crate::Result<String> {
+    if value.contains('=') {
+        return Ok(value.to_string());
+    }
+    std::env::var(value)
+        .map(|current| format!("{}={}", value, current))
+        .map_err(|_| {
+            FlockerError::UserInput(format!(
+                "Environment variable '{}' has no value here; use NAME=VALUE",
+                value
+            ))
+        })
+}
+
+/// Parse a signal name or number, normalizing names to the `SIGKILL` form
+pub fn parse_signal(value: &str) -> crate::Result<String> {
+    let value = value.trim().to_ascii_uppercase();
+    if let Ok(number) = value.parse::<u8>() {
+        if (1..=64).contains(&number) {
+            return Ok(number.to_string());
+        }
+    }
+    let name = value.strip_prefix("SIG").unwrap_or(&value);
+    let valid = name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-'));
+    if valid {
+        Ok(format!("SIG{}", name))
+    } else {
+        Err(FlockerError::UserInput(format!(
+            "Invalid signal '{}': expected a name like SIGTERM or a number like 9",
+            value
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+    use serial_test::{parallel, serial};
+
+    use super::*;
+
+    #[test]
+    #[parallel]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    #[parallel]
+    fn test_no_subcommand_is_interactive() {
+        let cli = Cli::try_parse_from(["flocker", "--verbose"]).unwrap();
+        assert!(cli.verbose);
+        assert!(cli.command.is_none());
+        assert_eq!(cli.output, OutputFormat::Table);
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_output_format() {
+        let cli = Cli::try_parse_from(["flocker", "ls", "--output", "json"]).unwrap();
+        assert_eq!(cli.output, OutputFormat::Json);
+        assert!(matches!(cli.command, Some(Commands::Ls)));
+
+        let cli = Cli::try_parse_from(["flocker", "-o", "plain", "images"]).unwrap();
+        assert_eq!(cli.output, OutputFormat::Plain);
+
+        assert!(Cli::try_parse_from(["flocker", "ls", "--output", "yaml"]).is_err());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_create() {
+        let cli = Cli::try_parse_from([
+            "flocker", "create", "--image", "latest", "--name", "dev", "--port", "9090", "--data",
+            "./data",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Create {
+                image,
+                name,
+                port,
+                data,
+                config,
+                resources,
+                command,
+                network,
+                restart,
+                autostart,
+                wait,
+            }) => {
+                assert_eq!(image, "latest");
+                assert_eq!(name, "dev");
+                assert_eq!(port, PortChoice::Port(9090));
+                assert_eq!(data, Some(PathBuf::from("./data")));
+                assert!(config.is_none());
+                assert_eq!(resources, ResourceArgs::default());
+                assert_eq!(command, CommandArgs::default());
+                assert_eq!(*network, NetworkArgs::default());
+                assert_eq!(restart, RestartPolicy::No);
+                assert!(!autostart);
+                assert_eq!(wait.ready_timeout, DEFAULT_READY_TIMEOUT_SECS);
+                assert!(!wait.no_wait);
+            }
+            _ => panic!("Expected Create command"),
+        }
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_port_choice() {
+        let port = |value: &str| match Cli::try_parse_from([
+            "flocker", "create", "--image", "latest", "--name", "dev", "--port", value,
+        ])
+        .map(|cli| cli.command)
+        {
+            Ok(Some(Commands::Create { port, .. })) => Some(port),
+            _ => None,
+        };
+        assert_eq!(port("auto"), Some(PortChoice::Auto));
+        assert_eq!(port("AUTO"), Some(PortChoice::Auto));
+        assert_eq!(port("9090"), Some(PortChoice::Port(9090)));
+        assert_eq!(port("0"), None);
+        assert_eq!(port("next"), None);
+        assert_eq!(PortChoice::Port(DEFAULT_PORT).to_string(), "8090");
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_resource_args() {
+        let cli = Cli::try_parse_from([
+            "flocker", "create", "--image", "latest", "--name", "dev", "--memory", "2g", "--cpus",
+            "1.5", "--heap", "1536m",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Create { resources, .. }) => {
+                let limits = ResourceLimits::from(resources);
+                assert_eq!(limits.memory, Some(2 * 1024 * 1024 * 1024));
+                assert_eq!(limits.cpus, Some(1.5));
+                assert_eq!(limits.java_heap, Some(1536 * 1024 * 1024));
+                assert!(limits.java_opts_var.is_none());
+            }
+            _ => panic!("Expected Create command"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "flocker", "create", "--image", "latest", "--name", "dev", "--memory", "lots",
+        ])
+        .is_err());
+        assert!(Cli::try_parse_from([
+            "flocker",
+            "create",
+            "--image",
+            "latest",
+            "--name",
+            "dev",
+            "--java-opts-var",
+            "JAVA_TOOL_OPTIONS",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_parse_command_args() {
+        std::env::set_var("FLOCKER_TEST_PROFILE", "dev");
+        let cli = Cli::try_parse_from([
+            "flocker",
+            "create",
+            "--image",
+            "latest",
+            "--name",
+            "dev",
+            "-e",
+            "FLUREE_LOG_LEVEL=debug",
+            "--env",
+            "FLOCKER_TEST_PROFILE",
+            "--entrypoint",
+            "/bin/sh",
+            "--",
+            "--profile",
+            "prod",
+        ])
+        .unwrap();
+        std::env::remove_var("FLOCKER_TEST_PROFILE");
+        match cli.command {
+            Some(Commands::Create { command, .. }) => {
+                let command = CommandOptions::from(command);
+                assert_eq!(
+                    command.env,
+                    vec!["FLUREE_LOG_LEVEL=debug", "FLOCKER_TEST_PROFILE=dev"]
+                );
+                assert_eq!(command.args, vec!["--profile", "prod"]);
+                assert_eq!(command.entrypoint.as_deref(), Some("/bin/sh"));
+            }
+            _ => panic!("Expected Create command"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "flocker",
+            "create",
+            "--image",
+            "latest",
+            "--name",
+            "dev",
+            "-e",
+            "FLOCKER_TEST_UNSET_VARIABLE",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_restart_and_autostart() {
+        let cli = Cli::try_parse_from([
+            "flocker",
+            "create",
+            "--image",
+            "latest",
+            "--name",
+            "dev",
+            "--restart",
+            "on-failure:3",
+            "--autostart",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Create {
+                restart, autostart, ..
+            }) => {
+                assert_eq!(restart, RestartPolicy::OnFailure(Some(3)));
+                assert!(autostart);
+            }
+            _ => panic!("Expected Create command"),
+        }
+        assert!(Cli::try_parse_from([
+            "flocker",
+            "create",
+            "--image",
+            "latest",
+            "--name",
+            "dev",
+            "--restart",
+            "sometimes",
+        ])
+        .is_err());
+
+        let cli = Cli::try_parse_from(["flocker", "autostart", "dev", "--disable"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Autostart { disable: true, .. })
+        ));
+        let cli = Cli::try_parse_from(["flocker", "up", "--ready-timeout", "30"]).unwrap();
+        match cli.command {
+            Some(Commands::Up { wait }) => assert_eq!(wait.ready_timeout, 30),
+            _ => panic!("Expected Up command"),
+        }
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_network_args() {
+        let cli = Cli::try_parse_from([
+            "flocker",
+            "create",
+            "--image",
+            "latest",
+            "--name",
+            "dev",
+            "--bind",
+            "0.0.0.0",
+            "--network",
+            "app",
+            "--network",
+            "tools",
+            "--network-alias",
+            "fluree",
+            "-p",
+            "9000:9000",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Create { network, .. }) => {
+                let network = NetworkOptions::from(*network);
+                assert!(network.bind_address.is_unspecified());
+                assert_eq!(network.networks.len(), 2);
+                assert_eq!(network.networks[1].name, "tools");
+                assert_eq!(network.networks[1].aliases, vec!["fluree".to_string()]);
+                assert_eq!(network.ports, vec!["9000:9000".parse().unwrap()]);
+            }
+            _ => panic!("Expected Create command"),
+        }
+
+        let create = |extra: &[&str]| {
+            let mut args = vec!["flocker", "create", "--image", "latest", "--name", "dev"];
+            args.extend_from_slice(extra);
+            Cli::try_parse_from(args)
+        };
+        assert!(create(&["--bind", "localhost"]).is_err());
+        assert!(create(&["--network-alias", "fluree"]).is_err());
+        assert!(create(&["--network", "my net"]).is_err());
+        assert!(create(&["--publish", "9000"]).is_err());
+
+        let cli =
+            Cli::try_parse_from(["flocker", "connect", "dev", "app", "--alias", "db"]).unwrap();
+        match cli.command {
+            Some(Commands::Connect {
+                name,
+                network,
+                aliases,
+            }) => {
+                assert_eq!((name.as_str(), network.as_str()), ("dev", "app"));
+                assert_eq!(aliases, vec!["db".to_string()]);
+            }
+            _ => panic!("Expected Connect command"),
+        }
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_wait_args() {
+        let cli =
+            Cli::try_parse_from(["flocker", "start", "dev", "--ready-timeout", "120"]).unwrap();
+        match cli.command {
+            Some(Commands::Start { wait, .. }) => assert_eq!(wait.ready_timeout, 120),
+            _ => panic!("Expected Start command"),
+        }
+
+        let cli = Cli::try_parse_from(["flocker", "start", "dev", "--no-wait"]).unwrap();
+        match cli.command {
+            Some(Commands::Start { wait, .. }) => assert!(wait.no_wait),
+            _ => panic!("Expected Start command"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "flocker",
+            "start",
+            "dev",
+            "--no-wait",
+            "--ready-timeout",
+            "5"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_lifecycle_commands() {
+        let cli = Cli::try_parse_from(["flocker", "stop", "dev", "-t", "30"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Stop {
+                timeout: Some(30),
+                ..
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["flocker", "restart", "dev", "--no-wait"]).unwrap();
+        match cli.command {
+            Some(Commands::Restart { timeout, wait, .. }) => {
+                assert!(timeout.is_none());
+                assert!(wait.no_wait);
+            }
+            _ => panic!("Expected Restart command"),
+        }
+
+        let cli = Cli::try_parse_from(["flocker", "kill", "dev"]).unwrap();
+        match cli.command {
+            Some(Commands::Kill { signal, .. }) => assert_eq!(signal, "SIGKILL"),
+            _ => panic!("Expected Kill command"),
+        }
+        let cli = Cli::try_parse_from(["flocker", "kill", "dev", "-s", "quit"]).unwrap();
+        match cli.command {
+            Some(Commands::Kill { signal, .. }) => assert_eq!(signal, "SIGQUIT"),
+            _ => panic!("Expected Kill command"),
+        }
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_ledger_commands() {
+        let cli = Cli::try_parse_from(["flocker", "create-ledger", "dev", "cookbook"]).unwrap();
+        match cli.command {
+            Some(Commands::CreateLedger { name, alias }) => {
+                assert_eq!((name.as_str(), alias.as_str()), ("dev", "cookbook"));
+            }
+            _ => panic!("Expected CreateLedger command"),
+        }
+        let cli = Cli::try_parse_from(["flocker", "drop-ledger", "dev", "cookbook"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::DropLedger { .. })));
+        let cli = Cli::try_parse_from(["flocker", "ledger", "dev", "cookbook"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Ledger { .. })));
+        assert!(Cli::try_parse_from(["flocker", "drop-ledger", "dev"]).is_err());
+
+        let cli = Cli::try_parse_from(["flocker", "backup", "dev", "cookbook", "-f", "c.tar.gz"])
+            .unwrap();
+        match cli.command {
+            Some(Commands::Backup { file, .. }) => {
+                assert_eq!(file, Some(PathBuf::from("c.tar.gz")));
+            }
+            _ => panic!("Expected Backup command"),
+        }
+        let cli = Cli::try_parse_from(["flocker", "restore", "dev", "c.tar.gz"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Restore { .. })));
+        assert!(Cli::try_parse_from(["flocker", "restore", "dev"]).is_err());
+
+        let cli =
+            Cli::try_parse_from(["flocker", "copy-ledger", "dev", "cookbook", "staging"]).unwrap();
+        match cli.command {
+            Some(Commands::CopyLedger { alias, target, .. }) => {
+                assert_eq!(alias, "cookbook");
+                assert_eq!(target, "staging");
+            }
+            _ => panic!("Expected CopyLedger command"),
+        }
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_signal() {
+        assert_eq!(parse_signal("SIGTERM").unwrap(), "SIGTERM");
+        assert_eq!(parse_signal("term").unwrap(), "SIGTERM");
+        assert_eq!(parse_signal("9").unwrap(), "9");
+        assert_eq!(parse_signal("SIGRTMIN+3").unwrap(), "SIGRTMIN+3");
+        assert!(parse_signal("").is_err());
+        assert!(parse_signal("0").is_err());
+        assert!(parse_signal("SIG KILL").is_err());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_create_requires_image_and_name() {
+        assert!(Cli::try_parse_from(["flocker", "create", "--name", "dev"]).is_err());
+        assert!(Cli::try_parse_from(["flocker", "create", "--image", "latest"]).is_err());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_repository() {
+        let cli = Cli::try_parse_from([
+            "flocker",
+            "--repository",
+            "registry.internal/fluree",
+            "images",
+        ])
+        .unwrap();
+        assert_eq!(cli.repository.as_deref(), Some("registry.internal/fluree"));
+
+        let cli = Cli::try_parse_from(["flocker", "repository", "--reset"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Repository {
+                name: None,
+                reset: true
+            })
+        ));
+        assert!(Cli::try_parse_from(["flocker", "repository", "x/y", "--reset"]).is_err());
+        assert!(Cli::try_parse_from(["flocker", "repository", "fluree/server:latest"]).is_err());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_logs() {
+        let cli = Cli::try_parse_from([
+            "flocker",
+            "logs",
+            "dev",
+            "-f",
+            "--since",
+            "1707393600",
+            "--level",
+            "warn",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Logs {
+                name,
+                tail,
+                follow,
+                since,
+                until,
+                level,
+            }) => {
+                assert_eq!(name, "dev");
+                assert_eq!(tail, "1000");
+                assert!(follow);
+                assert_eq!(since, Some(1707393600));
+                assert!(until.is_none());
+                assert_eq!(level, Some(LogLevel::Warn));
+            }
+            _ => panic!("Expected Logs command"),
+        }
+
+        assert!(Cli::try_parse_from(["flocker", "logs", "dev", "--since", "soon"]).is_err());
+    }
 }
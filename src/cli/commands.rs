@@ -0,0 +1,875 @@
+//! Non-interactive command handling.
+//!
+//! This module runs the subcommands parsed in `args` directly against
+//! Docker and the persisted state, without prompting the user.
+
+use console::style;
+use futures_util::StreamExt;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::{
+    cli::{format_bytes, format_duration_since},
+    config::FlureeConfig,
+    docker::{
+        ports::{check_ports, next_free_port, DEFAULT_PORT},
+        readiness::{readiness_url, wait_until_ready, Probe, Readiness},
+        CommandOptions, DockerOperations, FlureeImage, LogOptions, NetworkAttachment,
+        NetworkOptions, ResourceLimits,
+    },
+    fluree::{
+        backup::{backup_ledger, default_backup_file, restore_ledger},
+        copy::copy_ledger,
+        HostLedgerReader, LedgerOperations,
+    },
+    state::{ContainerInfo, State},
+    ContainerStatus, FlockerError, Result,
+};
+
+use super::{
+    args::{Commands, PortChoice, WaitArgs},
+    dashboard,
+    logs::{passes_filter, LevelTracker, LogLevel},
+    output::{
+        branch_row, print_json, print_rows, stats_row, ContainerListing, OutputFormat,
+        BRANCH_HEADERS, STATS_HEADERS,
+    },
+    progress,
+};
+
+/// Run a single non-interactive command
+///
+/// `repository` overrides the default image repository from the state.
+pub async fn run(
+    command: Commands,
+    output: OutputFormat,
+    repository: Option<&str>,
+    docker: &impl DockerOperations,
+) -> Result<()> {
+    let mut state = State::load()?;
+    let repository = repository.unwrap_or(state.image_repository()).to_string();
+
+    match command {
+        Commands::Ls => list_containers(docker, &state, output).await,
+        Commands::Images => list_images(docker, &repository, output).await,
+        Commands::Create {
+            image,
+            name,
+            port,
+            data,
+            config,
+            resources,
+            command,
+            network,
+            restart,
+            autostart,
+            wait,
+        } => {
+            if state.containers.values().any(|c| c.name == name) {
+                return Err(FlockerError::Config(format!(
+                    "Container name '{}' is already in use",
+                    name
+                )));
+            }
+            let network = NetworkOptions::from(*network);
+            let port = match port {
+                PortChoice::Port(port) => port,
+                PortChoice::Auto => {
+                    next_free_port(docker, &state, network.bind_address, DEFAULT_PORT).await?
+                }
+            };
+            let fluree_config = fluree_config(
+                port,
+                data.as_deref(),
+                config.as_deref(),
+                resources.into(),
+                command.into(),
+                network,
+            )?
+            .with_restart(restart, autostart);
+            check_ports(docker, &state, &fluree_config).await?;
+            let since = chrono::Utc::now().timestamp();
+            let info = create_container(
+                docker,
+                &mut state,
+                &repository,
+                &image,
+                &name,
+                fluree_config,
+                output,
+            )
+            .await?;
+            let ready = wait_for_ready(docker, &info, wait, since, output).await?;
+            let message = format!(
+                "Created {} ({}) at {}{}",
+                style(&info.name).cyan(),
+                &info.id[..12.min(info.id.len())],
+                style(
+                    info.url(docker.remote_host().as_deref())
+                        .unwrap_or_else(|| format!("port {}", info.port))
+                )
+                .underlined(),
+                ready_suffix(ready)
+            );
+            report(output, &info, &message)
+        }
+        Commands::Start { name, wait } => {
+            let container = find_container(&state, &name)?.clone();
+            let now = chrono::Utc::now();
+            docker.start_container(&container.id).await?;
+            state.update_container_start_time(&container.id, now.to_rfc3339())?;
+            let ready = wait_for_ready(docker, &container, wait, now.timestamp(), output).await?;
+            let container = find_container(&state, &name)?;
+            report(
+                output,
+                container,
+                &format!("Started {}{}", container.name, ready_suffix(ready)),
+            )
+        }
+        Commands::Stop { name, timeout } => {
+            let container = find_container(&state, &name)?;
+            docker.stop_container(&container.id, timeout).await?;
+            report(output, container, &format!("Stopped {}", container.name))
+        }
+        Commands::Restart {
+            name,
+            timeout,
+            wait,
+        } => {
+            let container = find_container(&state, &name)?.clone();
+            let now = chrono::Utc::now();
+            docker.restart_container(&container.id, timeout).await?;
+            state.update_container_start_time(&container.id, now.to_rfc3339())?;
+            let ready = wait_for_ready(docker, &container, wait, now.timestamp(), output).await?;
+            let container = find_container(&state, &name)?;
+            report(
+                output,
+                container,
+                &format!("Restarted {}{}", container.name, ready_suffix(ready)),
+            )
+        }
+        Commands::Pause { name } => {
+            let container = find_container(&state, &name)?;
+            docker.pause_container(&container.id).await?;
+            report(output, container, &format!("Paused {}", container.name))
+        }
+        Commands::Unpause { name } => {
+            let container = find_container(&state, &name)?;
+            docker.unpause_container(&container.id).await?;
+            report(output, container, &format!("Unpaused {}", container.name))
+        }
+        Commands::Kill { name, signal } => {
+            let container = find_container(&state, &name)?;
+            docker.kill_container(&container.id, &signal).await?;
+            report(
+                output,
+                container,
+                &format!("Sent {} to {}", signal, container.name),
+            )
+        }
+        Commands::Up { wait } => up(docker, &mut state, wait, output).await,
+        Commands::Autostart { name, disable } => {
+            let container = find_container(&state, &name)?.clone();
+            state.set_autostart(&container.id, !disable)?;
+            let message = if disable {
+                format!("{} will no longer be started by flocker up", container.name)
+            } else {
+                format!("{} will be started by flocker up", container.name)
+            };
+            let container = find_container(&state, &name)?;
+            report(output, container, &message)
+        }
+        Commands::Connect {
+            name,
+            network,
+            aliases,
+        } => {
+            let container = find_container(&state, &name)?.clone();
+            let attachment = NetworkAttachment {
+                name: network,
+                aliases,
+            };
+            docker.connect_network(&container.id, &attachment).await?;
+            state.add_network(&container.id, attachment.clone())?;
+            let container = find_container(&state, &name)?;
+            report(
+                output,
+                container,
+                &format!("Connected {} to {}", container.name, attachment.name),
+            )
+        }
+        Commands::Rm { name } => {
+            let container = find_container(&state, &name)?.clone();
+            docker.remove_container(&container.id).await?;
+            state.remove_container(&container.id)?;
+            report(output, &container, &format!("Removed {}", container.name))
+        }
+        Commands::Logs {
+            name,
+            tail,
+            follow,
+            since,
+            until,
+            level,
+        } => {
+            let container = find_container(&state, &name)?;
+            let options = LogOptions { tail, since, until };
+            show_logs(docker, &container.id, &options, follow, level, output).await
+        }
+        Commands::Ledgers { name } => {
+            let container = find_container(&state, &name)?;
+            list_ledgers(docker, container, output).await
+        }
+        Commands::Ledger { name, alias } => {
+            let container = find_container(&state, &name)?;
+            let details = match stopped_ledger_reader(docker, container).await? {
+                Some(reader) => reader.details(&alias)?,
+                None => {
+                    LedgerOperations::new(docker, container)
+                        .details(&alias)
+                        .await?
+                }
+            };
+            println!("{}", details);
+            Ok(())
+        }
+        Commands::CreateLedger { name, alias } => {
+            let container = find_container(&state, &name)?;
+            ensure_running(docker, container).await?;
+            LedgerOperations::new(docker, container)
+                .create(&alias)
+                .await?;
+            report(
+                output,
+                container,
+                &format!("Created ledger {} in {}", alias, container.name),
+            )
+        }
+        Commands::DropLedger { name, alias } => {
+            let container = find_container(&state, &name)?;
+            ensure_running(docker, container).await?;
+            LedgerOperations::new(docker, container)
+                .drop_ledger(&alias)
+                .await?;
+            report(
+                output,
+                container,
+                &format!("Dropped ledger {} from {}", alias, container.name),
+            )
+        }
+        Commands::Backup { name, alias, file } => {
+            let container = find_container(&state, &name)?;
+            let file = file.unwrap_or_else(|| default_backup_file(&alias));
+            let manifest = backup_ledger(docker, container, &alias, &file).await?;
+            match output {
+                OutputFormat::Json => print_json(&manifest)?,
+                OutputFormat::Table => println!(
+                    "Backed up ledger {} ({} files, {}) to {}",
+                    alias,
+                    manifest.files.len(),
+                    format_bytes(manifest.size()),
+                    file.display()
+                ),
+                OutputFormat::Plain => println!("{}", file.display()),
+            }
+            Ok(())
+        }
+        Commands::Restore { name, archive } => {
+            let container = find_container(&state, &name)?;
+            let manifest = restore_ledger(docker, container, &archive).await?;
+            match output {
+                OutputFormat::Json => print_json(&manifest)?,
+                OutputFormat::Table => println!(
+                    "Restored ledger {} from {} into {}",
+                    manifest.alias,
+                    archive.display(),
+                    container.name
+                ),
+                OutputFormat::Plain => println!("{}", manifest.alias),
+            }
+            Ok(())
+        }
+        Commands::CopyLedger {
+            name,
+            alias,
+            target,
+        } => {
+            let source = find_container(&state, &name)?;
+            let target = find_container(&state, &target)?;
+            let copied = copy_ledger(docker, source, &alias, target).await?;
+            match output {
+                OutputFormat::Json => print_json(&copied)?,
+                OutputFormat::Table => println!(
+                    "Copied ledger {} from {} to {} (commit {})",
+                    alias, source.name, target.name, copied.commit_count
+                ),
+                OutputFormat::Plain => println!("{}", copied.alias),
+            }
+            Ok(())
+        }
+        Commands::Stats { name } => {
+            let container = find_container(&state, &name)?;
+            let stats = docker.get_container_stats(&container.id).await?;
+            print_rows(output, &STATS_HEADERS, &[stats_row(&stats)], &stats)
+        }
+        Commands::Top => {
+            let containers = running_containers(docker, &state).await?;
+            dashboard::run(docker, containers).await
+        }
+        Commands::Repository { name, reset } => {
+            if reset {
+                state.set_image_repository(None)?;
+            } else if let Some(name) = name {
+                state.set_image_repository(Some(name))?;
+            }
+            let repository = state.image_repository();
+            match output {
+                OutputFormat::Json => print_json(&serde_json::json!({ "repository": repository })),
+                OutputFormat::Table => {
+                    println!("Default image repository: {}", style(repository).cyan());
+                    Ok(())
+                }
+                OutputFormat::Plain => {
+                    println!("{}", repository);
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+/// Report the outcome of a single-container command
+fn report(output: OutputFormat, container: &ContainerInfo, message: &str) -> Result<()> {
+    match output {
+        OutputFormat::Json => print_json(container)?,
+        OutputFormat::Table => println!("{}", message),
+        OutputFormat::Plain => println!("{}", container.id),
+    }
+    Ok(())
+}
+
+/// Wait until a started container accepts requests
+///
+/// In table mode the startup logs are streamed to stderr while waiting.
+/// Returns how long startup took, or `None` when waiting was disabled.
+pub async fn wait_for_ready(
+    docker: &impl DockerOperations,
+    container: &ContainerInfo,
+    wait: WaitArgs,
+    since: i64,
+    output: OutputFormat,
+) -> Result<Option<Duration>> {
+    wait_for_container(
+        docker,
+        container,
+        wait,
+        since,
+        output == OutputFormat::Table,
+    )
+    .await
+}
+
+/// Wait until a started container accepts requests, optionally streaming its logs
+async fn wait_for_container(
+    docker: &impl DockerOperations,
+    container: &ContainerInfo,
+    wait: WaitArgs,
+    since: i64,
+    show_logs: bool,
+) -> Result<Option<Duration>> {
+    if wait.no_wait {
+        return Ok(None);
+    }
+
+    if show_logs {
+        eprintln!(
+            "{}",
+            style(format!(
+                "Waiting for Fluree to accept requests on port {}...",
+                container.port
+            ))
+            .cyan()
+        );
+    }
+    // A remote daemon's published ports may not be reachable from here
+    let probe = match container.url(docker.remote_host().as_deref()) {
+        Some(url) if docker.remote_host().is_none() => Probe::Http(readiness_url(&url)),
+        _ => Probe::Exec,
+    };
+    let readiness = wait_until_ready(
+        docker,
+        &container.id,
+        &probe,
+        Duration::from_secs(wait.ready_timeout),
+        Some(since),
+        |line| {
+            if show_logs {
+                eprintln!("  {}", style(line).dim());
+            }
+        },
+    )
+    .await?;
+
+    let last_logs = |lines: &[String]| {
+        if lines.is_empty() {
+            String::new()
+        } else {
+            format!("\nLast log lines:\n  {}", lines.join("\n  "))
+        }
+    };
+    match readiness {
+        Readiness::Ready { elapsed } => Ok(Some(elapsed)),
+        Readiness::TimedOut {
+            timeout,
+            last_logs: lines,
+        } => Err(FlockerError::Docker(format!(
+            "{} did not accept requests within {}s{}",
+            container.name,
+            timeout.as_secs(),
+            last_logs(&lines)
+        ))),
+        Readiness::Exited {
+            exit_code,
+            last_logs: lines,
+        } => Err(FlockerError::Docker(format!(
+            "{} exited during startup with code {}{}",
+            container.name,
+            exit_code.map_or("unknown".to_string(), |code| code.to_string()),
+            last_logs(&lines)
+        ))),
+    }
+}
+
+/// Column headers for `flocker up` results
+const UP_HEADERS: [&str; 4] = ["NAME", "ID", "RESULT", "DETAIL"];
+
+/// Result of starting one container with `flocker up`
+#[derive(Debug, Serialize)]
+struct UpResult {
+    name: String,
+    id: String,
+    /// `started`, `running` if it already was, or `failed`
+    result: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ready_seconds: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Start every autostart container in parallel and wait for each to be ready
+///
+/// Containers that are already running are left alone. Fails if any
+/// container couldn't be started or didn't become ready.
+async fn up(
+    docker: &impl DockerOperations,
+    state: &mut State,
+    wait: WaitArgs,
+    output: OutputFormat,
+) -> Result<()> {
+    let containers: Vec<ContainerInfo> =
+        state.autostart_containers().into_iter().cloned().collect();
+    if containers.is_empty() {
+        return match output {
+            OutputFormat::Table => {
+                println!(
+                    "No containers are set to autostart; enable one with flocker autostart <name>"
+                );
+                Ok(())
+            }
+            _ => print_rows(output, &UP_HEADERS, &[], &Vec::<UpResult>::new()),
+        };
+    }
+    if output == OutputFormat::Table {
+        eprintln!(
+            "{}",
+            style(format!("Starting {} containers...", containers.len())).cyan()
+        );
+    }
+
+    let now = chrono::Utc::now();
+    let outcomes = futures_util::future::join_all(
+        containers
+            .iter()
+            .map(|container| start_and_wait(docker, container, wait, now.timestamp())),
+    )
+    .await;
+
+    let mut results = Vec::new();
+    for (container, outcome) in containers.iter().zip(outcomes) {
+        let (result, ready, error) = match outcome {
+            Ok(Some(ready)) => {
+                state.update_container_start_time(&container.id, now.to_rfc3339())?;
+                ("started", ready, None)
+            }
+            Ok(None) => ("running", None, None),
+            Err(e) => ("failed", None, Some(e.to_string())),
+        };
+        results.push(UpResult {
+            name: container.name.clone(),
+            id: container.id.clone(),
+            result,
+            ready_seconds: ready.map(|elapsed| elapsed.as_secs_f64()),
+            error,
+        });
+    }
+
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .map(|result| {
+            let detail = match (&result.error, result.ready_seconds) {
+                (Some(error), _) => error.clone(),
+                (None, Some(seconds)) => format!("ready in {:.1}s", seconds),
+                (None, None) => String::new(),
+            };
+            vec![
+                result.name.clone(),
+                result.id[..12.min(result.id.len())].to_string(),
+                result.result.to_string(),
+                detail,
+            ]
+        })
+        .collect();
+    print_rows(output, &UP_HEADERS, &rows, &results)?;
+
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    if failed > 0 {
+        return Err(FlockerError::Docker(format!(
+            "{} of {} containers failed to start",
+            failed,
+            results.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Start a container unless it is running, then wait for it to be ready
+///
+/// Returns `None` if the container was already running, and otherwise how
+/// long startup took, which is also `None` when waiting is disabled.
+async fn start_and_wait(
+    docker: &impl DockerOperations,
+    container: &ContainerInfo,
+    wait: WaitArgs,
+    since: i64,
+) -> Result<Option<Option<Duration>>> {
+    match docker.get_container_status(&container.id).await? {
+        ContainerStatus::Running { .. } => return Ok(None),
+        ContainerStatus::Paused { .. } => docker.unpause_container(&container.id).await?,
+        ContainerStatus::NotFound => {
+            return Err(FlockerError::Docker(format!(
+                "Container {} no longer exists",
+                container.name
+            )))
+        }
+        _ => docker.start_container(&container.id).await?,
+    }
+    wait_for_container(docker, container, wait, since, false)
+        .await
+        .map(Some)
+}
+
+/// Describe how long startup took, for success messages
+fn ready_suffix(ready: Option<Duration>) -> String {
+    ready
+        .map(|elapsed| format!(" (ready in {:.1}s)", elapsed.as_secs_f64()))
+        .unwrap_or_default()
+}
+
+/// Get the `(name, id)` of every known container that is currently running
+pub async fn running_containers(
+    docker: &impl DockerOperations,
+    state: &State,
+) -> Result<Vec<(String, String)>> {
+    let mut running = Vec::new();
+    for container in state.get_containers() {
+        if let ContainerStatus::Running { .. } = docker.get_container_status(&container.id).await? {
+            running.push((container.name.clone(), container.id.clone()));
+        }
+    }
+    Ok(running)
+}
+
+/// Look up a container in state by name or ID
+fn find_container<'a>(state: &'a State, name: &str) -> Result<&'a ContainerInfo> {
+    state
+        .find_container(name)
+        .ok_or_else(|| FlockerError::Config(format!("No container named '{}' found", name)))
+}
+
+/// Resolve a possibly relative path against the current directory
+fn resolve_path(path: &Path) -> Result<PathBuf> {
+    if path.is_absolute() {
+        Ok(path.to_path_buf())
+    } else {
+        Ok(std::env::current_dir()?.join(path))
+    }
+}
+
+async fn list_containers(
+    docker: &impl DockerOperations,
+    state: &State,
+    output: OutputFormat,
+) -> Result<()> {
+    let mut listings = Vec::new();
+    for container in state.get_containers() {
+        let status = docker.get_container_status(&container.id).await?;
+        listings.push(ContainerListing {
+            info: container.clone(),
+            status,
+        });
+    }
+
+    if listings.is_empty() && output == OutputFormat::Table {
+        println!("No containers found");
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = listings
+        .iter()
+        .map(|listing| {
+            let container = &listing.info;
+            let last_start = container
+                .last_start
+                .as_ref()
+                .and_then(|t| format_duration_since(t).ok())
+                .unwrap_or_else(|| "Never".to_string());
+            vec![
+                container.name.clone(),
+                container.id.chars().take(12).collect(),
+                listing.status.style(None),
+                container.image_tag.clone(),
+                container.port.to_string(),
+                last_start,
+            ]
+        })
+        .collect();
+
+    print_rows(
+        output,
+        &["NAME", "ID", "STATUS", "IMAGE", "PORT", "LAST STARTED"],
+        &rows,
+        &listings,
+    )
+}
+
+async fn list_images(
+    docker: &impl DockerOperations,
+    repository: &str,
+    output: OutputFormat,
+) -> Result<()> {
+    let images = docker.list_local_images(repository).await?;
+
+    if images.is_empty() && output == OutputFormat::Table {
+        println!("No local {} images found", repository);
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = images
+        .iter()
+        .map(|image| {
+            vec![
+                image.tag.name.clone(),
+                image
+                    .id
+                    .trim_start_matches("sha256:")
+                    .chars()
+                    .take(12)
+                    .collect(),
+                image.created.to_rfc3339(),
+                format_bytes(image.size),
+            ]
+        })
+        .collect();
+
+    print_rows(output, &["IMAGE", "ID", "CREATED", "SIZE"], &rows, &images)
+}
+
+/// Fail unless a container is running
+async fn ensure_running(docker: &impl DockerOperations, container: &ContainerInfo) -> Result<()> {
+    let status = docker.get_container_status(&container.id).await?;
+    if !matches!(status, ContainerStatus::Running { .. }) {
+        return Err(not_running(container));
+    }
+    Ok(())
+}
+
+/// Reader for the host data directory of a container that isn't running
+///
+/// Returns `None` if the container is running, and fails if it isn't and
+/// has no data directory on this machine.
+async fn stopped_ledger_reader(
+    docker: &impl DockerOperations,
+    container: &ContainerInfo,
+) -> Result<Option<HostLedgerReader>> {
+    let status = docker.get_container_status(&container.id).await?;
+    if matches!(status, ContainerStatus::Running { .. }) {
+        return Ok(None);
+    }
+    HostLedgerReader::for_container(container)
+        .map(Some)
+        .ok_or_else(|| not_running(container))
+}
+
+fn not_running(container: &ContainerInfo) -> FlockerError {
+    FlockerError::Docker(format!("Container '{}' is not running", container.name))
+}
+
+async fn list_ledgers(
+    docker: &impl DockerOperations,
+    container: &ContainerInfo,
+    output: OutputFormat,
+) -> Result<()> {
+    let mut ledgers = match stopped_ledger_reader(docker, container).await? {
+        Some(reader) => reader.list()?,
+        None => LedgerOperations::new(docker, container).list().await?,
+    };
+    if ledgers.is_empty() && output == OutputFormat::Table {
+        println!("No ledgers found");
+        return Ok(());
+    }
+    ledgers.sort_by(|a, b| b.last_commit_time.cmp(&a.last_commit_time));
+
+    // One row per branch
+    let rows: Vec<Vec<String>> = ledgers
+        .iter()
+        .flat_map(|ledger| {
+            ledger.branches.iter().map(|branch| {
+                let mut row = vec![ledger.alias.clone()];
+                row.extend(branch_row(branch));
+                row
+            })
+        })
+        .collect();
+
+    let mut headers = vec!["ALIAS"];
+    headers.extend(BRANCH_HEADERS);
+    print_rows(output, &headers, &rows, &ledgers)
+}
+
+async fn show_logs(
+    docker: &impl DockerOperations,
+    container_id: &str,
+    options: &LogOptions,
+    follow: bool,
+    level: Option<LogLevel>,
+    output: OutputFormat,
+) -> Result<()> {
+    let mut tracker = LevelTracker::default();
+
+    if !follow {
+        let logs = docker.get_container_logs(container_id, options).await?;
+        let lines: Vec<&str> = logs
+            .lines()
+            .filter(|line| passes_filter(tracker.level_of(line), level))
+            .collect();
+        return match output {
+            OutputFormat::Json => print_json(&lines),
+            OutputFormat::Table | OutputFormat::Plain => {
+                for line in lines {
+                    println!("{}", line);
+                }
+                Ok(())
+            }
+        };
+    }
+
+    // Followed logs are printed as they arrive; JSON output is one string per line
+    let mut lines = docker.follow_container_logs(container_id, options);
+    while let Some(line) = lines.next().await {
+        let line = line?;
+        if !passes_filter(tracker.level_of(&line), level) {
+            continue;
+        }
+        match output {
+            OutputFormat::Json => println!(
+                "{}",
+                serde_json::to_string(&line).map_err(|e| FlockerError::Config(format!(
+                    "Failed to serialize output: {}",
+                    e
+                )))?
+            ),
+            OutputFormat::Table | OutputFormat::Plain => println!("{}", line),
+        }
+    }
+    Ok(())
+}
+
+/// Build and validate the server configuration from command line options
+fn fluree_config(
+    port: u16,
+    data: Option<&Path>,
+    config: Option<&Path>,
+    resources: ResourceLimits,
+    command: CommandOptions,
+    network: NetworkOptions,
+) -> Result<FlureeConfig> {
+    let data_mount = match data {
+        Some(path) => {
+            let path = resolve_path(path)?;
+            if !path.exists() {
+                std::fs::create_dir_all(&path)?;
+            }
+            Some(path.canonicalize()?)
+        }
+        None => None,
+    };
+
+    let (config_mount, config_file) = match config {
+        Some(path) => {
+            let path = resolve_path(path)?;
+            if !path.is_file() {
+                return Err(FlockerError::Config(format!(
+                    "Config file does not exist: {}",
+                    path.display()
+                )));
+            }
+            let dir = path.parent().map(Path::to_path_buf);
+            let file = path.file_name().map(PathBuf::from);
+            (dir, file)
+        }
+        None => (None, None),
+    };
+
+    let fluree_config = FlureeConfig::new(port, data_mount, config_mount, config_file)
+        .with_resources(resources)
+        .with_command(command)
+        .with_network(network);
+    fluree_config.validate()?;
+    Ok(fluree_config)
+}
+
+async fn create_container(
+    docker: &impl DockerOperations,
+    state: &mut State,
+    repository: &str,
+    tag: &str,
+    name: &str,
+    fluree_config: FlureeConfig,
+    output: OutputFormat,
+) -> Result<ContainerInfo> {
+    let image = ensure_image(docker, repository, tag, output).await?;
+    let autostart = fluree_config.autostart;
+    let mut info = docker
+        .create_and_start_container(&image.tag, &fluree_config.into_docker_config(), name)
+        .await?;
+    info.autostart = autostart;
+    state.add_container(info.clone())?;
+    Ok(info)
+}
+
+/// Get a local image by tag, pulling it first if it is not present
+async fn ensure_image(
+    docker: &impl DockerOperations,
+    repository: &str,
+    tag: &str,
+    output: OutputFormat,
+) -> Result<FlureeImage> {
+    match docker.get_image_by_tag(repository, tag).await {
+        Ok(image) => Ok(image),
+        Err(_) => {
+            progress::pull_image(docker, repository, tag, output).await?;
+            docker.get_image_by_tag(repository, tag).await
+        }
+    }
+}
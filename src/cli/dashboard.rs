@@ -0,0 +1,357 @@
+//! Live resource usage dashboard.
+//!
+//! This module keeps a stats stream open for each running container and
+//! redraws CPU, memory, network and block IO side by side every second,
+//! with a short sparkline history, until the user presses 'q'.
+
+use console::style;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    execute, queue,
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use futures_util::stream::{self, StreamExt};
+use std::collections::VecDeque;
+use std::io::{stdout, Write};
+use std::time::{Duration, Instant};
+
+use super::{format_bytes, terminal::get_terminal_width};
+use crate::{
+    docker::{ContainerStats, DockerOperations},
+    FlockerError, Result,
+};
+
+/// Number of samples kept for sparklines
+const HISTORY_LEN: usize = 60;
+
+/// Narrowest panel that still fits its labels
+const MIN_PANEL_WIDTH: usize = 30;
+
+/// Characters used to draw sparklines, from lowest to highest
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Render a sparkline of the last `width` values, scaled to `max`
+pub fn sparkline(values: &VecDeque<f64>, max: f64, width: usize) -> String {
+    let skip = values.len().saturating_sub(width);
+    values
+        .iter()
+        .skip(skip)
+        .map(|value| {
+            let ratio = if max > 0.0 {
+                (value / max).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let index = (ratio * (SPARK_CHARS.len() - 1) as f64).round() as usize;
+            SPARK_CHARS[index]
+        })
+        .collect()
+}
+
+/// Live state for a single container column
+struct Panel {
+    name: String,
+    id: String,
+    latest: Option<ContainerStats>,
+    received_at: Option<Instant>,
+    /// Network rx/tx and block read/write rates in bytes per second
+    rates: [f64; 4],
+    cpu_history: VecDeque<f64>,
+    mem_history: VecDeque<f64>,
+    error: Option<String>,
+}
+
+impl Panel {
+    fn new(name: String, id: String) -> Self {
+        Self {
+            name,
+            id,
+            latest: None,
+            received_at: None,
+            rates: [0.0; 4],
+            cpu_history: VecDeque::with_capacity(HISTORY_LEN),
+            mem_history: VecDeque::with_capacity(HISTORY_LEN),
+            error: None,
+        }
+    }
+
+    fn update(&mut self, stats: ContainerStats) {
+        let now = Instant::now();
+        if let (Some(previous), Some(at)) = (&self.latest, self.received_at) {
+            let elapsed = now.duration_since(at).as_secs_f64().max(0.001);
+            let rate = |current: u64, before: u64| current.saturating_sub(before) as f64 / elapsed;
+            self.rates = [
+                rate(stats.network_rx, previous.network_rx),
+                rate(stats.network_tx, previous.network_tx),
+                rate(stats.block_read, previous.block_read),
+                rate(stats.block_write, previous.block_write),
+            ];
+        }
+
+        for (history, value) in [
+            (&mut self.cpu_history, stats.cpu_percent),
+            (&mut self.mem_history, stats.memory_percent()),
+        ] {
+            if history.len() == HISTORY_LEN {
+                history.pop_front();
+            }
+            history.push_back(value);
+        }
+
+        self.latest = Some(stats);
+        self.received_at = Some(now);
+        self.error = None;
+    }
+
+    /// Render the panel as lines of exactly `width` visible characters
+    fn render(&self, width: usize) -> Vec<String> {
+        let inner = width.saturating_sub(2);
+        let fit = |text: String| -> String {
+            let text: String = text.chars().take(inner).collect();
+            format!("{:<inner$}", text)
+        };
+
+        let mut lines = vec![
+            style(fit(self.name.clone())).cyan().bold().to_string(),
+            style(fit(self.id.chars().take(12).collect()))
+                .dim()
+                .to_string(),
+            fit(String::new()),
+        ];
+
+        if let Some(error) = &self.error {
+            lines.push(style(fit(error.clone())).red().to_string());
+            return lines.into_iter().map(|l| format!("{}  ", l)).collect();
+        }
+
+        let Some(stats) = &self.latest else {
+            lines.push(
+                style(fit("Waiting for stats...".to_string()))
+                    .yellow()
+                    .to_string(),
+            );
+            return lines.into_iter().map(|l| format!("{}  ", l)).collect();
+        };
+
+        let cpu_max = self.cpu_history.iter().copied().fold(100.0, f64::max);
+        lines.push(fit(format!("CPU   {:.2}%", stats.cpu_percent)));
+        lines.push(
+            style(fit(sparkline(&self.cpu_history, cpu_max, inner)))
+                .green()
+                .to_string(),
+        );
+        lines.push(fit(format!(
+            "MEM   {} / {} ({:.1}%)",
+            format_bytes(stats.memory_usage),
+            format_bytes(stats.memory_limit),
+            stats.memory_percent()
+        )));
+        lines.push(
+            style(fit(sparkline(&self.mem_history, 100.0, inner)))
+                .blue()
+                .to_string(),
+        );
+        lines.push(fit(format!(
+            "NET   rx {}/s  tx {}/s",
+            format_bytes(self.rates[0] as u64),
+            format_bytes(self.rates[1] as u64)
+        )));
+        lines.push(
+            style(fit(format!(
+                "      total {} / {}",
+                format_bytes(stats.network_rx),
+                format_bytes(stats.network_tx)
+            )))
+            .dim()
+            .to_string(),
+        );
+        lines.push(fit(format!(
+            "BLOCK r {}/s  w {}/s",
+            format_bytes(self.rates[2] as u64),
+            format_bytes(self.rates[3] as u64)
+        )));
+        lines.push(
+            style(fit(format!(
+                "      total {} / {}",
+                format_bytes(stats.block_read),
+                format_bytes(stats.block_write)
+            )))
+            .dim()
+            .to_string(),
+        );
+        lines.push(fit(format!("PIDS  {}", stats.pids)));
+
+        lines.into_iter().map(|l| format!("{}  ", l)).collect()
+    }
+}
+
+/// Restores the terminal when the dashboard exits, even on error
+struct ScreenGuard;
+
+impl ScreenGuard {
+    fn enter() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(stdout(), EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for ScreenGuard {
+    fn drop(&mut self) {
+        let _ = execute!(stdout(), cursor::Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Run the live dashboard for the given `(name, id)` containers until 'q' is pressed
+pub async fn run(docker: &impl DockerOperations, containers: Vec<(String, String)>) -> Result<()> {
+    if containers.is_empty() {
+        return Err(FlockerError::Docker(
+            "No running containers to monitor".to_string(),
+        ));
+    }
+
+    let mut panels: Vec<Panel> = containers
+        .iter()
+        .map(|(name, id)| Panel::new(name.clone(), id.clone()))
+        .collect();
+
+    let mut updates = stream::select_all(containers.iter().enumerate().map(|(index, (_, id))| {
+        docker
+            .stream_container_stats(id)
+            .map(move |result| (index, result))
+    }));
+
+    let _guard = ScreenGuard::enter()?;
+    let mut redraw = tokio::time::interval(Duration::from_secs(1));
+    let mut input = tokio::time::interval(Duration::from_millis(50));
+
+    loop {
+        tokio::select! {
+            Some((index, result)) = updates.next() => match result {
+                Ok(stats) => panels[index].update(stats),
+                Err(e) => panels[index].error = Some(e.to_string()),
+            },
+            _ = redraw.tick() => draw(&panels)?,
+            _ = input.tick() => {
+                if quit_requested()? {
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check for a pending 'q' or Ctrl-C key press without blocking
+fn quit_requested() -> Result<bool> {
+    while event::poll(Duration::ZERO)? {
+        if let Event::Key(KeyEvent {
+            code, modifiers, ..
+        }) = event::read()?
+        {
+            match code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(true),
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => return Ok(true),
+                _ => {}
+            }
+        }
+    }
+    Ok(false)
+}
+
+fn draw(panels: &[Panel]) -> Result<()> {
+    let term_width = get_terminal_width() as usize;
+    let visible = (term_width / MIN_PANEL_WIDTH).clamp(1, panels.len());
+    let panel_width = term_width / visible;
+
+    let columns: Vec<Vec<String>> = panels
+        .iter()
+        .take(visible)
+        .map(|panel| panel.render(panel_width))
+        .collect();
+    let height = columns.iter().map(Vec::len).max().unwrap_or_default();
+
+    let mut out = stdout();
+    queue!(out, cursor::MoveTo(0, 0), Clear(ClearType::All))?;
+    write!(
+        out,
+        "{}\r\n\r\n",
+        style("Live container stats (press 'q' to quit)").bold()
+    )?;
+
+    for row in 0..height {
+        for column in &columns {
+            match column.get(row) {
+                Some(line) => write!(out, "{}", line)?,
+                None => write!(out, "{:width$}", "", width = panel_width)?,
+            }
+        }
+        write!(out, "\r\n")?;
+    }
+
+    if visible < panels.len() {
+        write!(
+            out,
+            "\r\n{}",
+            style(format!(
+                "{} more container(s) hidden; widen the terminal to see them",
+                panels.len() - visible
+            ))
+            .yellow()
+        )?;
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+
+    use super::*;
+
+    #[test]
+    #[parallel]
+    fn test_sparkline_scaling() {
+        let values: VecDeque<f64> = vec![0.0, 50.0, 100.0, 150.0].into();
+        assert_eq!(sparkline(&values, 100.0, 10), "▁▅██");
+    }
+
+    #[test]
+    #[parallel]
+    fn test_sparkline_keeps_most_recent() {
+        let values: VecDeque<f64> = vec![100.0, 0.0, 0.0].into();
+        assert_eq!(sparkline(&values, 100.0, 2), "▁▁");
+        assert_eq!(sparkline(&values, 0.0, 3), "▁▁▁");
+    }
+
+    #[test]
+    #[parallel]
+    fn test_panel_rates_and_history() {
+        let mut panel = Panel::new("dev".to_string(), "abc".to_string());
+        panel.update(ContainerStats {
+            cpu_percent: 10.0,
+            memory_usage: 50,
+            memory_limit: 100,
+            network_rx: 1000,
+            ..Default::default()
+        });
+        assert_eq!(panel.rates, [0.0; 4]);
+
+        panel.update(ContainerStats {
+            cpu_percent: 20.0,
+            memory_usage: 60,
+            memory_limit: 100,
+            network_rx: 2000,
+            ..Default::default()
+        });
+        assert!(panel.rates[0] > 0.0);
+        assert_eq!(panel.cpu_history, VecDeque::from(vec![10.0, 20.0]));
+        assert_eq!(panel.mem_history, VecDeque::from(vec![50.0, 60.0]));
+        assert_eq!(panel.render(40).len(), 12);
+    }
+}
@@ -1,14 +1,31 @@
 //! Docker Hub API client.
 //!
 //! This module provides functionality for interacting with
-//! the Docker Hub API to fetch image tags and metadata.
+//! the Docker Hub API to fetch image tags and metadata. Repositories on
+//! other registries are listed through the OCI registry API instead.
+//! Fetched tags are cached on disk, and the cache is used when the
+//! registry can't be reached.
 
-use reqwest::Client;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::{header, Client, StatusCode};
 use serde::Deserialize;
+use std::path::PathBuf;
 
-use super::Tag;
+use super::cache::{CacheEntry, TagCache, TAG_CACHE_FILE};
+use super::{RegistryClient, Tag};
+use crate::docker::{credentials::credentials_for_repository, runtime::registry_host};
+use crate::state::State;
 use crate::{FlockerError, Result};
 
+/// Default Docker Hub API address
+pub const DOCKER_HUB_URL: &str = "https://hub.docker.com";
+
+/// Environment variable that overrides the Docker Hub API address
+pub const HUB_URL_ENV: &str = "FLOCKER_HUB_URL";
+
+/// How long cached tags are used without asking the registry again
+pub const TAG_CACHE_TTL_MINUTES: i64 = 60;
+
 /// Response from Docker Hub API tag listing endpoint
 #[derive(Deserialize)]
 pub struct TagResponse {
@@ -18,47 +35,182 @@ pub struct TagResponse {
     pub next: Option<String>,
 }
 
+/// Where a list of tags came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagSource {
+    /// Fetched from, or confirmed unchanged by, the registry
+    Registry,
+    /// Read from a cache entry younger than the TTL
+    Cache,
+    /// Read from an expired cache entry because the registry couldn't be reached
+    Stale,
+}
+
+/// Tags for a repository and where they came from
+#[derive(Debug, Clone)]
+pub struct FetchedTags {
+    pub tags: Vec<Tag>,
+    pub source: TagSource,
+    /// When the tags were last fetched from the registry
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl FetchedTags {
+    fn from_entry(entry: CacheEntry, source: TagSource) -> Self {
+        Self {
+            tags: entry.tags,
+            source,
+            fetched_at: entry.fetched_at,
+        }
+    }
+}
+
 /// Docker Hub API client
 pub struct HubClient {
     client: Client,
+    registry: RegistryClient,
+    base_url: String,
+    cache_path: Option<PathBuf>,
+    cache_ttl: Duration,
 }
 
 impl HubClient {
     /// Create a new Docker Hub API client
+    ///
+    /// Tags are cached in the flocker config directory, and the API address
+    /// can be overridden with `FLOCKER_HUB_URL`.
     pub fn new() -> Self {
         Self {
             client: Client::new(),
+            registry: RegistryClient::new(),
+            base_url: std::env::var(HUB_URL_ENV)
+                .ok()
+                .filter(|url| !url.is_empty())
+                .unwrap_or_else(|| DOCKER_HUB_URL.to_string()),
+            cache_path: State::config_dir().ok().map(|dir| dir.join(TAG_CACHE_FILE)),
+            cache_ttl: Duration::minutes(TAG_CACHE_TTL_MINUTES),
+        }
+    }
+
+    /// Use a different Docker Hub API address
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Use a different cache file and TTL, or no cache at all
+    pub fn with_cache(mut self, path: Option<PathBuf>, ttl: Duration) -> Self {
+        self.cache_path = path;
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Fetch all tags for an image repository
+    ///
+    /// Cached tags are returned while they are younger than the TTL, unless
+    /// `refresh` is set. If the registry can't be reached, expired cached
+    /// tags are returned instead of an error.
+    pub async fn fetch_tags(&self, repository: &str, refresh: bool) -> Result<FetchedTags> {
+        let mut cache = self
+            .cache_path
+            .as_deref()
+            .map(TagCache::load)
+            .unwrap_or_default();
+        let cached = cache.get(repository).cloned();
+        let now = Utc::now();
+
+        if let Some(entry) = &cached {
+            if !refresh && entry.is_fresh(self.cache_ttl, now) {
+                return Ok(FetchedTags::from_entry(entry.clone(), TagSource::Cache));
+            }
         }
+
+        let etag = cached.as_ref().and_then(|entry| entry.etag.as_deref());
+        let entry = match (self.fetch_remote(repository, etag).await, cached) {
+            (Ok(Some((tags, etag))), _) => CacheEntry {
+                tags,
+                etag,
+                fetched_at: now,
+            },
+            (Ok(None), Some(entry)) => CacheEntry {
+                fetched_at: now,
+                ..entry
+            },
+            (Ok(None), None) => {
+                return Err(FlockerError::Docker(
+                    "Registry reported no changes, but no tags are cached".to_string(),
+                ))
+            }
+            (Err(e), Some(entry)) => {
+                tracing::warn!("Using cached tags for {}: {}", repository, e);
+                return Ok(FetchedTags::from_entry(entry, TagSource::Stale));
+            }
+            (Err(e), None) => return Err(e),
+        };
+
+        if let Some(path) = &self.cache_path {
+            cache.insert(repository, entry.clone());
+            if let Err(e) = cache.save(path) {
+                tracing::warn!("Could not save tag cache: {}", e);
+            }
+        }
+        Ok(FetchedTags::from_entry(entry, TagSource::Registry))
     }
 
-    /// Fetch all tags for the Fluree server image
-    pub async fn fetch_tags(&self) -> Result<Vec<Tag>> {
-        let mut url = "https://hub.docker.com/v2/repositories/fluree/server/tags".to_string();
+    /// Fetch tags from the registry, returning `None` if they match `etag`
+    async fn fetch_remote(
+        &self,
+        repository: &str,
+        etag: Option<&str>,
+    ) -> Result<Option<(Vec<Tag>, Option<String>)>> {
+        if let Some(host) = registry_host(repository) {
+            let credentials = credentials_for_repository(repository).await;
+            let tags = self
+                .registry
+                .fetch_tags(host, &repository[host.len() + 1..], credentials.as_ref())
+                .await?;
+            return Ok(Some((tags, None)));
+        }
+
+        let mut url = self.tags_url(repository);
         let mut tags = Vec::new();
+        let mut first_page_etag = None;
+        let mut first_page = true;
 
         loop {
-            let response = self
-                .client
-                .get(&url)
+            let mut request = self.client.get(&url);
+            // Only the first page is revalidated; if it is unchanged, so is the list
+            if let (Some(etag), true) = (etag, first_page) {
+                request = request.header(header::IF_NONE_MATCH, etag);
+            }
+            let response = request
                 .send()
                 .await
-                .map_err(|e| FlockerError::Docker(format!("Failed to fetch tags: {}", e)))
-                .and_then(|res| {
-                    if res.status().is_success() {
-                        Ok(res)
-                    } else {
-                        Err(FlockerError::Docker(format!(
-                            "Failed to fetch tags: {}",
-                            res.status()
-                        )))
-                    }
-                })?;
+                .map_err(|e| FlockerError::Docker(format!("Failed to fetch tags: {}", e)))?;
+
+            if response.status() == StatusCode::NOT_MODIFIED {
+                return Ok(None);
+            }
+            if !response.status().is_success() {
+                return Err(FlockerError::Docker(format!(
+                    "Failed to fetch tags: {}",
+                    response.status()
+                )));
+            }
+            if first_page {
+                first_page_etag = response
+                    .headers()
+                    .get(header::ETAG)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+            }
 
             let response: TagResponse = response.json().await.map_err(|e| {
                 FlockerError::Docker(format!("Failed to parse tags response: {}", e))
             })?;
 
-            tags.extend(response.results.into_iter());
+            tags.extend(response.results);
+            first_page = false;
 
             if let Some(next_url) = response.next {
                 url = next_url;
@@ -67,7 +219,18 @@ impl HubClient {
             }
         }
 
-        Ok(tags)
+        Ok(Some((tags, first_page_etag)))
+    }
+
+    /// Docker Hub tag listing URL for a repository
+    fn tags_url(&self, repository: &str) -> String {
+        // Official images live under the "library" namespace
+        let repository = if repository.contains('/') {
+            repository.to_string()
+        } else {
+            format!("library/{}", repository)
+        };
+        format!("{}/v2/repositories/{}/tags", self.base_url, repository)
     }
 }
 
@@ -76,3 +239,111 @@ impl Default for HubClient {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    const TAGS_PAGE: &str = r#"{
+        "results": [{ "name": "latest", "last_updated": "2024-02-08T21:00:00Z" }],
+        "next": null
+    }"#;
+
+    fn client(base_url: &str, cache: &std::path::Path, ttl: Duration) -> HubClient {
+        HubClient::new()
+            .with_base_url(base_url)
+            .with_cache(Some(cache.join(TAG_CACHE_FILE)), ttl)
+    }
+
+    #[test]
+    #[parallel]
+    fn test_tags_url() {
+        let client = HubClient::new().with_base_url(DOCKER_HUB_URL);
+        assert_eq!(
+            client.tags_url("fluree/server"),
+            "https://hub.docker.com/v2/repositories/fluree/server/tags"
+        );
+        assert_eq!(
+            client.tags_url("alpine"),
+            "https://hub.docker.com/v2/repositories/library/alpine/tags"
+        );
+    }
+
+    #[tokio::test]
+    #[parallel]
+    async fn test_fetch_tags_uses_fresh_cache() {
+        let mut server = mockito::Server::new_async().await;
+        let page = server
+            .mock("GET", "/v2/repositories/fluree/server/tags")
+            .with_body(TAGS_PAGE)
+            .expect(1)
+            .create_async()
+            .await;
+        let dir = tempdir().unwrap();
+        let client = client(&server.url(), dir.path(), Duration::hours(1));
+
+        let fetched = client.fetch_tags("fluree/server", false).await.unwrap();
+        assert_eq!(fetched.source, TagSource::Registry);
+        assert_eq!(fetched.tags[0].name, "latest");
+
+        let fetched = client.fetch_tags("fluree/server", false).await.unwrap();
+        assert_eq!(fetched.source, TagSource::Cache);
+        assert_eq!(fetched.tags[0].name, "latest");
+        page.assert_async().await;
+    }
+
+    #[tokio::test]
+    #[parallel]
+    async fn test_fetch_tags_revalidates_with_etag() {
+        let mut server = mockito::Server::new_async().await;
+        let page = server
+            .mock("GET", "/v2/repositories/fluree/server/tags")
+            .match_header("if-none-match", mockito::Matcher::Missing)
+            .with_header("etag", "\"v1\"")
+            .with_body(TAGS_PAGE)
+            .create_async()
+            .await;
+        let not_modified = server
+            .mock("GET", "/v2/repositories/fluree/server/tags")
+            .match_header("if-none-match", "\"v1\"")
+            .with_status(304)
+            .create_async()
+            .await;
+        let dir = tempdir().unwrap();
+        let client = client(&server.url(), dir.path(), Duration::zero());
+
+        client.fetch_tags("fluree/server", false).await.unwrap();
+        let fetched = client.fetch_tags("fluree/server", false).await.unwrap();
+        assert_eq!(fetched.source, TagSource::Registry);
+        assert_eq!(fetched.tags[0].name, "latest");
+        page.assert_async().await;
+        not_modified.assert_async().await;
+    }
+
+    #[tokio::test]
+    #[parallel]
+    async fn test_fetch_tags_falls_back_to_cache_when_offline() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/v2/repositories/fluree/server/tags")
+            .with_body(TAGS_PAGE)
+            .create_async()
+            .await;
+        let dir = tempdir().unwrap();
+        client(&server.url(), dir.path(), Duration::hours(1))
+            .fetch_tags("fluree/server", false)
+            .await
+            .unwrap();
+
+        // Nothing listens on port 1, so a refresh has to use the cache
+        let offline = client("http://127.0.0.1:1", dir.path(), Duration::hours(1));
+        let fetched = offline.fetch_tags("fluree/server", true).await.unwrap();
+        assert_eq!(fetched.source, TagSource::Stale);
+        assert_eq!(fetched.tags[0].name, "latest");
+
+        assert!(offline.fetch_tags("fluree/other", false).await.is_err());
+    }
+}
@@ -0,0 +1,118 @@
+//! Persistent tag cache.
+//!
+//! This module stores fetched tag lists in the flocker config directory so
+//! the image picker is fast on repeated use and still works offline.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use super::Tag;
+use crate::{FlockerError, Result};
+
+/// File name of the tag cache in the flocker config directory
+pub const TAG_CACHE_FILE: &str = "tag-cache.json";
+
+/// Cached tag lists, keyed by image repository
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TagCache {
+    #[serde(default)]
+    repositories: HashMap<String, CacheEntry>,
+}
+
+/// Tags fetched for one repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub tags: Vec<Tag>,
+    /// ETag of the first page, used to ask whether the list has changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// When the tags were last fetched or confirmed unchanged
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl CacheEntry {
+    /// Whether the entry is younger than `ttl`
+    pub fn is_fresh(&self, ttl: Duration, now: DateTime<Utc>) -> bool {
+        now.signed_duration_since(self.fetched_at) < ttl
+    }
+}
+
+impl TagCache {
+    /// Load the cache from `path`
+    ///
+    /// A missing or unreadable cache is treated as empty, since it can
+    /// always be rebuilt from the registry.
+    pub fn load(path: &Path) -> Self {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(_) => return Self::default(),
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            tracing::debug!("Ignoring unreadable tag cache {:?}: {}", path, e);
+            Self::default()
+        })
+    }
+
+    /// Save the cache to `path`
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                FlockerError::Config(format!("Failed to create config directory: {}", e))
+            })?;
+        }
+        let content = serde_json::to_string(self)
+            .map_err(|e| FlockerError::Config(format!("Failed to serialize tag cache: {}", e)))?;
+        fs::write(path, content)
+            .map_err(|e| FlockerError::Config(format!("Failed to write tag cache: {}", e)))
+    }
+
+    /// Cached tags for a repository
+    pub fn get(&self, repository: &str) -> Option<&CacheEntry> {
+        self.repositories.get(repository)
+    }
+
+    /// Store tags for a repository
+    pub fn insert(&mut self, repository: &str, entry: CacheEntry) {
+        self.repositories.insert(repository.to_string(), entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    #[parallel]
+    fn test_tag_cache_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("nested").join(TAG_CACHE_FILE);
+        assert!(TagCache::load(&path).get("fluree/server").is_none());
+
+        let fetched_at = Utc::now() - Duration::minutes(90);
+        let mut cache = TagCache::default();
+        cache.insert(
+            "fluree/server",
+            CacheEntry {
+                tags: vec![Tag::new("latest".to_string(), String::new())],
+                etag: Some("\"abc\"".to_string()),
+                fetched_at,
+            },
+        );
+        cache.save(&path).unwrap();
+
+        let entry = TagCache::load(&path).get("fluree/server").cloned().unwrap();
+        assert_eq!(entry.tags[0].name, "latest");
+        assert_eq!(entry.etag.as_deref(), Some("\"abc\""));
+        assert!(entry.is_fresh(Duration::hours(2), Utc::now()));
+        assert!(!entry.is_fresh(Duration::hours(1), Utc::now()));
+
+        fs::write(&path, "not json").unwrap();
+        assert!(TagCache::load(&path).get("fluree/server").is_none());
+    }
+}
@@ -0,0 +1,381 @@
+//! Tag sorting and filtering.
+//!
+//! This module orders tags newest version first, classifies them as
+//! releases, prereleases or build tags, and filters them for the image
+//! picker.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::Tag;
+
+/// Architecture suffixes used by platform-specific tags, e.g. `v3.0.0-arm64`
+const PLATFORM_SUFFIXES: &[&str] = &["amd64", "arm64", "armv7", "arm", "386", "ppc64le", "s390x"];
+
+/// A version parsed from a tag name like `v3.0.0` or `3.0.0-rc1`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Prerelease identifiers, e.g. `["rc", "1"]` for `-rc.1`
+    pub pre: Vec<String>,
+}
+
+impl Version {
+    /// Parse a tag name as a version, allowing a `v` prefix and missing
+    /// minor or patch numbers
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.strip_prefix(['v', 'V']).unwrap_or(name);
+        let name = name.split_once('+').map_or(name, |(version, _)| version);
+        let (core, pre) = match name.split_once('-') {
+            Some((core, pre)) => (core, pre.split('.').map(str::to_string).collect()),
+            None => (name, Vec::new()),
+        };
+
+        let mut numbers = core.split('.').map(|part| {
+            (!part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+                .then(|| part.parse::<u64>().ok())
+                .flatten()
+        });
+        let major = numbers.next()??;
+        let minor = numbers.next().unwrap_or(Some(0))?;
+        let patch = numbers.next().unwrap_or(Some(0))?;
+        if numbers.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+
+    /// Whether the version has a platform suffix instead of a prerelease
+    fn is_platform(&self) -> bool {
+        self.pre.len() == 1 && PLATFORM_SUFFIXES.contains(&self.pre[0].as_str())
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                // A release is newer than its prereleases
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                _ => compare_identifiers(&self.pre, &other.pre),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compare prerelease identifiers as semver does: numbers numerically and
+/// before words
+fn compare_identifiers(a: &[String], b: &[String]) -> Ordering {
+    for (a, b) in a.iter().zip(b) {
+        let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => a.cmp(b),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+/// What kind of build a tag refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagKind {
+    /// A released version, e.g. `v3.0.0`
+    Release,
+    /// A prerelease or snapshot, e.g. `v3.0.0-rc1`
+    Prerelease,
+    /// A commit or platform-specific build, e.g. `a1b2c3d` or `v3.0.0-arm64`
+    Build,
+    /// Any other tag, e.g. `latest` or `main`
+    Other,
+}
+
+impl TagKind {
+    /// Classify a tag by its name
+    pub fn of(name: &str) -> Self {
+        match Version::parse(name) {
+            Some(version) if version.is_platform() => TagKind::Build,
+            Some(version) if version.pre.is_empty() => TagKind::Release,
+            Some(_) => TagKind::Prerelease,
+            None if is_sha(name) || is_platform(name) => TagKind::Build,
+            None if name.to_ascii_lowercase().contains("snapshot") => TagKind::Prerelease,
+            None => TagKind::Other,
+        }
+    }
+}
+
+/// Whether a tag names a commit, e.g. `a1b2c3d` or `sha-a1b2c3d`
+fn is_sha(name: &str) -> bool {
+    let hash = name
+        .strip_prefix("sha256-")
+        .or_else(|| name.strip_prefix("sha-"))
+        .unwrap_or(name);
+    (7..=64).contains(&hash.len())
+        && hash.chars().all(|c| c.is_ascii_hexdigit())
+        && !hash.chars().all(|c| c.is_ascii_digit())
+}
+
+fn is_platform(name: &str) -> bool {
+    name.rsplit_once('-')
+        .is_some_and(|(_, suffix)| PLATFORM_SUFFIXES.contains(&suffix))
+}
+
+/// Which tags the image picker shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TagFilter {
+    /// Releases and named tags like `latest`
+    #[default]
+    Stable,
+    /// Stable tags plus prereleases and snapshots
+    Prereleases,
+    /// Every tag, including commit and platform-specific builds
+    All,
+}
+
+impl fmt::Display for TagFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TagFilter::Stable => write!(f, "stable only"),
+            TagFilter::Prereleases => write!(f, "including prereleases"),
+            TagFilter::All => write!(f, "including SHA tags"),
+        }
+    }
+}
+
+impl TagFilter {
+    /// The next filter, as toggled from the picker
+    pub fn cycle(self) -> Self {
+        match self {
+            TagFilter::Stable => TagFilter::Prereleases,
+            TagFilter::Prereleases => TagFilter::All,
+            TagFilter::All => TagFilter::Stable,
+        }
+    }
+
+    /// Whether a tag passes this filter
+    pub fn allows(self, tag: &Tag) -> bool {
+        match TagKind::of(&tag.name) {
+            TagKind::Release | TagKind::Other => true,
+            TagKind::Prerelease => self != TagFilter::Stable,
+            TagKind::Build => self == TagFilter::All,
+        }
+    }
+
+    /// Tags that pass this filter and fuzzily match `query`, in order
+    pub fn apply<'a>(self, tags: &'a [Tag], query: &str) -> Vec<&'a Tag> {
+        tags.iter()
+            .filter(|tag| self.allows(tag) && fuzzy_match(&tag.name, query))
+            .collect()
+    }
+}
+
+/// Whether the characters of `query` appear in order in `name`, ignoring case
+pub fn fuzzy_match(name: &str, query: &str) -> bool {
+    let mut name = name.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !c.is_whitespace())
+        .all(|q| name.any(|c| c == q))
+}
+
+/// The tag of an image reference, e.g. `v3.0.0` for `fluree/server:v3.0.0`
+///
+/// Local images are named by their full reference while registry tags are
+/// bare; bare tags are returned unchanged.
+pub fn tag_name(reference: &str) -> &str {
+    let name_start = reference.rfind('/').map_or(0, |i| i + 1);
+    match reference[name_start..].rfind(':') {
+        Some(i) => &reference[name_start + i + 1..],
+        None => reference,
+    }
+}
+
+/// Order tags for display: `latest` first, then versions newest first, then
+/// the remaining tags most recently updated first
+///
+/// Local image references are ordered by their tag.
+pub fn compare_tags(a: &Tag, b: &Tag) -> Ordering {
+    let rank = |tag: &Tag| match (tag_name(&tag.name), Version::parse(tag_name(&tag.name))) {
+        ("latest", _) => (0, None),
+        (_, Some(version)) => (1, Some(version)),
+        (_, None) => (2, None),
+    };
+    let (a_rank, a_version) = rank(a);
+    let (b_rank, b_version) = rank(b);
+
+    a_rank
+        .cmp(&b_rank)
+        .then_with(|| b_version.cmp(&a_version))
+        .then_with(|| b.last_updated.cmp(&a.last_updated))
+        .then_with(|| a.name.cmp(&b.name))
+}
+
+/// Sort tags with [`compare_tags`]
+pub fn sort_tags(tags: &mut [Tag]) {
+    tags.sort_by(compare_tags);
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+
+    use super::*;
+
+    fn tags(names: &[&str]) -> Vec<Tag> {
+        names
+            .iter()
+            .map(|name| Tag::new(name.to_string(), String::new()))
+            .collect()
+    }
+
+    fn names<'a>(tags: impl IntoIterator<Item = &'a Tag>) -> Vec<&'a str> {
+        tags.into_iter().map(Tag::name).collect()
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_version() {
+        let version = Version::parse("v3.1.2").unwrap();
+        assert_eq!((version.major, version.minor, version.patch), (3, 1, 2));
+        assert!(version.pre.is_empty());
+
+        assert_eq!(Version::parse("3.0"), Version::parse("3.0.0"));
+        assert_eq!(Version::parse("3.0.0-rc.1").unwrap().pre, ["rc", "1"]);
+        assert_eq!(Version::parse("3.0.0+build.5"), Version::parse("3.0.0"));
+        assert!(Version::parse("latest").is_none());
+        assert!(Version::parse("3.0.0.1").is_none());
+        assert!(Version::parse("v").is_none());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_version_ordering() {
+        let v = |name| Version::parse(name).unwrap();
+        assert!(v("v3.0.0") > v("v2.9.9"));
+        assert!(v("v3.10.0") > v("v3.9.0"));
+        assert!(v("v3.0.0") > v("v3.0.0-rc1"));
+        assert!(v("v3.0.0-beta.11") > v("v3.0.0-beta.2"));
+        assert!(v("v3.0.0-rc1") > v("v3.0.0-beta"));
+    }
+
+    #[test]
+    #[parallel]
+    fn test_tag_kind() {
+        assert_eq!(TagKind::of("v3.0.0"), TagKind::Release);
+        assert_eq!(TagKind::of("v3.0.0-alpha2"), TagKind::Prerelease);
+        assert_eq!(TagKind::of("3.0.0-SNAPSHOT"), TagKind::Prerelease);
+        assert_eq!(TagKind::of("main-SNAPSHOT"), TagKind::Prerelease);
+        assert_eq!(TagKind::of("v3.0.0-arm64"), TagKind::Build);
+        assert_eq!(TagKind::of("latest-amd64"), TagKind::Build);
+        assert_eq!(TagKind::of("a1b2c3d"), TagKind::Build);
+        assert_eq!(TagKind::of("sha-0f3e9a1b"), TagKind::Build);
+        assert_eq!(TagKind::of("latest"), TagKind::Other);
+        assert_eq!(TagKind::of("feedbeef-branch"), TagKind::Other);
+    }
+
+    #[test]
+    #[parallel]
+    fn test_sort_tags() {
+        let mut tags = tags(&[
+            "v2.0.0",
+            "main",
+            "v3.0.0-rc1",
+            "latest",
+            "v10.0.0",
+            "v3.0.0",
+        ]);
+        sort_tags(&mut tags);
+        assert_eq!(
+            names(&tags),
+            [
+                "latest",
+                "v10.0.0",
+                "v3.0.0",
+                "v3.0.0-rc1",
+                "v2.0.0",
+                "main"
+            ]
+        );
+    }
+
+    #[test]
+    #[parallel]
+    fn test_sort_local_images() {
+        // Local images are listed by repository-qualified reference
+        let mut local = tags(&[
+            "fluree/server:v2.0.0",
+            "fluree/server:main",
+            "fluree/server:latest",
+            "fluree/server:v3.0.0",
+        ]);
+        sort_tags(&mut local);
+        assert_eq!(
+            names(&local),
+            [
+                "fluree/server:latest",
+                "fluree/server:v3.0.0",
+                "fluree/server:v2.0.0",
+                "fluree/server:main"
+            ]
+        );
+    }
+
+    #[test]
+    #[parallel]
+    fn test_tag_name() {
+        assert_eq!(tag_name("latest"), "latest");
+        assert_eq!(tag_name("fluree/server:v3.0.0"), "v3.0.0");
+        assert_eq!(tag_name("localhost/fluree/server:dev"), "dev");
+        assert_eq!(tag_name("registry.internal:5000/team/fluree:1.2"), "1.2");
+        assert_eq!(
+            tag_name("registry.internal:5000/team/fluree"),
+            "registry.internal:5000/team/fluree"
+        );
+    }
+
+    #[test]
+    #[parallel]
+    fn test_tag_filter() {
+        let tags = tags(&["latest", "v3.0.0", "v3.1.0-rc1", "a1b2c3d", "v3.0.0-arm64"]);
+        assert_eq!(
+            names(TagFilter::Stable.apply(&tags, "")),
+            ["latest", "v3.0.0"]
+        );
+        assert_eq!(
+            names(TagFilter::Prereleases.apply(&tags, "")),
+            ["latest", "v3.0.0", "v3.1.0-rc1"]
+        );
+        assert_eq!(TagFilter::All.apply(&tags, "").len(), tags.len());
+        assert_eq!(TagFilter::Stable.cycle().cycle().cycle(), TagFilter::Stable);
+
+        assert_eq!(names(TagFilter::All.apply(&tags, "31")), ["v3.1.0-rc1"]);
+    }
+
+    #[test]
+    #[parallel]
+    fn test_fuzzy_match() {
+        assert!(fuzzy_match("v3.0.0-rc1", "rc"));
+        assert!(fuzzy_match("v3.0.0-rc1", "V3RC"));
+        assert!(fuzzy_match("v3.0.0-rc1", ""));
+        assert!(!fuzzy_match("v3.0.0-rc1", "cr"));
+    }
+}
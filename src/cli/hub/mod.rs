@@ -3,10 +3,18 @@
 //! This module provides functionality for:
 //! - Fetching and managing Docker image tags
 //! - Interacting with Docker Hub API
+//! - Listing tags from other OCI registries
 //! - Formatting tag information
+//! - Caching tags for offline use
+//! - Sorting and filtering tags by version
 
 mod api;
+mod cache;
+mod filter;
+mod registry;
 mod tag;
 
-pub use api::{HubClient, TagResponse};
+pub use api::{FetchedTags, HubClient, TagResponse, TagSource};
+pub use filter::{compare_tags, fuzzy_match, sort_tags, tag_name, TagFilter, TagKind, Version};
+pub use registry::RegistryClient;
 pub use tag::Tag;
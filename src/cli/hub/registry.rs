@@ -0,0 +1,469 @@
+//! OCI registry API client.
+//!
+//! This module lists tags from any registry that implements the OCI
+//! distribution API (`/v2/<name>/tags/list`), including the token
+//! authentication flow used by most hosted registries.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::{header, Client, Response, StatusCode};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::Tag;
+use crate::docker::credentials::RegistryCredentials;
+use crate::{FlockerError, Result};
+
+/// Response from the tag listing endpoint
+#[derive(Deserialize)]
+struct TagList {
+    /// Missing when the repository has no tags
+    tags: Option<Vec<String>>,
+}
+
+/// Client ID sent when exchanging an identity token for an access token
+const OAUTH_CLIENT_ID: &str = "flocker";
+
+/// Response from a registry token endpoint
+#[derive(Deserialize)]
+struct TokenResponse {
+    token: Option<String>,
+    access_token: Option<String>,
+}
+
+/// A parsed `WWW-Authenticate` challenge
+#[derive(Debug, PartialEq, Eq)]
+struct Challenge {
+    scheme: String,
+    params: HashMap<String, String>,
+}
+
+/// OCI registry API client
+pub struct RegistryClient {
+    client: Client,
+}
+
+impl RegistryClient {
+    /// Create a new registry API client
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+        }
+    }
+
+    /// Fetch all tags for `name` from `registry`, e.g. `registry.example.com:5000`
+    pub async fn fetch_tags(
+        &self,
+        registry: &str,
+        name: &str,
+        credentials: Option<&RegistryCredentials>,
+    ) -> Result<Vec<Tag>> {
+        let base = base_url(registry);
+        let mut url = format!("{}/v2/{}/tags/list", base, name);
+        let mut authorization: Option<String> = None;
+        let mut tags = Vec::new();
+
+        loop {
+            let response = self.get(&url, authorization.as_deref()).await?;
+
+            if response.status() == StatusCode::UNAUTHORIZED && authorization.is_none() {
+                authorization = Some(self.authorize(&response, name, credentials).await?);
+                continue;
+            }
+            if !response.status().is_success() {
+                return Err(FlockerError::Docker(format!(
+                    "Failed to fetch tags from {}: {}",
+                    registry,
+                    response.status()
+                )));
+            }
+
+            let next = next_link(&response, &base);
+            let list: TagList = response.json().await.map_err(|e| {
+                FlockerError::Docker(format!("Failed to parse tags response: {}", e))
+            })?;
+            tags.extend(
+                list.tags
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|tag| Tag::new(tag, String::new())),
+            );
+
+            match next {
+                Some(next) => url = next,
+                None => break,
+            }
+        }
+
+        Ok(tags)
+    }
+
+    async fn get(&self, url: &str, authorization: Option<&str>) -> Result<Response> {
+        let mut request = self.client.get(url);
+        if let Some(authorization) = authorization {
+            request = request.header(header::AUTHORIZATION, authorization);
+        }
+        request
+            .send()
+            .await
+            .map_err(|e| FlockerError::Docker(format!("Failed to fetch tags: {}", e)))
+    }
+
+    /// Answer an authentication challenge, returning an `Authorization` header value
+    async fn authorize(
+        &self,
+        response: &Response,
+        name: &str,
+        credentials: Option<&RegistryCredentials>,
+    ) -> Result<String> {
+        let challenge = response
+            .headers()
+            .get(header::WWW_AUTHENTICATE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_challenge)
+            .ok_or_else(|| FlockerError::Docker("Registry requires authentication".to_string()))?;
+        let basic = credentials.and_then(RegistryCredentials::basic);
+
+        if challenge.scheme.eq_ignore_ascii_case("basic") {
+            let (username, password) = basic.ok_or_else(|| {
+                FlockerError::Docker(
+                    "Registry requires authentication; run `docker login` first".to_string(),
+                )
+            })?;
+            return Ok(format!(
+                "Basic {}",
+                STANDARD.encode(format!("{}:{}", username, password))
+            ));
+        }
+
+        if !challenge.scheme.eq_ignore_ascii_case("bearer") {
+            return Err(FlockerError::Docker(format!(
+                "Unsupported registry authentication scheme: {}",
+                challenge.scheme
+            )));
+        }
+
+        let realm = challenge.params.get("realm").ok_or_else(|| {
+            FlockerError::Docker("Registry token challenge has no realm".to_string())
+        })?;
+        let scope = challenge
+            .params
+            .get("scope")
+            .cloned()
+            .unwrap_or_else(|| format!("repository:{}:pull", name));
+        let mut query = vec![("scope", scope.as_str())];
+        if let Some(service) = challenge.params.get("service") {
+            query.push(("service", service));
+        }
+
+        // Identity tokens are OAuth2 refresh tokens, exchanged with a POST;
+        // everything else uses the GET flow with optional basic auth
+        let identity_token = credentials.and_then(|c| c.identity_token.as_deref());
+        let request = match (identity_token, basic) {
+            (Some(refresh_token), None) => {
+                let mut form = query;
+                form.extend([
+                    ("grant_type", "refresh_token"),
+                    ("refresh_token", refresh_token),
+                    ("client_id", OAUTH_CLIENT_ID),
+                ]);
+                self.client.post(realm).form(&form)
+            }
+            (_, Some((username, password))) => self
+                .client
+                .get(realm)
+                .query(&query)
+                .basic_auth(username, Some(password)),
+            (None, None) => self.client.get(realm).query(&query),
+        };
+        let response = request
+            .send()
+            .await
+            .map_err(|e| FlockerError::Docker(format!("Failed to get registry token: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(FlockerError::Docker(format!(
+                "Failed to get registry token: {}",
+                response.status()
+            )));
+        }
+
+        let token: TokenResponse = response
+            .json()
+            .await
+            .map_err(|e| FlockerError::Docker(format!("Failed to parse registry token: {}", e)))?;
+        token
+            .token
+            .or(token.access_token)
+            .map(|token| format!("Bearer {}", token))
+            .ok_or_else(|| FlockerError::Docker("Registry returned no token".to_string()))
+    }
+}
+
+impl Default for RegistryClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Registries on the local machine are usually plain HTTP, like `docker` assumes
+fn base_url(registry: &str) -> String {
+    let host = registry.rsplit_once(':').map_or(registry, |(host, port)| {
+        if port.chars().all(|c| c.is_ascii_digit()) {
+            host
+        } else {
+            registry
+        }
+    });
+    let scheme = match host {
+        "localhost" | "127.0.0.1" | "[::1]" => "http",
+        _ => "https",
+    };
+    format!("{}://{}", scheme, registry)
+}
+
+/// The next page URL from a `Link: <...>; rel="next"` header
+fn next_link(response: &Response, base: &str) -> Option<String> {
+    let link = response.headers().get(header::LINK)?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let (target, params) = part.split_once(';')?;
+        if !params.replace(' ', "").contains("rel=\"next\"") {
+            return None;
+        }
+        let target = target.trim().strip_prefix('<')?.strip_suffix('>')?;
+        Some(if target.starts_with('/') {
+            format!("{}{}", base, target)
+        } else {
+            target.to_string()
+        })
+    })
+}
+
+/// Parse a challenge like `Bearer realm="https://auth.example.com/token",service="registry"`
+fn parse_challenge(header: &str) -> Option<Challenge> {
+    let header = header.trim();
+    let (scheme, rest) = header.split_once(' ').unwrap_or((header, ""));
+    if scheme.is_empty() {
+        return None;
+    }
+
+    let mut params = HashMap::new();
+    let mut chars = rest.chars().peekable();
+    loop {
+        while chars.next_if(|c| *c == ',' || c.is_whitespace()).is_some() {}
+        let key: String = std::iter::from_fn(|| chars.next_if(|c| *c != '=')).collect();
+        if key.is_empty() || chars.next().is_none() {
+            break;
+        }
+        let value: String = if chars.next_if_eq(&'"').is_some() {
+            let value = std::iter::from_fn(|| chars.next_if(|c| *c != '"')).collect();
+            chars.next();
+            value
+        } else {
+            std::iter::from_fn(|| chars.next_if(|c| *c != ',')).collect()
+        };
+        params.insert(key.trim().to_ascii_lowercase(), value);
+    }
+
+    Some(Challenge {
+        scheme: scheme.to_string(),
+        params,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+
+    use super::*;
+
+    fn tag_names(tags: &[Tag]) -> Vec<&str> {
+        tags.iter().map(Tag::name).collect()
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_challenge() {
+        let challenge = parse_challenge(
+            r#"Bearer realm="https://auth.example.com/token",service="registry.example.com",scope="repository:team/fluree:pull""#,
+        )
+        .unwrap();
+        assert_eq!(challenge.scheme, "Bearer");
+        assert_eq!(challenge.params["realm"], "https://auth.example.com/token");
+        assert_eq!(challenge.params["service"], "registry.example.com");
+        assert_eq!(challenge.params["scope"], "repository:team/fluree:pull");
+
+        let challenge = parse_challenge(r#"Basic realm="Registry Realm""#).unwrap();
+        assert_eq!(challenge.scheme, "Basic");
+        assert_eq!(challenge.params["realm"], "Registry Realm");
+
+        assert!(parse_challenge("").is_none());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_base_url() {
+        assert_eq!(base_url("localhost:5000"), "http://localhost:5000");
+        assert_eq!(base_url("127.0.0.1:5000"), "http://127.0.0.1:5000");
+        assert_eq!(
+            base_url("registry.example.com"),
+            "https://registry.example.com"
+        );
+    }
+
+    #[tokio::test]
+    #[parallel]
+    async fn test_fetch_tags_anonymous() {
+        let mut server = mockito::Server::new_async().await;
+        let list = server
+            .mock("GET", "/v2/team/fluree/tags/list")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"name":"team/fluree","tags":["v3.0.0","latest"]}"#)
+            .create_async()
+            .await;
+
+        let registry = server.host_with_port();
+        let tags = RegistryClient::new()
+            .fetch_tags(&registry, "team/fluree", None)
+            .await
+            .unwrap();
+        assert_eq!(tag_names(&tags), ["v3.0.0", "latest"]);
+        list.assert_async().await;
+    }
+
+    #[tokio::test]
+    #[parallel]
+    async fn test_fetch_tags_with_bearer_token() {
+        let mut server = mockito::Server::new_async().await;
+        let challenge = format!(
+            r#"Bearer realm="{}/token",service="test-registry""#,
+            server.url()
+        );
+        let unauthorized = server
+            .mock("GET", "/v2/team/fluree/tags/list")
+            .match_header("authorization", mockito::Matcher::Missing)
+            .with_status(401)
+            .with_header("www-authenticate", &challenge)
+            .create_async()
+            .await;
+        // "ci:secret"
+        let token = server
+            .mock("GET", "/token")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("service".into(), "test-registry".into()),
+                mockito::Matcher::UrlEncoded("scope".into(), "repository:team/fluree:pull".into()),
+            ]))
+            .match_header("authorization", "Basic Y2k6c2VjcmV0")
+            .with_body(r#"{"token":"abc123"}"#)
+            .create_async()
+            .await;
+        let list = server
+            .mock("GET", "/v2/team/fluree/tags/list")
+            .match_header("authorization", "Bearer abc123")
+            .with_body(r#"{"name":"team/fluree","tags":["v3.1.0"]}"#)
+            .create_async()
+            .await;
+
+        let credentials = RegistryCredentials {
+            username: Some("ci".to_string()),
+            password: Some("secret".to_string()),
+            identity_token: None,
+        };
+        let tags = RegistryClient::new()
+            .fetch_tags(&server.host_with_port(), "team/fluree", Some(&credentials))
+            .await
+            .unwrap();
+        assert_eq!(tag_names(&tags), ["v3.1.0"]);
+        unauthorized.assert_async().await;
+        token.assert_async().await;
+        list.assert_async().await;
+    }
+
+    #[tokio::test]
+    #[parallel]
+    async fn test_fetch_tags_with_identity_token() {
+        let mut server = mockito::Server::new_async().await;
+        let challenge = format!(
+            r#"Bearer realm="{}/oauth2/token",service="test-registry""#,
+            server.url()
+        );
+        let unauthorized = server
+            .mock("GET", "/v2/team/fluree/tags/list")
+            .match_header("authorization", mockito::Matcher::Missing)
+            .with_status(401)
+            .with_header("www-authenticate", &challenge)
+            .create_async()
+            .await;
+        let token = server
+            .mock("POST", "/oauth2/token")
+            .match_body(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("grant_type".into(), "refresh_token".into()),
+                mockito::Matcher::UrlEncoded("refresh_token".into(), "refresh-me".into()),
+                mockito::Matcher::UrlEncoded("service".into(), "test-registry".into()),
+                mockito::Matcher::UrlEncoded("scope".into(), "repository:team/fluree:pull".into()),
+            ]))
+            .with_body(r#"{"access_token":"xyz789"}"#)
+            .create_async()
+            .await;
+        let list = server
+            .mock("GET", "/v2/team/fluree/tags/list")
+            .match_header("authorization", "Bearer xyz789")
+            .with_body(r#"{"name":"team/fluree","tags":["v3.1.0"]}"#)
+            .create_async()
+            .await;
+
+        let credentials = RegistryCredentials {
+            identity_token: Some("refresh-me".to_string()),
+            ..Default::default()
+        };
+        let tags = RegistryClient::new()
+            .fetch_tags(&server.host_with_port(), "team/fluree", Some(&credentials))
+            .await
+            .unwrap();
+        assert_eq!(tag_names(&tags), ["v3.1.0"]);
+        unauthorized.assert_async().await;
+        token.assert_async().await;
+        list.assert_async().await;
+    }
+
+    #[tokio::test]
+    #[parallel]
+    async fn test_fetch_tags_follows_pagination() {
+        let mut server = mockito::Server::new_async().await;
+        let first = server
+            .mock("GET", "/v2/fluree/tags/list")
+            .with_header("link", r#"</v2/fluree/tags/list?last=b&n=2>; rel="next""#)
+            .with_body(r#"{"name":"fluree","tags":["a","b"]}"#)
+            .create_async()
+            .await;
+        let second = server
+            .mock("GET", "/v2/fluree/tags/list?last=b&n=2")
+            .with_body(r#"{"name":"fluree","tags":["c"]}"#)
+            .create_async()
+            .await;
+
+        let tags = RegistryClient::new()
+            .fetch_tags(&server.host_with_port(), "fluree", None)
+            .await
+            .unwrap();
+        assert_eq!(tag_names(&tags), ["a", "b", "c"]);
+        first.assert_async().await;
+        second.assert_async().await;
+    }
+
+    #[tokio::test]
+    #[parallel]
+    async fn test_fetch_tags_requires_login_for_basic_auth() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/v2/fluree/tags/list")
+            .with_status(401)
+            .with_header("www-authenticate", r#"Basic realm="Registry""#)
+            .create_async()
+            .await;
+
+        let error = RegistryClient::new()
+            .fetch_tags(&server.host_with_port(), "fluree", None)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("docker login"));
+    }
+}
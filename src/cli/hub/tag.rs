@@ -5,10 +5,10 @@
 
 use crate::Result;
 use pad::PadStr;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Docker image tag information
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Tag {
     /// Tag name (e.g., "latest", "v1.0.0")
     pub name: String,
@@ -30,6 +30,10 @@ impl Tag {
         } else {
             self.name.clone()
         };
+        // Registries other than Docker Hub don't report update times
+        if self.last_updated.is_empty() {
+            return name;
+        }
         format!(
             "{} (updated {})",
             name,
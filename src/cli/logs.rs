@@ -0,0 +1,230 @@
+//! Log level parsing and time bounds for container logs.
+//!
+//! This module provides helpers for filtering Fluree server logs by level
+//! and for parsing `--since`/`--until` values into Unix timestamps.
+
+use clap::ValueEnum;
+use std::fmt;
+
+use crate::{FlockerError, Result};
+
+/// Fluree log levels, ordered from most to least severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    /// Parse the level from a single log line, if it contains one
+    ///
+    /// Fluree's logback output puts the level in its own token, e.g.
+    /// `2024-02-08 21:56:23,123 INFO  f.s.handlers - ...`, sometimes wrapped
+    /// in brackets.
+    pub fn from_line(line: &str) -> Option<Self> {
+        line.split_whitespace().take(8).find_map(|token| {
+            match token.trim_matches(|c: char| !c.is_ascii_alphabetic()) {
+                "ERROR" | "FATAL" | "SEVERE" => Some(Self::Error),
+                "WARN" | "WARNING" => Some(Self::Warn),
+                "INFO" => Some(Self::Info),
+                "DEBUG" => Some(Self::Debug),
+                "TRACE" => Some(Self::Trace),
+                _ => None,
+            }
+        })
+    }
+
+    /// The next, more verbose filter level, cycling back to no filter
+    pub fn cycle(level: Option<Self>) -> Option<Self> {
+        match level {
+            None => Some(Self::Error),
+            Some(Self::Error) => Some(Self::Warn),
+            Some(Self::Warn) => Some(Self::Info),
+            Some(Self::Info) => Some(Self::Debug),
+            Some(Self::Debug) | Some(Self::Trace) => None,
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Tracks log levels across lines so continuation lines (e.g. stack traces)
+/// inherit the level of the line that started them
+#[derive(Debug, Default)]
+pub struct LevelTracker {
+    current: Option<LogLevel>,
+}
+
+impl LevelTracker {
+    /// Get the effective level of the next line
+    pub fn level_of(&mut self, line: &str) -> Option<LogLevel> {
+        if let Some(level) = LogLevel::from_line(line) {
+            self.current = Some(level);
+        }
+        self.current
+    }
+}
+
+/// Check whether a line at `level` passes a minimum severity filter
+pub fn passes_filter(level: Option<LogLevel>, filter: Option<LogLevel>) -> bool {
+    match (filter, level) {
+        (None, _) => true,
+        (Some(filter), Some(level)) => level <= filter,
+        (Some(_), None) => false,
+    }
+}
+
+/// Parse a `--since`/`--until` value into a Unix timestamp
+///
+/// Accepts RFC 3339 timestamps (`2024-02-08T21:56:23Z`), UTC date-times
+/// (`2024-02-08 21:56:23` or `2024-02-08T21:56`), dates (`2024-02-08`),
+/// raw Unix timestamps, and durations relative to now (`30s`, `15m`, `2h`, `1d`).
+pub fn parse_time_bound(value: &str) -> Result<i64> {
+    parse_time_bound_at(value, chrono::Utc::now())
+}
+
+fn parse_time_bound_at(value: &str, now: chrono::DateTime<chrono::Utc>) -> Result<i64> {
+    let value = value.trim();
+
+    if let Ok(time) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(time.timestamp());
+    }
+
+    for format in [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+    ] {
+        if let Ok(time) = chrono::NaiveDateTime::parse_from_str(value, format) {
+            return Ok(time.and_utc().timestamp());
+        }
+    }
+
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_time(chrono::NaiveTime::MIN).and_utc().timestamp());
+    }
+
+    if let Ok(timestamp) = value.parse::<i64>() {
+        return Ok(timestamp);
+    }
+
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => 0,
+    };
+    match amount.parse::<i64>() {
+        Ok(amount) if seconds > 0 => Ok(now.timestamp() - amount * seconds),
+        _ => Err(FlockerError::UserInput(format!(
+            "Invalid time '{}': expected a timestamp like 2024-02-08T21:56:23Z or a duration like 15m",
+            value
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+
+    use super::*;
+
+    #[test]
+    #[parallel]
+    fn test_level_from_line() {
+        assert_eq!(
+            LogLevel::from_line("2024-02-08 21:56:23,123 INFO  fluree.server - started"),
+            Some(LogLevel::Info)
+        );
+        assert_eq!(
+            LogLevel::from_line("[2024-02-08 21:56:23.123] [main] WARN f.db - slow"),
+            Some(LogLevel::Warn)
+        );
+        assert_eq!(
+            LogLevel::from_line("21:56:23.123 [qtp-1] ERROR f.s.handlers - boom"),
+            Some(LogLevel::Error)
+        );
+        assert_eq!(LogLevel::from_line("\tat clojure.core$apply.invoke"), None);
+    }
+
+    #[test]
+    #[parallel]
+    fn test_continuation_lines_inherit_level() {
+        let mut tracker = LevelTracker::default();
+        assert_eq!(tracker.level_of("no level yet"), None);
+        assert_eq!(
+            tracker.level_of("12:00:00 ERROR f.s - failed"),
+            Some(LogLevel::Error)
+        );
+        assert_eq!(tracker.level_of("\tat foo.bar"), Some(LogLevel::Error));
+        assert_eq!(
+            tracker.level_of("12:00:01 DEBUG f.s - ok"),
+            Some(LogLevel::Debug)
+        );
+    }
+
+    #[test]
+    #[parallel]
+    fn test_level_filter() {
+        assert!(passes_filter(None, None));
+        assert!(passes_filter(Some(LogLevel::Error), Some(LogLevel::Warn)));
+        assert!(passes_filter(Some(LogLevel::Warn), Some(LogLevel::Warn)));
+        assert!(!passes_filter(Some(LogLevel::Info), Some(LogLevel::Warn)));
+        assert!(!passes_filter(None, Some(LogLevel::Debug)));
+        assert_eq!(LogLevel::cycle(None), Some(LogLevel::Error));
+        assert_eq!(LogLevel::cycle(Some(LogLevel::Debug)), None);
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_time_bound() {
+        let now = chrono::DateTime::parse_from_rfc3339("2024-02-08T12:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let noon = now.timestamp();
+
+        assert_eq!(
+            parse_time_bound_at("2024-02-08T12:00:00Z", now).unwrap(),
+            noon
+        );
+        assert_eq!(
+            parse_time_bound_at("2024-02-08T13:00:00+01:00", now).unwrap(),
+            noon
+        );
+        assert_eq!(
+            parse_time_bound_at("2024-02-08 12:00:00", now).unwrap(),
+            noon
+        );
+        assert_eq!(parse_time_bound_at("2024-02-08T12:00", now).unwrap(), noon);
+        assert_eq!(
+            parse_time_bound_at("2024-02-08", now).unwrap(),
+            noon - 12 * 3600
+        );
+        assert_eq!(parse_time_bound_at("15m", now).unwrap(), noon - 900);
+        assert_eq!(parse_time_bound_at("2h", now).unwrap(), noon - 7200);
+        assert_eq!(parse_time_bound_at("1d", now).unwrap(), noon - 86400);
+        assert_eq!(parse_time_bound_at("1707393600", now).unwrap(), noon);
+        assert!(parse_time_bound_at("yesterday", now).is_err());
+        assert!(parse_time_bound_at("5w", now).is_err());
+    }
+}
@@ -3,14 +3,24 @@
 //! This module provides the command-line interface components,
 //! organized into submodules for different concerns:
 //! - args: Command line argument parsing
+//! - commands: Non-interactive command handling
+//! - dashboard: Live container stats view
+//! - logs: Log level filtering and time bounds
+//! - output: Table, plain and JSON output for commands
+//! - progress: Image pull progress reporting
 //! - actions: Container and ledger action handling
 //! - hub: Docker Hub interactions
 //! - ui: User interface state and interactions
 
 pub mod actions;
 pub mod args;
+pub mod commands;
+pub mod dashboard;
 pub mod hub;
+pub mod logs;
+pub mod output;
 pub mod pager;
+pub mod progress;
 pub mod terminal;
 pub mod ui;
 
@@ -0,0 +1,258 @@
+//! Output formatting for non-interactive commands.
+//!
+//! This module provides the `--output` format switch and helpers for
+//! printing command results as styled tables, plain text or JSON.
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use super::{format_bytes, Column, TableFormatter};
+use crate::{
+    docker::{BranchInfo, ContainerStats},
+    state::ContainerInfo,
+    ContainerStatus, FlockerError, Result,
+};
+
+/// Output format for non-interactive commands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned, human readable tables
+    #[default]
+    Table,
+    /// Pretty printed JSON, for `jq` and other tools
+    Json,
+    /// Tab separated values without headers or styling
+    Plain,
+}
+
+/// A known container together with its live status
+#[derive(Debug, Clone, Serialize)]
+pub struct ContainerListing {
+    #[serde(flatten)]
+    pub info: ContainerInfo,
+    pub status: ContainerStatus,
+}
+
+/// Column headers for container stats tables
+pub const STATS_HEADERS: [&str; 7] = [
+    "CONTAINER ID",
+    "CPU %",
+    "MEM USAGE / LIMIT",
+    "MEM %",
+    "NET I/O",
+    "BLOCK I/O",
+    "PIDS",
+];
+
+/// Format container stats as a table row matching `STATS_HEADERS`
+pub fn stats_row(stats: &ContainerStats) -> Vec<String> {
+    vec![
+        stats.id.chars().take(12).collect(),
+        format!("{:.2}%", stats.cpu_percent),
+        format!(
+            "{} / {}",
+            format_bytes(stats.memory_usage),
+            format_bytes(stats.memory_limit)
+        ),
+        format!("{:.1}%", stats.memory_percent()),
+        format!(
+            "{} / {}",
+            format_bytes(stats.network_rx),
+            format_bytes(stats.network_tx)
+        ),
+        format!(
+            "{} / {}",
+            format_bytes(stats.block_read),
+            format_bytes(stats.block_write)
+        ),
+        stats.pids.to_string(),
+    ]
+}
+
+/// Column headers for ledger branch tables
+pub const BRANCH_HEADERS: [&str; 6] = [
+    "BRANCH",
+    "LAST COMMIT",
+    "COMMITS",
+    "LAST INDEX",
+    "SIZE",
+    "FLAKES",
+];
+
+/// Format a ledger branch as a table row matching `BRANCH_HEADERS`
+pub fn branch_row(branch: &BranchInfo) -> Vec<String> {
+    vec![
+        branch.name.clone(),
+        branch.last_commit_time.clone(),
+        branch.commit_count.to_string(),
+        branch
+            .last_index
+            .map(|i| i.to_string())
+            .unwrap_or_else(|| "None".to_string()),
+        format_bytes(branch.size),
+        branch.flakes_count.clone(),
+    ]
+}
+
+/// Print a value as pretty printed JSON
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<()> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| FlockerError::Config(format!("Failed to serialize output: {}", e)))?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Print rows in the given format, using `value` for JSON output
+pub fn print_rows<T: Serialize + ?Sized>(
+    format: OutputFormat,
+    headers: &[&str],
+    rows: &[Vec<String>],
+    value: &T,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json => print_json(value)?,
+        OutputFormat::Table => print_table(headers, rows),
+        OutputFormat::Plain => {
+            for row in rows {
+                println!("{}", row.join("\t"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Print rows as a table with columns sized to fit their content
+pub fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let columns = headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            let width = rows
+                .iter()
+                .filter_map(|row| row.get(i).map(String::len))
+                .chain(std::iter::once(header.len()))
+                .max()
+                .unwrap_or_default();
+            Column::new(*header, width)
+        })
+        .collect();
+
+    let formatter = TableFormatter::new(columns);
+    formatter.print_header();
+    for row in rows {
+        formatter.print_row(row);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+
+    use super::*;
+
+    #[test]
+    #[parallel]
+    fn test_container_listing_json() {
+        let listing = ContainerListing {
+            info: ContainerInfo::new(
+                "abc123".to_string(),
+                "dev".to_string(),
+                8090,
+                None,
+                None,
+                "fluree/server:latest".to_string(),
+            ),
+            status: ContainerStatus::Stopped {
+                id: "abc123".to_string(),
+                name: "dev".to_string(),
+                last_start: None,
+            },
+        };
+
+        let json = serde_json::to_value(&listing).unwrap();
+        assert_eq!(json["id"], "abc123");
+        assert_eq!(json["image_tag"], "fluree/server:latest");
+        assert_eq!(json["status"]["state"], "stopped");
+    }
+
+    #[test]
+    #[parallel]
+    fn test_stats_row() {
+        let stats = ContainerStats {
+            id: "0123456789abcdef".to_string(),
+            cpu_percent: 12.345,
+            memory_usage: 512 * 1024 * 1024,
+            memory_limit: 2048 * 1024 * 1024,
+            network_rx: 2048,
+            network_tx: 10,
+            block_read: 0,
+            block_write: 1024,
+            pids: 37,
+        };
+        let row = stats_row(&stats);
+        assert_eq!(row.len(), STATS_HEADERS.len());
+        assert_eq!(row[0], "0123456789ab");
+        assert_eq!(row[1], "12.35%");
+        assert_eq!(row[2], "512.0 MB / 2.0 GB");
+        assert_eq!(row[3], "25.0%");
+        assert_eq!(row[4], "2.0 KB / 10 B");
+        assert_eq!(row[5], "0 B / 1.0 KB");
+        assert_eq!(row[6], "37");
+    }
+
+    #[test]
+    #[parallel]
+    fn test_branch_row() {
+        let branch = BranchInfo {
+            name: "main".to_string(),
+            last_commit_time: "2024-02-08T21:56:23Z".to_string(),
+            commit_count: 5,
+            size: 2048,
+            flakes_count: "1,200".to_string(),
+            last_index: None,
+        };
+        let row = branch_row(&branch);
+        assert_eq!(row.len(), BRANCH_HEADERS.len());
+        assert_eq!(row[0], "main");
+        assert_eq!(row[2], "5");
+        assert_eq!(row[3], "None");
+        assert_eq!(row[4], "2.0 KB");
+    }
+
+    #[test]
+    #[parallel]
+    fn test_exited_status() {
+        let status = ContainerStatus::Exited {
+            id: "abc123".to_string(),
+            name: "dev".to_string(),
+            code: 137,
+            oom_killed: true,
+            finished_at: Some("2024-02-08T22:10:00Z".to_string()),
+            last_start: None,
+        };
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["state"], "exited");
+        assert_eq!(json["code"], 137);
+        assert_eq!(json["oom_killed"], true);
+        assert_eq!(status.style(None), "exited (137, out of memory)");
+        assert!(status.is_failure());
+
+        let stopped = ContainerStatus::Exited {
+            id: "abc123".to_string(),
+            name: "dev".to_string(),
+            code: 143,
+            oom_killed: false,
+            finished_at: None,
+            last_start: None,
+        };
+        assert_eq!(stopped.style(None), "exited (143)");
+        assert!(!stopped.is_failure());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_not_found_status_json() {
+        let json = serde_json::to_value(ContainerStatus::NotFound).unwrap();
+        assert_eq!(json, serde_json::json!({ "state": "not_found" }));
+    }
+}
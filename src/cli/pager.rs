@@ -1,24 +1,48 @@
 //! Terminal pager for scrollable text output.
 //!
 //! This module provides functionality for displaying large text content
-//! in a scrollable pager with 'q' to exit.
+//! in a scrollable pager with 'q' to exit. The pager can also follow a
+//! live stream of lines (like `tail -f`), search with `/`, `n` and `N`,
+//! and filter log lines by level with `l`.
 
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use std::io::{stdout, Write};
+use std::time::Duration;
 use termion::{
-    event::Key,
-    input::TermRead,
     raw::{IntoRawMode, RawTerminal},
     screen::{AlternateScreen, IntoAlternateScreen},
 };
+use tokio::sync::mpsc::{error::TryRecvError, UnboundedReceiver};
+
+use super::logs::{passes_filter, LevelTracker, LogLevel};
 
 type PagerOutput = AlternateScreen<RawTerminal<std::io::Stdout>>;
 
+/// How long to wait for a key press before checking for new lines
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// A simple terminal pager for scrollable text
 pub struct Pager {
     stdout: PagerOutput,
     lines: Vec<String>,
+    levels: Vec<Option<LogLevel>>,
+    tracker: LevelTracker,
+    /// Indices into `lines` that pass the level filter
+    visible: Vec<usize>,
+    /// First row of `visible` shown on screen
     current_line: usize,
     terminal_height: u16,
+    level_filter: Option<LogLevel>,
+    /// Confirmed search query
+    search: Option<String>,
+    /// Rows of `visible` that match the search query
+    matches: Vec<usize>,
+    current_match: Option<usize>,
+    /// Search query being typed after '/'
+    input: Option<String>,
+    incoming: Option<UnboundedReceiver<String>>,
+    /// Keep the view pinned to the newest line as lines arrive
+    follow: bool,
 }
 
 impl Pager {
@@ -26,104 +50,356 @@ impl Pager {
     pub fn new(content: &str) -> std::io::Result<Self> {
         let raw = stdout().into_raw_mode()?;
         let stdout = raw.into_alternate_screen()?;
-        let lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
         let terminal_height = termion::terminal_size()?.1;
 
-        let visible_height = terminal_height.saturating_sub(1) as usize;
-        let max_scroll = lines.len().saturating_sub(visible_height);
-
-        Ok(Self {
+        let mut pager = Self {
             stdout,
-            lines,
-            current_line: max_scroll, // Start at the bottom
+            lines: Vec::new(),
+            levels: Vec::new(),
+            tracker: LevelTracker::default(),
+            visible: Vec::new(),
+            current_line: 0,
             terminal_height,
-        })
+            level_filter: None,
+            search: None,
+            matches: Vec::new(),
+            current_match: None,
+            input: None,
+            incoming: None,
+            follow: false,
+        };
+        pager.push_lines(content);
+        pager.current_line = pager.max_scroll(); // Start at the bottom
+        Ok(pager)
+    }
+
+    /// Create a pager that appends lines from `incoming` as they arrive
+    pub fn follow(incoming: UnboundedReceiver<String>) -> std::io::Result<Self> {
+        let mut pager = Self::new("")?;
+        pager.incoming = Some(incoming);
+        pager.follow = true;
+        Ok(pager)
     }
 
     /// Display the content in a scrollable view
     pub fn display(&mut self) -> std::io::Result<()> {
-        let stdin = std::io::stdin();
-        let mut keys = stdin.keys();
-
         self.draw()?;
 
-        while let Some(Ok(key)) = keys.next() {
-            match key {
-                Key::Char('q') | Key::Ctrl('c') => break,
-                Key::Char('j') | Key::Down => self.scroll_down()?,
-                Key::Char('k') | Key::Up => self.scroll_up()?,
-                Key::PageDown | Key::Char(' ') => self.page_down()?,
-                Key::PageUp => self.page_up()?,
-                _ => (),
+        loop {
+            let mut dirty = self.receive_lines();
+
+            if event::poll(POLL_INTERVAL)? {
+                match event::read()? {
+                    Event::Key(key) if key.kind != KeyEventKind::Release => {
+                        if !self.handle_key(key) {
+                            break;
+                        }
+                        dirty = true;
+                    }
+                    Event::Resize(_, height) => {
+                        self.terminal_height = height;
+                        dirty = true;
+                    }
+                    _ => (),
+                }
+            }
+
+            if dirty {
+                self.draw()?;
             }
-            self.draw()?;
         }
 
         Ok(())
     }
 
+    /// Handle a key press, returning false when the pager should close
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if let Some(input) = self.input.as_mut() {
+            match key.code {
+                KeyCode::Enter => {
+                    let query = self.input.take().unwrap_or_default();
+                    self.set_search(query);
+                }
+                KeyCode::Esc => self.input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => (),
+            }
+            return true;
+        }
+
+        match key.code {
+            KeyCode::Char('q') => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Char('j') | KeyCode::Down => self.scroll_down(),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll_up(),
+            KeyCode::PageDown | KeyCode::Char(' ') => self.page_down(),
+            KeyCode::PageUp => self.page_up(),
+            KeyCode::Char('g') | KeyCode::Home => self.current_line = 0,
+            KeyCode::Char('G') | KeyCode::End => self.current_line = self.max_scroll(),
+            KeyCode::Char('/') => self.input = Some(String::new()),
+            KeyCode::Char('n') => self.next_match(),
+            KeyCode::Char('N') => self.previous_match(),
+            KeyCode::Char('l') => self.set_level_filter(LogLevel::cycle(self.level_filter)),
+            KeyCode::Char('f') if self.incoming.is_some() => {
+                self.follow = !self.follow;
+                if self.follow {
+                    self.current_line = self.max_scroll();
+                }
+            }
+            _ => (),
+        }
+        true
+    }
+
+    /// Append any lines that arrived since the last check
+    fn receive_lines(&mut self) -> bool {
+        let mut received = Vec::new();
+        let mut disconnected = false;
+        if let Some(incoming) = self.incoming.as_mut() {
+            loop {
+                match incoming.try_recv() {
+                    Ok(line) => received.push(line),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        disconnected = true;
+                        break;
+                    }
+                }
+            }
+        }
+        if disconnected {
+            self.incoming = None;
+            self.follow = false;
+        }
+        if received.is_empty() {
+            return disconnected;
+        }
+
+        let at_bottom = self.current_line >= self.max_scroll();
+        for line in received {
+            self.push_lines(&line);
+        }
+        if self.follow && at_bottom {
+            self.current_line = self.max_scroll();
+        }
+        true
+    }
+
+    fn push_lines(&mut self, content: &str) {
+        for line in content.lines() {
+            let level = self.tracker.level_of(line);
+            self.lines.push(line.to_string());
+            self.levels.push(level);
+            if passes_filter(level, self.level_filter) {
+                let row = self.visible.len();
+                self.visible.push(self.lines.len() - 1);
+                if self.is_match(line) {
+                    self.matches.push(row);
+                }
+            }
+        }
+    }
+
+    fn is_match(&self, line: &str) -> bool {
+        self.search
+            .as_ref()
+            .is_some_and(|query| line.to_lowercase().contains(&query.to_lowercase()))
+    }
+
+    fn set_level_filter(&mut self, filter: Option<LogLevel>) {
+        // Keep the line at the top of the screen in view where possible
+        let anchor = self.visible.get(self.current_line).copied().unwrap_or(0);
+        self.level_filter = filter;
+        self.visible = (0..self.lines.len())
+            .filter(|&i| passes_filter(self.levels[i], filter))
+            .collect();
+        self.refresh_matches();
+        self.current_line = self
+            .visible
+            .partition_point(|&i| i < anchor)
+            .min(self.max_scroll());
+        if self.follow {
+            self.current_line = self.max_scroll();
+        }
+    }
+
+    fn set_search(&mut self, query: String) {
+        self.search = if query.is_empty() { None } else { Some(query) };
+        self.refresh_matches();
+        self.current_match = None;
+        self.next_match();
+    }
+
+    fn refresh_matches(&mut self) {
+        self.matches = self
+            .visible
+            .iter()
+            .enumerate()
+            .filter(|(_, &i)| self.is_match(&self.lines[i]))
+            .map(|(row, _)| row)
+            .collect();
+        self.current_match = None;
+    }
+
+    fn next_match(&mut self) {
+        let from = self
+            .current_match
+            .map(|m| m + 1)
+            .unwrap_or(self.current_line);
+        let index =
+            self.matches
+                .iter()
+                .position(|&row| row >= from)
+                .or(if self.matches.is_empty() {
+                    None
+                } else {
+                    Some(0)
+                });
+        self.jump_to_match(index);
+    }
+
+    fn previous_match(&mut self) {
+        let from = self.current_match.unwrap_or(self.current_line);
+        let index = self
+            .matches
+            .iter()
+            .rposition(|&row| row < from)
+            .or(self.matches.len().checked_sub(1));
+        self.jump_to_match(index);
+    }
+
+    fn jump_to_match(&mut self, index: Option<usize>) {
+        if let Some(index) = index {
+            let row = self.matches[index];
+            self.current_match = Some(row);
+            self.follow = false;
+            // Show the match a few lines below the top for context
+            self.current_line = row.saturating_sub(3).min(self.max_scroll());
+        }
+    }
+
+    /// Wrap case-insensitive occurrences of the search query in inverse video
+    fn highlight(&self, line: &str) -> String {
+        let Some(query) = self.search.as_ref() else {
+            return line.to_string();
+        };
+        let lower_line = line.to_lowercase();
+        let lower_query = query.to_lowercase();
+        // Lowercasing can change byte lengths for some scripts; skip highlighting then
+        if lower_line.len() != line.len() || lower_query.is_empty() {
+            return line.to_string();
+        }
+
+        let mut output = String::with_capacity(line.len());
+        let mut start = 0;
+        while let Some(offset) = lower_line[start..].find(&lower_query) {
+            let begin = start + offset;
+            let end = begin + lower_query.len();
+            output.push_str(&line[start..begin]);
+            output.push_str(&format!(
+                "{}{}{}",
+                termion::style::Invert,
+                &line[begin..end],
+                termion::style::NoInvert
+            ));
+            start = end;
+        }
+        output.push_str(&line[start..]);
+        output
+    }
+
+    fn status_line(&self) -> String {
+        if let Some(input) = &self.input {
+            return format!("/{}", input);
+        }
+
+        let visible_height = self.terminal_height.saturating_sub(1) as usize;
+        let mut parts = Vec::new();
+        if self.visible.len() > visible_height {
+            let progress = (self.current_line as f64 / self.max_scroll().max(1) as f64 * 100.0)
+                .round() as usize;
+            parts.push(format!("--{}%--", progress));
+        }
+        if self.follow {
+            parts.push("[following]".to_string());
+        } else if self.incoming.is_some() {
+            parts.push("[paused, f to follow]".to_string());
+        }
+        if let Some(level) = self.level_filter {
+            parts.push(format!("[level: {}+]", level));
+        }
+        if let Some(query) = &self.search {
+            let position = self
+                .current_match
+                .and_then(|row| self.matches.iter().position(|&m| m == row))
+                .map(|i| (i + 1).to_string())
+                .unwrap_or_else(|| "-".to_string());
+            parts.push(format!("[/{} {}/{}]", query, position, self.matches.len()));
+        }
+        parts.push("q quit, / search, n/N next/prev, l level".to_string());
+        parts.join(" ")
+    }
+
     fn draw(&mut self) -> std::io::Result<()> {
         write!(self.stdout, "{}", termion::clear::All)?;
         write!(self.stdout, "{}", termion::cursor::Goto(1, 1))?;
 
         let visible_height = self.terminal_height.saturating_sub(1) as usize;
-        let visible_lines = self
-            .lines
+        let rows: Vec<String> = self
+            .visible
             .iter()
             .skip(self.current_line)
-            .take(visible_height);
+            .take(visible_height)
+            .map(|&i| self.highlight(&self.lines[i]))
+            .collect();
 
-        for line in visible_lines {
+        for line in rows {
             writeln!(self.stdout, "{}\r", line)?;
         }
 
-        // Draw scroll indicator
-        let total_lines = self.lines.len();
-        if total_lines > visible_height {
-            let progress = (self.current_line as f64 / (total_lines - visible_height) as f64
-                * 100.0)
-                .round() as usize;
-            write!(
-                self.stdout,
-                "{}--{}%--",
-                termion::cursor::Goto(1, self.terminal_height),
-                progress
-            )?;
-        }
+        let status = self.status_line();
+        write!(
+            self.stdout,
+            "{}{}{}",
+            termion::cursor::Goto(1, self.terminal_height),
+            termion::clear::CurrentLine,
+            status
+        )?;
 
         self.stdout.flush()
     }
 
-    fn scroll_up(&mut self) -> std::io::Result<()> {
+    fn scroll_up(&mut self) {
         if self.current_line > 0 {
             self.current_line -= 1;
+            self.follow = false;
         }
-        Ok(())
     }
 
-    fn scroll_down(&mut self) -> std::io::Result<()> {
+    fn scroll_down(&mut self) {
         let max_scroll = self.max_scroll();
         if self.current_line < max_scroll {
             self.current_line += 1;
         }
-        Ok(())
     }
 
-    fn page_up(&mut self) -> std::io::Result<()> {
+    fn page_up(&mut self) {
         let page_size = self.terminal_height.saturating_sub(1) as usize;
         self.current_line = self.current_line.saturating_sub(page_size);
-        Ok(())
+        self.follow = false;
     }
 
-    fn page_down(&mut self) -> std::io::Result<()> {
+    fn page_down(&mut self) {
         let page_size = self.terminal_height.saturating_sub(1) as usize;
         let max_scroll = self.max_scroll();
         self.current_line = (self.current_line + page_size).min(max_scroll);
-        Ok(())
     }
 
     fn max_scroll(&self) -> usize {
         let visible_height = self.terminal_height.saturating_sub(1) as usize;
-        self.lines.len().saturating_sub(visible_height)
+        self.visible.len().saturating_sub(visible_height)
     }
 }
@@ -0,0 +1,436 @@
+//! Image pull progress.
+//!
+//! This module aggregates the progress events of an image pull into
+//! per-layer progress bars with total bytes, speed and ETA. When stderr is
+//! not a terminal, layer status changes are printed as plain lines, and in
+//! JSON mode every event is written as a line of JSON.
+
+use console::style;
+use crossterm::{
+    cursor, queue,
+    terminal::{Clear, ClearType},
+};
+use std::io::{stderr, IsTerminal, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+use super::{format_bytes, output::OutputFormat, terminal::get_terminal_width};
+use crate::docker::{DockerOperations, PullProgress};
+use crate::{FlockerError, Result};
+
+/// Minimum time between redraws of the progress bars
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Width of each progress bar in characters
+const BAR_WIDTH: usize = 24;
+
+/// Exit code of a process terminated by Ctrl-C
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Progress of a single layer
+#[derive(Debug, Clone, Default)]
+struct Layer {
+    id: String,
+    status: String,
+    /// Compressed bytes downloaded so far
+    downloaded: u64,
+    /// Compressed size, once the download has started
+    size: u64,
+    /// Progress of the current step, e.g. extraction
+    step: Option<(u64, u64)>,
+    done: bool,
+}
+
+/// Aggregated state of an image pull
+#[derive(Debug, Default)]
+pub struct PullTracker {
+    layers: Vec<Layer>,
+}
+
+impl PullTracker {
+    /// Apply a progress event
+    pub fn update(&mut self, event: &PullProgress) {
+        let Some(id) = &event.layer else {
+            return;
+        };
+        let index = match self.layers.iter().position(|layer| &layer.id == id) {
+            Some(index) => index,
+            None => {
+                self.layers.push(Layer {
+                    id: id.clone(),
+                    ..Default::default()
+                });
+                self.layers.len() - 1
+            }
+        };
+        let layer = &mut self.layers[index];
+
+        layer.status = event.status.clone();
+        layer.step = event.current.zip(event.total);
+        match event.status.as_str() {
+            "Downloading" => {
+                if let Some(total) = event.total {
+                    layer.size = total;
+                }
+                if let Some(current) = event.current {
+                    layer.downloaded = current;
+                }
+            }
+            "Verifying Checksum" | "Download complete" | "Extracting" => {
+                layer.downloaded = layer.size;
+            }
+            "Pull complete" | "Already exists" => {
+                layer.downloaded = layer.size;
+                layer.done = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// Downloaded and total bytes over all layers whose size is known
+    pub fn totals(&self) -> (u64, u64) {
+        self.layers.iter().fold((0, 0), |(current, total), layer| {
+            (current + layer.downloaded, total + layer.size)
+        })
+    }
+
+    /// Number of finished layers and number of known layers
+    pub fn layer_counts(&self) -> (usize, usize) {
+        let done = self.layers.iter().filter(|layer| layer.done).count();
+        (done, self.layers.len())
+    }
+
+    /// Render one line per layer plus a summary line
+    fn render(&self, elapsed: Duration, width: usize) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .layers
+            .iter()
+            .map(|layer| {
+                let (bar, bytes) = match (layer.done, layer.step) {
+                    (true, _) => (progress_bar(1, 1), String::new()),
+                    (false, Some((current, total))) => (
+                        progress_bar(current, total),
+                        format!("{} / {}", format_bytes(current), format_bytes(total)),
+                    ),
+                    (false, None) => (progress_bar(0, 0), String::new()),
+                };
+                let id = &layer.id[..layer.id.len().min(12)];
+                truncate(
+                    &format!("{:<12} {:<18} {} {}", id, layer.status, bar, bytes),
+                    width,
+                )
+            })
+            .collect();
+
+        let (current, total) = self.totals();
+        let (done, layers) = self.layer_counts();
+        let rate = current as f64 / elapsed.as_secs_f64().max(0.001);
+        let eta = eta(total.saturating_sub(current), rate)
+            .map(|eta| format!("ETA {}", format_eta(eta)))
+            .unwrap_or_default();
+        lines.push(truncate(
+            &format!(
+                "{} layers {}/{}  {} / {}  {}/s  {}",
+                style("Total").bold(),
+                done,
+                layers,
+                format_bytes(current),
+                format_bytes(total),
+                format_bytes(rate as u64),
+                eta
+            ),
+            width,
+        ));
+        lines
+    }
+}
+
+/// Draw a progress bar for `current` out of `total`
+fn progress_bar(current: u64, total: u64) -> String {
+    let filled = if total == 0 {
+        0
+    } else {
+        ((current.min(total) as f64 / total as f64) * BAR_WIDTH as f64).round() as usize
+    };
+    format!("[{}{}]", "=".repeat(filled), " ".repeat(BAR_WIDTH - filled))
+}
+
+/// Estimated time to download `remaining` bytes at `rate` bytes per second
+pub fn eta(remaining: u64, rate: f64) -> Option<Duration> {
+    (remaining > 0 && rate > 0.0).then(|| Duration::from_secs_f64(remaining as f64 / rate))
+}
+
+/// Format a duration as `1h02m`, `3m05s` or `12s`
+pub fn format_eta(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    if seconds >= 3600 {
+        format!("{}h{:02}m", seconds / 3600, seconds % 3600 / 60)
+    } else if seconds >= 60 {
+        format!("{}m{:02}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Truncate a line so it doesn't wrap, which would break redrawing
+fn truncate(line: &str, width: usize) -> String {
+    console::truncate_str(line, width.saturating_sub(1), "").into_owned()
+}
+
+/// How pull progress is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReportMode {
+    /// Redrawn progress bars on a terminal
+    Bars,
+    /// One line per layer status change
+    Lines,
+    /// One JSON object per event
+    Json,
+}
+
+struct ReporterState {
+    tracker: PullTracker,
+    started: Instant,
+    last_draw: Option<Instant>,
+    drawn_lines: usize,
+}
+
+/// Reports pull progress on stderr
+pub struct PullReporter {
+    mode: ReportMode,
+    state: Mutex<ReporterState>,
+}
+
+impl PullReporter {
+    /// Create a reporter suited to the output format and terminal
+    pub fn new(output: OutputFormat) -> Self {
+        let mode = match output {
+            OutputFormat::Json => ReportMode::Json,
+            OutputFormat::Table if stderr().is_terminal() => ReportMode::Bars,
+            _ => ReportMode::Lines,
+        };
+        Self {
+            mode,
+            state: Mutex::new(ReporterState {
+                tracker: PullTracker::default(),
+                started: Instant::now(),
+                last_draw: None,
+                drawn_lines: 0,
+            }),
+        }
+    }
+
+    /// Handle a progress event
+    pub fn report(&self, event: PullProgress) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match self.mode {
+            ReportMode::Json => {
+                if let Ok(line) = serde_json::to_string(&event) {
+                    eprintln!("{}", line);
+                }
+            }
+            ReportMode::Lines => {
+                let changed = match &event.layer {
+                    Some(id) => !state
+                        .tracker
+                        .layers
+                        .iter()
+                        .any(|layer| &layer.id == id && layer.status == event.status),
+                    None => true,
+                };
+                if changed {
+                    match &event.layer {
+                        Some(id) => eprintln!("{}: {}", id, event.status),
+                        None => eprintln!("{}", event.status),
+                    }
+                }
+                state.tracker.update(&event);
+            }
+            ReportMode::Bars => {
+                state.tracker.update(&event);
+                let due = match state.last_draw {
+                    Some(last) => last.elapsed() >= REDRAW_INTERVAL,
+                    None => true,
+                };
+                if due {
+                    Self::draw(&mut state);
+                }
+            }
+        }
+    }
+
+    /// Draw the final state of the progress bars
+    pub fn finish(&self) {
+        if self.mode == ReportMode::Bars {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            if !state.tracker.layers.is_empty() {
+                Self::draw(&mut state);
+            }
+        }
+    }
+
+    fn draw(state: &mut ReporterState) {
+        let lines = state
+            .tracker
+            .render(state.started.elapsed(), get_terminal_width() as usize);
+        let mut stderr = stderr();
+        if state.drawn_lines > 0 {
+            let _ = queue!(stderr, cursor::MoveUp(state.drawn_lines as u16));
+        }
+        for line in &lines {
+            let _ = queue!(stderr, Clear(ClearType::CurrentLine));
+            let _ = writeln!(stderr, "{}", line);
+        }
+        let _ = stderr.flush();
+        state.drawn_lines = lines.len();
+        state.last_draw = Some(Instant::now());
+    }
+}
+
+/// Ctrl-C handling shared by all pulls
+///
+/// Once a Ctrl-C handler is installed it stays for the rest of the process,
+/// so a single watcher cancels the pulls in progress and otherwise exits the
+/// way the default handler would.
+struct Interrupts {
+    /// Woken on Ctrl-C while pulls are in progress
+    cancel: Notify,
+    /// Number of pulls in progress
+    pulls: AtomicUsize,
+}
+
+impl Interrupts {
+    /// The shared handler, started on first use
+    fn get() -> &'static Self {
+        static INTERRUPTS: OnceLock<Interrupts> = OnceLock::new();
+        INTERRUPTS.get_or_init(|| {
+            tokio::spawn(Self::watch());
+            Self {
+                cancel: Notify::new(),
+                pulls: AtomicUsize::new(0),
+            }
+        })
+    }
+
+    async fn watch() {
+        while tokio::signal::ctrl_c().await.is_ok() {
+            let interrupts = Self::get();
+            if interrupts.pulls.load(Ordering::SeqCst) > 0 {
+                interrupts.cancel.notify_waiters();
+            } else {
+                let _ = queue!(stderr(), cursor::Show);
+                let _ = stderr().flush();
+                std::process::exit(INTERRUPTED_EXIT_CODE);
+            }
+        }
+    }
+}
+
+/// Counts a pull as in progress until dropped
+struct PullGuard(&'static Interrupts);
+
+impl Drop for PullGuard {
+    fn drop(&mut self) {
+        self.0.pulls.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Pull an image, reporting progress in the given output format
+///
+/// Ctrl-C cancels the pull: the request to the daemon is dropped, which
+/// stops the download, and a [`FlockerError::Cancelled`] error is returned.
+/// Outside of pulls, Ctrl-C still exits Flocker.
+pub async fn pull_image(
+    docker: &impl DockerOperations,
+    repository: &str,
+    tag: &str,
+    output: OutputFormat,
+) -> Result<()> {
+    let interrupts = Interrupts::get();
+    let cancelled = interrupts.cancel.notified();
+    tokio::pin!(cancelled);
+    // Listen before counting the pull, so a Ctrl-C can't be missed
+    cancelled.as_mut().enable();
+    interrupts.pulls.fetch_add(1, Ordering::SeqCst);
+    let _guard = PullGuard(interrupts);
+
+    let reporter = PullReporter::new(output);
+    let report = |event| reporter.report(event);
+    let result = tokio::select! {
+        result = docker.pull_image(repository, tag, &report) => result,
+        _ = cancelled => Err(FlockerError::Cancelled(format!(
+            "pull of {}:{}",
+            repository, tag
+        ))),
+    };
+    reporter.finish();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+
+    use super::*;
+
+    fn event(layer: &str, status: &str, progress: Option<(u64, u64)>) -> PullProgress {
+        PullProgress {
+            layer: Some(layer.to_string()),
+            status: status.to_string(),
+            current: progress.map(|(current, _)| current),
+            total: progress.map(|(_, total)| total),
+        }
+    }
+
+    #[test]
+    #[parallel]
+    fn test_pull_tracker_totals() {
+        let mut tracker = PullTracker::default();
+        tracker.update(&PullProgress {
+            layer: None,
+            status: "Pulling from fluree/server".to_string(),
+            ..Default::default()
+        });
+        tracker.update(&event("aaa", "Pulling fs layer", None));
+        tracker.update(&event("bbb", "Already exists", None));
+        tracker.update(&event("ccc", "Downloading", Some((100, 400))));
+        tracker.update(&event("aaa", "Downloading", Some((50, 200))));
+        assert_eq!(tracker.totals(), (150, 600));
+        assert_eq!(tracker.layer_counts(), (1, 3));
+
+        // Extraction progress doesn't count as downloaded bytes
+        tracker.update(&event("ccc", "Extracting", Some((10, 900))));
+        assert_eq!(tracker.totals(), (450, 600));
+
+        tracker.update(&event("aaa", "Pull complete", None));
+        tracker.update(&event("ccc", "Pull complete", None));
+        assert_eq!(tracker.totals(), (600, 600));
+        assert_eq!(tracker.layer_counts(), (3, 3));
+
+        let lines = tracker.render(Duration::from_secs(2), 120);
+        assert_eq!(lines.len(), 4);
+        assert!(lines[3].contains("3/3"));
+    }
+
+    #[test]
+    #[parallel]
+    fn test_progress_bar() {
+        assert_eq!(progress_bar(0, 0), format!("[{}]", " ".repeat(BAR_WIDTH)));
+        assert_eq!(progress_bar(5, 10).matches('=').count(), BAR_WIDTH / 2);
+        assert_eq!(progress_bar(20, 10).matches('=').count(), BAR_WIDTH);
+    }
+
+    #[test]
+    #[parallel]
+    fn test_eta() {
+        assert_eq!(eta(1000, 100.0), Some(Duration::from_secs(10)));
+        assert_eq!(eta(0, 100.0), None);
+        assert_eq!(eta(1000, 0.0), None);
+
+        assert_eq!(format_eta(Duration::from_secs(12)), "12s");
+        assert_eq!(format_eta(Duration::from_secs(185)), "3m05s");
+        assert_eq!(format_eta(Duration::from_secs(3720)), "1h02m");
+    }
+}
@@ -8,7 +8,8 @@ use crossterm::{
     cursor, execute,
     terminal::{Clear, ClearType},
 };
-use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+use dialoguer::{theme::ColorfulTheme, Confirm, FuzzySelect, Input, Select};
+use futures_util::StreamExt;
 
 /// Common UI functionality shared across components
 pub trait UserInterface {
@@ -76,20 +77,39 @@ impl UserInterface for DefaultUI {
         println!("\n{}", style(message).yellow().bold());
     }
 }
-use std::{io, path::PathBuf};
+use std::{
+    collections::HashSet,
+    io,
+    net::IpAddr,
+    path::{Path, PathBuf},
+};
 use tracing::debug;
 
 use crate::{
     cli::{format_bytes, format_duration_since},
-    config::FlureeConfig,
-    docker::{DockerOperations, FlureeImage},
+    config::{parse_network_name, parse_size, FlureeConfig},
+    docker::{
+        ports::{check_ports, next_free_port, port_conflict, DEFAULT_PORT},
+        CommandOptions, DockerOperations, FlureeImage, LedgerInfo, LogOptions, NetworkAttachment,
+        NetworkOptions, PortMapping, ResourceLimits, RestartPolicy, DEFAULT_KILL_SIGNAL,
+    },
+    fluree::{
+        backup::{backup_ledger, default_backup_file, restore_ledger},
+        copy::copy_ledger,
+        HostLedgerReader, LedgerOperations,
+    },
     state::{ContainerInfo, DataDirConfig, State},
-    ContainerStatus, FlockerError, Result,
+    ContainerStatus, FlockerError, Health, Result,
 };
 
 use super::{
     actions::{LedgerAction, RunningContainerAction},
-    hub::HubClient,
+    args::{parse_signal, WaitArgs},
+    commands::{running_containers, wait_for_ready},
+    hub::{compare_tags, sort_tags, tag_name, FetchedTags, HubClient, TagFilter, TagSource},
+    output::{branch_row, print_table, stats_row, OutputFormat, BRANCH_HEADERS, STATS_HEADERS},
+    pager::Pager,
+    progress,
 };
 
 /// Custom theme for container list formatting
@@ -175,6 +195,8 @@ pub struct CliState {
     state: State,
     config: Option<FlureeConfig>,
     hub_client: HubClient,
+    /// Image repository overriding the default in the state
+    repository: Option<String>,
 }
 
 impl Default for CliState {
@@ -184,6 +206,7 @@ impl Default for CliState {
             state: State::load().unwrap_or_default(),
             config: None,
             hub_client: HubClient::new(),
+            repository: None,
         }
     }
 }
@@ -194,6 +217,19 @@ impl CliState {
         Self::default()
     }
 
+    /// Use `repository` for images instead of the configured default
+    pub fn with_repository(mut self, repository: Option<String>) -> Self {
+        self.repository = repository;
+        self
+    }
+
+    /// The image repository to list, pull and create containers from
+    fn repository(&self) -> &str {
+        self.repository
+            .as_deref()
+            .unwrap_or(self.state.image_repository())
+    }
+
     /// Get config file configuration from user
     pub fn get_config_file_config(&mut self) -> Result<(Option<PathBuf>, Option<PathBuf>)> {
         let use_config = Confirm::with_theme(&self.theme)
@@ -297,22 +333,45 @@ impl CliState {
             .map_err(|e| FlockerError::UserInput(e.to_string()))
     }
 
-    /// Get port configuration from user
-    pub fn get_port_config(&mut self) -> Result<u16> {
-        let (default_port, _) = self.state.get_default_settings();
+    /// Get port configuration from user, suggesting the next port that is
+    /// free on `bind_address`
+    pub async fn get_port_config(
+        &mut self,
+        docker: &impl DockerOperations,
+        bind_address: IpAddr,
+    ) -> Result<u16> {
+        let mut default_port =
+            next_free_port(docker, &self.state, bind_address, DEFAULT_PORT).await?;
 
-        Input::with_theme(&self.theme)
-            .with_prompt("Enter host port to map to container port 8090")
-            .default(default_port)
-            .validate_with(|input: &u16| {
-                if *input < 1024 {
-                    Err("Port must be >= 1024")
-                } else {
-                    Ok(())
+        loop {
+            let port = Input::with_theme(&self.theme)
+                .with_prompt("Enter host port to map to container port 8090")
+                .default(default_port)
+                .validate_with(|input: &u16| {
+                    if *input < 1024 {
+                        Err("Port must be >= 1024")
+                    } else {
+                        Ok(())
+                    }
+                })
+                .interact()
+                .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+
+            match port_conflict(docker, &self.state, bind_address, port).await? {
+                None => return Ok(port),
+                Some(conflict) => {
+                    default_port = next_free_port(docker, &self.state, bind_address, port).await?;
+                    println!(
+                        "{}",
+                        style(format!(
+                            "Port {} is {}; port {} is free",
+                            port, conflict, default_port
+                        ))
+                        .yellow()
+                    );
                 }
-            })
-            .interact()
-            .map_err(|e| FlockerError::UserInput(e.to_string()))
+            }
+        }
     }
 
     /// Get data mount configuration from user
@@ -376,6 +435,277 @@ impl CliState {
         Ok(Some(canonical_path))
     }
 
+    /// Get memory, CPU and Java heap limits from user
+    pub fn get_resource_config(&mut self) -> Result<ResourceLimits> {
+        let set_limits = Confirm::with_theme(&self.theme)
+            .with_prompt("Set resource limits (memory, CPUs, Java heap)?")
+            .default(false)
+            .interact()
+            .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+
+        if !set_limits {
+            return Ok(ResourceLimits::default());
+        }
+
+        let size = |prompt: &str| -> Result<Option<u64>> {
+            let input: String = Input::with_theme(&self.theme)
+                .with_prompt(prompt)
+                .allow_empty(true)
+                .validate_with(|input: &String| {
+                    if input.trim().is_empty() {
+                        return Ok(());
+                    }
+                    parse_size(input).map(|_| ()).map_err(|e| e.to_string())
+                })
+                .interact_text()
+                .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+            match input.trim() {
+                "" => Ok(None),
+                input => parse_size(input).map(Some),
+            }
+        };
+
+        let memory = size("Memory limit, e.g. 2g (leave empty for no limit)")?;
+        let cpus: String = Input::with_theme(&self.theme)
+            .with_prompt("Number of CPUs, e.g. 1.5 (leave empty for no limit)")
+            .allow_empty(true)
+            .validate_with(|input: &String| match input.trim() {
+                "" => Ok(()),
+                input => match input.parse::<f64>() {
+                    Ok(cpus) if cpus > 0.0 => Ok(()),
+                    _ => Err("Enter a positive number"),
+                },
+            })
+            .interact_text()
+            .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+        let java_heap = size("Maximum Java heap, e.g. 1g (leave empty for the JVM default)")?;
+
+        Ok(ResourceLimits {
+            memory,
+            cpus: cpus.trim().parse().ok(),
+            java_heap,
+            java_opts_var: None,
+        })
+    }
+
+    /// Get environment variables, extra server arguments and entrypoint from user
+    pub fn get_command_config(&mut self) -> Result<CommandOptions> {
+        let customize = Confirm::with_theme(&self.theme)
+            .with_prompt("Set environment variables, server arguments or entrypoint?")
+            .default(false)
+            .interact()
+            .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+
+        if !customize {
+            return Ok(CommandOptions::default());
+        }
+
+        let mut env = Vec::new();
+        loop {
+            let entry: String = Input::with_theme(&self.theme)
+                .with_prompt("Environment variable as NAME=value (leave empty when done)")
+                .allow_empty(true)
+                .validate_with(|input: &String| {
+                    if input.is_empty() || input.split_once('=').is_some_and(|(n, _)| !n.is_empty())
+                    {
+                        Ok(())
+                    } else {
+                        Err("Use NAME=value")
+                    }
+                })
+                .interact_text()
+                .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+            if entry.is_empty() {
+                break;
+            }
+            env.push(entry);
+        }
+
+        let args: String = Input::with_theme(&self.theme)
+            .with_prompt("Extra server arguments, separated by spaces (leave empty for none)")
+            .allow_empty(true)
+            .interact_text()
+            .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+
+        let entrypoint: String = Input::with_theme(&self.theme)
+            .with_prompt("Entrypoint to run instead of the image's (leave empty to keep it)")
+            .allow_empty(true)
+            .interact_text()
+            .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+
+        Ok(CommandOptions {
+            env,
+            args: args.split_whitespace().map(str::to_string).collect(),
+            entrypoint: Some(entrypoint.trim().to_string()).filter(|e| !e.is_empty()),
+        })
+    }
+
+    /// Get the restart policy and whether `flocker up` starts the container from user
+    pub fn get_restart_config(&mut self) -> Result<(RestartPolicy, bool)> {
+        let policies = [
+            RestartPolicy::No,
+            RestartPolicy::OnFailure(None),
+            RestartPolicy::UnlessStopped,
+            RestartPolicy::Always,
+        ];
+        let labels = [
+            "Never restart",
+            "Restart after a crash (on-failure)",
+            "Restart unless stopped, also after a reboot (unless-stopped)",
+            "Always restart (always)",
+        ];
+        let selection = Select::with_theme(&self.theme)
+            .with_prompt("When should Docker restart this container?")
+            .items(&labels)
+            .default(0)
+            .interact()
+            .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+
+        let autostart = Confirm::with_theme(&self.theme)
+            .with_prompt("Start this container with `flocker up`?")
+            .default(false)
+            .interact()
+            .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+
+        Ok((policies[selection], autostart))
+    }
+
+    /// Get the bind address, Docker networks and extra published ports from user
+    ///
+    /// `remote_host` is the daemon's host when it runs on another machine;
+    /// ports are then published on all of its addresses, since its loopback
+    /// address can't be reached from here.
+    pub fn get_network_config(&mut self, remote_host: Option<&str>) -> Result<NetworkOptions> {
+        let mut network = NetworkOptions::default();
+        if let Some(host) = remote_host {
+            network.bind_address = std::net::Ipv4Addr::UNSPECIFIED.into();
+            println!(
+                "{}",
+                style(format!(
+                    "Docker runs on {}, so ports are published on all of its addresses",
+                    host
+                ))
+                .yellow()
+            );
+        }
+
+        let customize = Confirm::with_theme(&self.theme)
+            .with_prompt("Configure networking (bind address, Docker networks, extra ports)?")
+            .default(false)
+            .interact()
+            .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+
+        if !customize {
+            return Ok(network);
+        }
+
+        if remote_host.is_none() {
+            let expose = Confirm::with_theme(&self.theme)
+                .with_prompt("Allow other machines to reach Fluree? (it has no authentication)")
+                .default(false)
+                .interact()
+                .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+            if expose {
+                network.bind_address = std::net::Ipv4Addr::UNSPECIFIED.into();
+            }
+        }
+
+        // Comma-separated lists, each item checked with `validate`
+        let list = |prompt: &str, validate: fn(&str) -> Result<()>| -> Result<Vec<String>> {
+            let input: String = Input::with_theme(&self.theme)
+                .with_prompt(prompt)
+                .allow_empty(true)
+                .validate_with(|input: &String| {
+                    split_list(input)
+                        .try_for_each(validate)
+                        .map_err(|e| e.to_string())
+                })
+                .interact_text()
+                .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+            Ok(split_list(&input).map(str::to_string).collect())
+        };
+        let network_name = |item: &str| parse_network_name(item).map(|_| ());
+
+        let networks = list(
+            "Docker networks to join, separated by commas (leave empty for none)",
+            network_name,
+        )?;
+        if !networks.is_empty() {
+            let aliases = list(
+                "Names other containers can use for this one, separated by commas (leave empty for none)",
+                network_name,
+            )?;
+            network.networks = networks
+                .into_iter()
+                .map(|name| NetworkAttachment {
+                    name,
+                    aliases: aliases.clone(),
+                })
+                .collect();
+        }
+
+        network.ports = list(
+            "Extra ports to publish as HOST:CONTAINER, separated by commas (leave empty for none)",
+            |item| item.parse::<PortMapping>().map(|_| ()),
+        )?
+        .iter()
+        .map(|port| port.parse())
+        .collect::<Result<_>>()?;
+
+        Ok(network)
+    }
+
+    /// Print the resource limits and command settings a container was created with
+    fn print_container_settings(&self, id: &str) {
+        let Some(info) = self.state.get_container(id) else {
+            return;
+        };
+        let resources = &info.resources;
+        if let Some(memory) = resources.memory {
+            println!("Memory limit: {}", style(format_bytes(memory)).cyan());
+        }
+        if let Some(cpus) = resources.cpus {
+            println!("CPU limit: {}", style(cpus).cyan());
+        }
+        if let Some(heap) = resources.java_heap {
+            println!("Java heap: {}", style(format_bytes(heap)).cyan());
+        }
+        if !info.restart_policy.is_no() {
+            println!("Restart policy: {}", style(info.restart_policy).cyan());
+        }
+        if info.autostart {
+            println!("Autostart: {}", style("yes").cyan());
+        }
+        let command = &info.command;
+        if let Some(entrypoint) = &command.entrypoint {
+            println!("Entrypoint: {}", style(entrypoint).cyan());
+        }
+        if !command.args.is_empty() {
+            println!("Server arguments: {}", style(command.args.join(" ")).cyan());
+        }
+        for entry in &command.env {
+            println!("Environment: {}", style(entry).cyan());
+        }
+        let network = &info.network;
+        if !network.bind_address.is_loopback() {
+            println!("Bind address: {}", style(network.bind_address).cyan());
+        }
+        for attachment in &network.networks {
+            if attachment.aliases.is_empty() {
+                println!("Network: {}", style(&attachment.name).cyan());
+            } else {
+                println!(
+                    "Network: {} (as {})",
+                    style(&attachment.name).cyan(),
+                    attachment.aliases.join(", ")
+                );
+            }
+        }
+        for mapping in &network.ports {
+            println!("Published port: {}", style(mapping).cyan());
+        }
+    }
+
     /// Get complete configuration from user
     pub async fn get_config(
         &mut self,
@@ -383,12 +713,22 @@ impl CliState {
     ) -> Result<(FlureeImage, FlureeConfig, String)> {
         let image = self.select_image(docker).await?;
         let name = self.get_container_name()?;
-        let host_port = self.get_port_config()?;
+        // The port is checked on the address it will be published on
+        let network = self.get_network_config(docker.remote_host().as_deref())?;
+        let host_port = self.get_port_config(docker, network.bind_address).await?;
         let data_mount = self.get_data_mount_config()?;
         let (config_mount, config_file) = self.get_config_file_config()?;
-
-        let config = FlureeConfig::new(host_port, data_mount, config_mount, config_file);
+        let resources = self.get_resource_config()?;
+        let command = self.get_command_config()?;
+        let (restart_policy, autostart) = self.get_restart_config()?;
+
+        let config = FlureeConfig::new(host_port, data_mount, config_mount, config_file)
+            .with_resources(resources)
+            .with_command(command)
+            .with_network(network)
+            .with_restart(restart_policy, autostart);
         config.validate()?;
+        check_ports(docker, &self.state, &config).await?;
 
         self.config = Some(config.clone());
 
@@ -397,9 +737,10 @@ impl CliState {
 
     /// Display available Fluree images and get user selection
     pub async fn select_image(&self, docker: &impl DockerOperations) -> Result<FlureeImage> {
+        let remote_label = format!("Remote ({})", self.repository());
         let remote_or_local_selection = Select::with_theme(&self.theme)
             .with_prompt("Do you want to list remote or local Fluree images?")
-            .items(&["Remote (Docker Hub)", "Local"])
+            .items(&[remote_label.as_str(), "Local"])
             .default(0)
             .interact()
             .map_err(|e| FlockerError::UserInput(e.to_string()))?;
@@ -415,35 +756,114 @@ impl CliState {
     async fn select_remote_image(&self, docker: &impl DockerOperations) -> Result<FlureeImage> {
         println!(
             "{}",
-            style("Fetching available images from Docker Hub...").cyan()
+            style(format!(
+                "Fetching available images for {}...",
+                self.repository()
+            ))
+            .cyan()
         );
 
-        let tags = self.hub_client.fetch_tags().await?;
-
-        // Find the longest tag name for alignment
-        let max_tag_length = tags
-            .iter()
-            .map(|tag| tag.name.len())
-            .max()
+        let mut fetched = self.fetch_remote_tags(false).await?;
+
+        // Tags that are already pulled don't need to be pulled again. Local
+        // images are named by full reference, remote tags are bare.
+        let local_tags: HashSet<String> = docker
+            .list_local_images(self.repository())
+            .await
+            .map(|images| {
+                images
+                    .into_iter()
+                    .map(|image| tag_name(&image.tag.name).to_string())
+                    .collect()
+            })
             .unwrap_or_default();
 
-        let tag_strings_to_display = tags
-            .iter()
-            .map(|tag| tag.pretty_print(Some(max_tag_length)))
-            .collect::<Vec<String>>();
+        // The first entries change the filter or refresh the list, the rest
+        // are tags. Typing in the picker narrows the list down further.
+        const ACTIONS: usize = 2;
+        let mut filter = TagFilter::default();
+        loop {
+            let tags = &fetched.tags;
+            let visible = filter.apply(tags, "");
+            let max_tag_length = visible
+                .iter()
+                .map(|tag| tag.name.len())
+                .max()
+                .unwrap_or_default();
+
+            let mut items = vec![
+                format!("Showing: {} (select to change)", filter),
+                format!(
+                    "Refresh tags (fetched {})",
+                    format_duration_since(&fetched.fetched_at.to_rfc3339())
+                        .map(|since| since.to_lowercase())
+                        .unwrap_or_else(|_| "at an unknown time".to_string())
+                ),
+            ];
+            items.extend(visible.iter().map(|tag| {
+                let line = tag.pretty_print(Some(max_tag_length));
+                if local_tags.contains(&tag.name) {
+                    format!("{} {}", line, style("[local]").green())
+                } else {
+                    line
+                }
+            }));
+
+            let selection = FuzzySelect::with_theme(&self.theme)
+                .with_prompt(format!(
+                    "Select a Fluree image, type to search ({} of {} tags)",
+                    visible.len(),
+                    tags.len()
+                ))
+                .items(&items)
+                .default(if visible.is_empty() { 0 } else { ACTIONS })
+                .max_length(20)
+                .interact()
+                .map_err(|e| FlockerError::UserInput(e.to_string()))?;
 
-        let selection = Select::with_theme(&self.theme)
-            .with_prompt("Select a Fluree image")
-            .items(tag_strings_to_display.as_slice())
-            .default(0)
-            .interact()
-            .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+            match selection {
+                0 => filter = filter.cycle(),
+                1 => fetched = self.fetch_remote_tags(true).await?,
+                index => {
+                    let selected_tag = visible[index - ACTIONS].name.clone();
+                    if !local_tags.contains(&selected_tag) {
+                        match self.pull_remote_image(docker, &selected_tag).await {
+                            Err(FlockerError::Cancelled(_)) => {
+                                println!("\n{}", style("Pull cancelled").yellow());
+                                continue;
+                            }
+                            result => result?,
+                        }
+                    }
+                    return docker
+                        .get_image_by_tag(self.repository(), &selected_tag)
+                        .await;
+                }
+            }
+        }
+    }
 
-        let selected_tag = &tags[selection].name;
+    /// Fetch and sort remote tags, noting when they come from an old cache
+    async fn fetch_remote_tags(&self, refresh: bool) -> Result<FetchedTags> {
+        let mut fetched = self
+            .hub_client
+            .fetch_tags(self.repository(), refresh)
+            .await?;
+        sort_tags(&mut fetched.tags);
 
-        self.pull_remote_image(docker, selected_tag).await?;
+        if fetched.source == TagSource::Stale {
+            println!(
+                "{}",
+                style(format!(
+                    "Could not reach the registry; showing tags cached {}",
+                    format_duration_since(&fetched.fetched_at.to_rfc3339())
+                        .unwrap_or_else(|_| "earlier".to_string())
+                ))
+                .yellow()
+            );
+        }
 
-        docker.get_image_by_tag(selected_tag).await
+        Ok(fetched)
     }
 
     /// Pull a remote image from Docker Hub
@@ -451,15 +871,19 @@ impl CliState {
         println!(
             "\n{} {}",
             style("Pulling image").cyan(),
-            style(format!("fluree/server:{}", tag)).cyan().bold()
+            style(format!("{}:{}", self.repository(), tag))
+                .cyan()
+                .bold()
         );
 
-        docker.pull_image(tag).await?;
+        progress::pull_image(docker, self.repository(), tag, OutputFormat::Table).await?;
 
         println!(
             "\n{} {}",
             style("Successfully pulled").green(),
-            style(format!("fluree/server:{}", tag)).green().bold()
+            style(format!("{}:{}", self.repository(), tag))
+                .green()
+                .bold()
         );
 
         Ok(())
@@ -467,15 +891,23 @@ impl CliState {
 
     /// Select a local image
     pub async fn select_local_image(&self, docker: &impl DockerOperations) -> Result<FlureeImage> {
-        let images = docker.list_local_images().await?;
+        let mut images = docker.list_local_images(self.repository()).await?;
 
         if images.is_empty() {
-            println!("{}", style("No local Fluree images found.").yellow());
+            println!(
+                "{}",
+                style(format!("No local {} images found.", self.repository())).yellow()
+            );
             println!("Please pull an image first using:");
-            println!("{}", style("docker pull fluree/server:latest").cyan());
+            println!(
+                "{}",
+                style(format!("docker pull {}:latest", self.repository())).cyan()
+            );
             std::process::exit(1);
         }
 
+        images.sort_by(|a, b| compare_tags(&a.tag, &b.tag));
+
         // Find the longest tag for alignment
         let max_tag_length = images
             .iter()
@@ -508,14 +940,16 @@ impl CliState {
 
         let mut found_containers = vec![];
         for container in containers.into_iter() {
-            let status = docker.get_container_status(&container.id).await;
-            match status {
-                Ok(ContainerStatus::Running { .. }) | Ok(ContainerStatus::Stopped { .. }) => {
-                    found_containers.push(container);
-                }
-                _ => {
+            match docker.get_container_status(&container.id).await {
+                Ok(ContainerStatus::NotFound) => {
                     self.state.remove_container(&container.id)?;
                 }
+                Ok(status) => found_containers.push((container, Some(status))),
+                Err(e) => {
+                    // Keep the container; the daemon may only be briefly unavailable
+                    tracing::warn!("Could not get status of {}: {}", container.name, e);
+                    found_containers.push((container, None));
+                }
             }
         }
 
@@ -551,11 +985,7 @@ impl CliState {
         // Create container info strings
         let mut raw_items = vec![];
         let mut items = vec![];
-        for c in &found_containers {
-            let status = docker
-                .get_container_status(&c.id)
-                .await
-                .unwrap_or(ContainerStatus::NotFound);
+        for (c, status) in &found_containers {
             tracing::debug!("Container status: {:?}", status);
             let last_start = c
                 .last_start
@@ -570,11 +1000,9 @@ impl CliState {
                 })
                 .unwrap_or_else(|| "Never".to_string());
 
-            let status_str = match status {
-                ContainerStatus::Running { .. } => "running",
-                ContainerStatus::Stopped { .. } => "stopped",
-                ContainerStatus::NotFound => "not found",
-            };
+            let status_str = status
+                .as_ref()
+                .map_or("unknown".to_string(), |status| status.style(None));
 
             // let item = format!(
             //     "{:<name_width$} {:<status_width$} {:<image_width$} {:<port_width$} {:<time_width$}",
@@ -630,19 +1058,28 @@ impl CliState {
                 }
             }
 
-            raw_items.push((&c.name, status_str, &c.image_tag, c.port, last_start));
+            raw_items.push((
+                &c.name,
+                status,
+                status_str,
+                &c.image_tag,
+                c.port,
+                last_start,
+            ));
         }
 
-        for (name, status, image, port, time) in raw_items {
+        for (name, status, status_str, image, port, time) in raw_items {
             let item = format!(
                 "{:<name_width$} {:<status_width$} {:<image_width$} {:<port_width$} {:<time_width$}",
                 style(truncate(name, name_width)).blue().bold(),
                 // truncate(format!("STATE: {}", status).as_str(), status_width),
                 match status {
-                    "running" => style("running").green(),
-                    "stopped" => style("stopped").yellow(),
-                    "not found" => style("not found").red(),
-                    _ => style(status).cyan(),
+                    Some(status) if status.is_failure() => style(status_str).red(),
+                    Some(ContainerStatus::Running { .. }) => style(status_str).green(),
+                    Some(ContainerStatus::Stopped { .. } | ContainerStatus::Exited { .. }) => {
+                        style(status_str).yellow()
+                    }
+                    _ => style(status_str).cyan(),
                 },
                 truncate(image, image_width),
                 // truncate(format!("PORT: {}", port).as_str(), port_width),
@@ -690,7 +1127,7 @@ impl CliState {
             return Ok(None);
         }
         tracing::debug!("Selected container: {}", selection);
-        let selected_container = found_containers[selection].clone(); // Clone to avoid borrow issues
+        let selected_container = found_containers[selection].0.clone(); // Clone to avoid borrow issues
         tracing::debug!("Selected container: {:?}", selected_container);
         let status = docker.get_container_status(&selected_container.id).await?;
         debug!("Selected container status: {:?}", status);
@@ -715,6 +1152,8 @@ impl CliState {
         docker: &impl DockerOperations,
         status: ContainerStatus,
     ) -> Result<()> {
+        let status_label = status.style(None);
+        let paused = matches!(status, ContainerStatus::Paused { .. });
         match status {
             ContainerStatus::Running {
                 id,
@@ -723,6 +1162,7 @@ impl CliState {
                 data_dir,
                 config_dir,
                 started_at: _,
+                health,
             } => {
                 println!(
                     "\n{} {}",
@@ -731,12 +1171,18 @@ impl CliState {
                 );
                 println!("Container ID: {}", style(&id[..12]).cyan());
                 println!("Mapped port: {}", style(port).cyan());
+                match health {
+                    Some(Health::Unhealthy) => println!("Health: {}", style("unhealthy").red()),
+                    Some(health) => println!("Health: {}", style(health.label()).cyan()),
+                    None => {}
+                }
                 if let Some(dir) = data_dir {
                     println!("Data directory: {}", style(dir).cyan());
                 }
                 if let Some(dir) = config_dir {
                     println!("Config directory: {}", style(dir).cyan());
                 }
+                self.print_container_settings(&id);
                 println!("\n");
 
                 let selection = Select::with_theme(&self.theme)
@@ -747,10 +1193,41 @@ impl CliState {
                     .map_err(|e| FlockerError::UserInput(e.to_string()))?;
 
                 match RunningContainerAction::from_index(selection) {
+                    Some(RunningContainerAction::Restart) => {
+                        let timeout = self.get_stop_timeout()?;
+                        let now_time = chrono::Utc::now();
+                        docker.restart_container(&id, timeout).await?;
+                        self.state
+                            .update_container_start_time(&id, now_time.to_rfc3339())?;
+                        self.report_started(docker, &id, now_time.timestamp(), "restarted")
+                            .await;
+                    }
+                    Some(RunningContainerAction::Pause) => {
+                        docker.pause_container(&id).await?;
+                        println!("\n{}", style("Container paused successfully").green());
+                    }
                     Some(RunningContainerAction::Stop) => {
-                        docker.stop_container(&id).await?;
+                        let timeout = self.get_stop_timeout()?;
+                        docker.stop_container(&id, timeout).await?;
                         println!("\n{}", style("Container stopped successfully").green());
                     }
+                    Some(RunningContainerAction::Kill) => {
+                        let signal: String = Input::with_theme(&self.theme)
+                            .with_prompt("Signal to send")
+                            .default(DEFAULT_KILL_SIGNAL.to_string())
+                            .validate_with(|input: &String| {
+                                parse_signal(input).map(|_| ()).map_err(|e| e.to_string())
+                            })
+                            .interact_text()
+                            .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+                        let signal = parse_signal(&signal)?;
+                        docker.kill_container(&id, &signal).await?;
+                        println!(
+                            "\n{} {}",
+                            style("Sent signal to container:").green(),
+                            style(signal).cyan()
+                        );
+                    }
                     Some(RunningContainerAction::StopAndDestroy) => {
                         docker.remove_container(&id).await?;
                         println!("\n{}", style("Container removed successfully").green());
@@ -758,14 +1235,17 @@ impl CliState {
                     }
                     Some(RunningContainerAction::ViewStats) => {
                         let stats = docker.get_container_stats(&id).await?;
-                        println!("\n{}", stats);
+                        println!();
+                        print_table(&STATS_HEADERS, &[stats_row(&stats)]);
+                        println!();
+                    }
+                    Some(RunningContainerAction::LiveStats) => {
+                        let containers = running_containers(docker, &self.state).await?;
+                        super::dashboard::run(docker, containers).await?;
                     }
                     Some(RunningContainerAction::ViewLogs) => {
-                        // Get the last 1000 lines of logs
-                        let logs = docker.get_container_logs(&id, Some("1000")).await?;
-                        if let Ok(mut pager) = super::pager::Pager::new(&logs) {
-                            pager.display()?;
-                        }
+                        // Show the last 1000 lines and keep following new ones
+                        self.view_logs(docker, &id, true).await?;
                     }
                     Some(RunningContainerAction::ListLedgers) => {
                         self.handle_ledger_management(docker, &id).await?;
@@ -790,12 +1270,73 @@ impl CliState {
                 if let Some(time) = last_start {
                     println!("Last started: {}", style(time).yellow());
                 }
+                self.print_container_settings(&id);
+                self.handle_stopped_container(docker, &id).await?;
+            }
+            ContainerStatus::Exited {
+                id,
+                name,
+                code,
+                oom_killed,
+                finished_at,
+                last_start,
+            } => {
+                println!(
+                    "\n{} {} ({})",
+                    style("Found exited container:").yellow(),
+                    style(&name).cyan(),
+                    style(&id[..12]).dim()
+                );
+                if oom_killed {
+                    println!(
+                        "Exit code: {} {}",
+                        style(code).red(),
+                        style("(killed for running out of memory)").red()
+                    );
+                } else if code == 0 {
+                    println!("Exit code: {}", style(code).green());
+                } else {
+                    println!("Exit code: {}", style(code).red());
+                }
+                if let Some(time) = last_start {
+                    println!("Last started: {}", style(time).yellow());
+                }
+                if let Some(time) = finished_at {
+                    let ago = format_duration_since(&time).unwrap_or(time);
+                    println!("Exited: {}", style(ago).yellow());
+                }
+                self.print_container_settings(&id);
+                self.handle_stopped_container(docker, &id).await?;
+            }
+            ContainerStatus::Dead { id, name } => {
+                println!(
+                    "\n{} {} ({})",
+                    style("Found dead container:").red(),
+                    style(&name).cyan(),
+                    style(&id[..12]).dim()
+                );
+                println!("The container runtime could not stop or remove it cleanly");
+                self.handle_stopped_container(docker, &id).await?;
+            }
+            ContainerStatus::Paused { id, name, .. }
+            | ContainerStatus::Restarting { id, name, .. } => {
+                println!(
+                    "\n{} {} ({})",
+                    style("Found container:").cyan(),
+                    style(&name).cyan(),
+                    style(&id[..12]).dim()
+                );
+                println!("Status: {}", style(status_label).cyan());
 
-                let options = vec![
-                    "Start this container",
+                let mut options = vec![
                     "Get container logs",
+                    "Stop this container",
                     "Destroy this container",
+                    "Go back",
                 ];
+                if paused {
+                    options.insert(0, "Unpause this container");
+                }
                 let selection = Select::with_theme(&self.theme)
                     .with_prompt("What would you like to do?")
                     .items(&options)
@@ -803,29 +1344,23 @@ impl CliState {
                     .interact()
                     .map_err(|e| FlockerError::UserInput(e.to_string()))?;
 
-                match selection {
-                    0 => {
-                        // Start the container
-                        docker.start_container(&id).await?;
-                        let now_time = chrono::Utc::now();
-                        let now_time_string = now_time.to_rfc3339();
-                        self.state
-                            .update_container_start_time(&id, now_time_string)?;
-                        println!("\n{}", style("Container started successfully").green());
+                match options[selection] {
+                    "Unpause this container" => {
+                        docker.unpause_container(&id).await?;
+                        println!("\n{}", style("Container unpaused successfully").green());
                     }
-                    1 => {
-                        // Get the last 1000 lines of logs
-                        let logs = docker.get_container_logs(&id, Some("1000")).await?;
-                        if let Ok(mut pager) = super::pager::Pager::new(&logs) {
-                            pager.display()?;
-                        }
+                    "Get container logs" => self.view_logs(docker, &id, false).await?,
+                    "Stop this container" => {
+                        let timeout = self.get_stop_timeout()?;
+                        docker.stop_container(&id, timeout).await?;
+                        println!("\n{}", style("Container stopped successfully").green());
                     }
-                    2 => {
+                    "Destroy this container" => {
                         docker.remove_container(&id).await?;
                         println!("\n{}", style("Container removed successfully").green());
                         self.state.remove_container(&id)?;
                     }
-                    _ => unreachable!(),
+                    _ => return Ok(()),
                 }
             }
             ContainerStatus::NotFound => {
@@ -836,92 +1371,319 @@ impl CliState {
         Ok(())
     }
 
-    /// Handle ledger management for a container
-    async fn handle_ledger_management(
+    /// Offer to start, inspect or remove a container that isn't running
+    async fn handle_stopped_container(
+        &mut self,
+        docker: &impl DockerOperations,
+        id: &str,
+    ) -> Result<()> {
+        // Ledgers can still be read if the data directory is on the host
+        let host_ledgers = self
+            .state
+            .get_container(id)
+            .and_then(HostLedgerReader::for_container);
+
+        let mut options = vec!["Start this container", "Get container logs"];
+        if host_ledgers.is_some() {
+            options.push("List ledgers");
+        }
+        options.push("Destroy this container");
+        let selection = Select::with_theme(&self.theme)
+            .with_prompt("What would you like to do?")
+            .items(&options)
+            .default(0)
+            .interact()
+            .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+
+        match options[selection] {
+            "Start this container" => {
+                // Start the container and wait for Fluree to accept requests
+                let now_time = chrono::Utc::now();
+                docker.start_container(id).await?;
+                self.state
+                    .update_container_start_time(id, now_time.to_rfc3339())?;
+                self.report_started(docker, id, now_time.timestamp(), "started")
+                    .await;
+            }
+            "Get container logs" => {
+                // Get the last 1000 lines of logs
+                self.view_logs(docker, id, false).await?;
+            }
+            "List ledgers" => {
+                if let Some(reader) = &host_ledgers {
+                    self.browse_host_ledgers(reader)?;
+                }
+            }
+            "Destroy this container" => {
+                docker.remove_container(id).await?;
+                println!("\n{}", style("Container removed successfully").green());
+                self.state.remove_container(id)?;
+            }
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+
+    /// Ask how long to wait for a graceful shutdown, leaving it to the
+    /// container's default when empty
+    fn get_stop_timeout(&self) -> Result<Option<u64>> {
+        let timeout: String = Input::with_theme(&self.theme)
+            .with_prompt(
+                "Seconds to wait before killing the container (leave empty for the default)",
+            )
+            .allow_empty(true)
+            .validate_with(|input: &String| match input.trim() {
+                "" => Ok(()),
+                input => input
+                    .parse::<u64>()
+                    .map(|_| ())
+                    .map_err(|_| "Enter a whole number of seconds"),
+            })
+            .interact_text()
+            .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+        Ok(timeout.trim().parse().ok())
+    }
+
+    /// Wait for a (re)started container to accept requests and report the outcome
+    async fn report_started(
+        &self,
+        docker: &impl DockerOperations,
+        id: &str,
+        since: i64,
+        action: &str,
+    ) {
+        let ready = match self.state.get_container(id).cloned() {
+            Some(info) => {
+                wait_for_ready(
+                    docker,
+                    &info,
+                    WaitArgs::default(),
+                    since,
+                    OutputFormat::Table,
+                )
+                .await
+            }
+            None => Ok(None),
+        };
+        match ready {
+            Ok(_) => println!(
+                "\n{}",
+                style(format!("Container {} successfully", action)).green()
+            ),
+            Err(e) => println!("\n{} {}", style("ERROR:").red(), e),
+        }
+    }
+
+    /// Show container logs in the pager, optionally following new lines
+    async fn view_logs(
         &self,
         docker: &impl DockerOperations,
         container_id: &str,
+        follow: bool,
     ) -> Result<()> {
-        loop {
-            // Get list of ledgers
-            let mut ledgers = docker.list_ledgers(container_id).await?;
+        let options = LogOptions::default();
+
+        if !follow {
+            let logs = docker.get_container_logs(container_id, &options).await?;
+            if let Ok(mut pager) = Pager::new(&logs) {
+                pager.display()?;
+            }
+            return Ok(());
+        }
+
+        // Forward the log stream to the pager, which polls for new lines between key presses
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut lines = docker.follow_container_logs(container_id, &options);
+        let forward = tokio::spawn(async move {
+            while let Some(line) = lines.next().await {
+                let line = line.unwrap_or_else(|e| format!("[flocker] {}", e));
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let result = match Pager::follow(receiver) {
+            Ok(mut pager) => tokio::task::block_in_place(|| pager.display()),
+            Err(_) => Ok(()),
+        };
+        forward.abort();
+        result.map_err(FlockerError::from)
+    }
+
+    /// Ask for an archive and back up a ledger to it
+    async fn backup_ledger_to_file(
+        &self,
+        docker: &impl DockerOperations,
+        container: &ContainerInfo,
+        alias: &str,
+    ) -> Result<()> {
+        let file: String = Input::with_theme(&self.theme)
+            .with_prompt("Backup file")
+            .default(default_backup_file(alias).to_string_lossy().to_string())
+            .interact_text()
+            .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+
+        match backup_ledger(docker, container, alias, Path::new(&file)).await {
+            Ok(manifest) => println!(
+                "\n{}",
+                style(format!(
+                    "Backed up {} files ({}) to {}",
+                    manifest.files.len(),
+                    format_bytes(manifest.size()),
+                    file
+                ))
+                .green()
+                .bold()
+            ),
+            Err(e) => println!("{} {}", style("ERROR:").red(), e),
+        }
+        Ok(())
+    }
+
+    /// Ask for a backup archive and restore its ledger into a container
+    async fn restore_ledger_from_file(
+        &self,
+        docker: &impl DockerOperations,
+        container: &ContainerInfo,
+    ) -> Result<()> {
+        let file: String = Input::with_theme(&self.theme)
+            .with_prompt("Backup file to restore")
+            .validate_with(|input: &String| {
+                if Path::new(input.trim()).is_file() {
+                    Ok(())
+                } else {
+                    Err("File not found")
+                }
+            })
+            .interact_text()
+            .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+
+        match restore_ledger(docker, container, Path::new(file.trim())).await {
+            Ok(manifest) => println!(
+                "\n{}",
+                style(format!(
+                    "Ledger {} restored from {} (backed up from {} at {})",
+                    manifest.alias,
+                    file.trim(),
+                    manifest.container,
+                    manifest.created_at
+                ))
+                .green()
+                .bold()
+            ),
+            Err(e) => println!("{} {}", style("ERROR:").red(), e),
+        }
+        Ok(())
+    }
+
+    /// Ask for a target container and alias and copy a ledger there
+    async fn copy_ledger_to_container(
+        &self,
+        docker: &impl DockerOperations,
+        source: &ContainerInfo,
+        alias: &str,
+    ) -> Result<()> {
+        let mut targets: Vec<&ContainerInfo> = self
+            .state
+            .containers
+            .values()
+            .filter(|target| target.id != source.id)
+            .collect();
+        if targets.is_empty() {
+            println!(
+                "\n{}",
+                style("There are no other containers to copy to").yellow()
+            );
+            return Ok(());
+        }
+        targets.sort_by(|a, b| a.name.cmp(&b.name));
+        let labels: Vec<String> = targets
+            .iter()
+            .map(|target| format!("{} ({})", target.name, target.image_tag))
+            .collect();
+        let selection = Select::with_theme(&self.theme)
+            .with_prompt("Copy to which container?")
+            .items(&labels)
+            .default(0)
+            .interact()
+            .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+        let target = targets[selection];
+
+        match copy_ledger(docker, source, alias, target).await {
+            Ok(copied) => println!(
+                "\n{}",
+                style(format!(
+                    "Copied {} to {}, at commit {}",
+                    alias, target.name, copied.commit_count
+                ))
+                .green()
+                .bold()
+            ),
+            Err(e) => println!("{} {}", style("ERROR:").red(), e),
+        }
+        Ok(())
+    }
 
+    /// Browse the ledgers of a stopped container in its host data directory
+    fn browse_host_ledgers(&self, reader: &HostLedgerReader) -> Result<()> {
+        loop {
+            let mut ledgers = reader.list()?;
             if ledgers.is_empty() {
                 println!("\n{}", style("No ledgers found").yellow());
                 return Ok(());
             }
-
             ledgers.sort_by(|a, b| b.last_commit_time.cmp(&a.last_commit_time));
 
-            let raw_values: Vec<(String, String, String, Option<String>, String, String)> = ledgers
-                .iter()
-                .map(|ledger| {
-                    let duration = format_duration_since(&ledger.last_commit_time)
-                        .unwrap_or_else(|_| "unknown time ago".to_string());
-                    let size = format_bytes(ledger.size);
-                    let commit_count = ledger.commit_count.to_string();
-                    let last_index = ledger.last_index.map(|i| i.to_string());
-                    let flakes_count = ledger.flakes_count.to_string();
-                    let alias = ledger.alias.clone();
-
-                    (
-                        alias,
-                        duration,
-                        commit_count,
-                        last_index,
-                        size,
-                        flakes_count,
-                    )
-                })
-                .collect();
+            let mut ledger_strings = ledger_menu_items(&ledgers);
+            ledger_strings.push("Go Back to Container Menu".to_string());
 
-            // Step 2: Determine the max width for each field
-            let max_widths = raw_values.iter().fold(
-                (0, 0, 0, 0, 0, 0),
-                |(max_alias, max_duration, max_commits, max_index, max_size, max_flakes),
-                 (alias, duration, commits, index, size, flakes)| {
-                    (
-                        max_alias.max(alias.len()),
-                        max_duration.max(duration.len()),
-                        max_commits.max(commits.len()),
-                        max_index.max(index.as_ref().unwrap_or(&"None".to_string()).len()),
-                        max_size.max(size.len()),
-                        max_flakes.max(flakes.len()),
-                    )
-                },
-            );
+            let selection = Select::with_theme(&self.theme)
+                .with_prompt("Select a ledger to view its details")
+                .items(&ledger_strings)
+                .default(0)
+                .interact()
+                .map_err(|e| FlockerError::UserInput(e.to_string()))?;
 
-            let (alias_w, duration_w, commits_w, index_w, size_w, flakes_w) = max_widths;
+            let Some(selected_ledger) = ledgers.get(selection) else {
+                return Ok(());
+            };
+            print_branches(selected_ledger);
+            let details = reader.details(&selected_ledger.alias)?;
+            println!("\n{}", style("Ledger Details:").cyan().bold());
+            println!("{}", details);
+        }
+    }
 
-            // Format ledger information for display
-            let mut ledger_strings: Vec<String> = raw_values
-                .into_iter()
-                .map(|(alias, duration, commit_count, last_index, size, flakes_count)| {
-                    format!(
-                        "{:<alias_w$} Last commit: {:<duration_w$}  Commits: {:<commits_w$}  Last Indexed Commit: {:<index_w$}  Size: {:<size_w$}  Flakes: {:<flakes_w$}",
-                        style(alias).cyan(),
-                        style(duration).yellow(),
-                        style(commit_count.clone()).green(),
-                        match last_index {
-                            Some(i) => if i == commit_count {
-                                style(i).green()
-                            } else {
-                                style(i).yellow()
-                            },
-                            None => style("None".to_string()).red(),
-                        },
-                        style(size).blue(),
-                        style(flakes_count).blue(),
-                        alias_w = alias_w,
-                        duration_w = duration_w,
-                        commits_w = commits_w,
-                        index_w = index_w,
-                        size_w = size_w,
-                        flakes_w = flakes_w,
-                    )
-                })
-                .collect();
+    /// Handle ledger management for a container
+    async fn handle_ledger_management(
+        &self,
+        docker: &impl DockerOperations,
+        container_id: &str,
+    ) -> Result<()> {
+        let container = self
+            .state
+            .get_container(container_id)
+            .cloned()
+            .ok_or_else(|| {
+                FlockerError::Config(format!("Container {} not found in state", container_id))
+            })?;
+        let ledger_operations = LedgerOperations::new(docker, &container);
+
+        loop {
+            // Get list of ledgers
+            let mut ledgers = ledger_operations.list().await?;
+
+            if ledgers.is_empty() {
+                println!("\n{}", style("No ledgers found").yellow());
+            }
+
+            ledgers.sort_by(|a, b| b.last_commit_time.cmp(&a.last_commit_time));
+            let mut ledger_strings = ledger_menu_items(&ledgers);
 
+            ledger_strings.push("Create a New Ledger".to_string());
+            ledger_strings.push("Restore a Ledger from Backup".to_string());
             ledger_strings.push("Go Back to Container Menu".to_string());
 
             // Let user select a ledger
@@ -932,12 +1694,40 @@ impl CliState {
                 .interact()
                 .map_err(|e| FlockerError::UserInput(e.to_string()))?;
 
-            let selected_ledger = if selection < ledgers.len() {
-                &ledgers[selection]
-            } else {
-                break;
+            let selected_ledger = match selection.checked_sub(ledgers.len()) {
+                None => &ledgers[selection],
+                Some(0) => {
+                    let alias: String = Input::with_theme(&self.theme)
+                        .with_prompt("Alias for the new ledger")
+                        .validate_with(|input: &String| {
+                            if input.trim().is_empty() {
+                                Err("Alias must not be empty")
+                            } else {
+                                Ok(())
+                            }
+                        })
+                        .interact_text()
+                        .map_err(|e| FlockerError::UserInput(e.to_string()))?;
+                    match ledger_operations.create(alias.trim()).await {
+                        Ok(()) => println!(
+                            "\n{}",
+                            style(format!("Ledger {} created", alias.trim()))
+                                .green()
+                                .bold()
+                        ),
+                        Err(e) => println!("{} {}", style("ERROR:").red(), e),
+                    }
+                    continue;
+                }
+                Some(1) => {
+                    self.restore_ledger_from_file(docker, &container).await?;
+                    continue;
+                }
+                Some(_) => break,
             };
 
+            print_branches(selected_ledger);
+
             // Show ledger actions
             let action_selection = Select::with_theme(&self.theme)
                 .with_prompt("What would you like to do?")
@@ -948,12 +1738,18 @@ impl CliState {
 
             match LedgerAction::from_index(action_selection) {
                 Some(LedgerAction::ViewDetails) => {
-                    let details = docker
-                        .get_ledger_details(container_id, &selected_ledger.path)
-                        .await?;
+                    let details = ledger_operations.details(&selected_ledger.alias).await?;
                     println!("\n{}", style("Ledger Details:").cyan().bold());
                     println!("{}", details);
                 }
+                Some(LedgerAction::Backup) => {
+                    self.backup_ledger_to_file(docker, &container, &selected_ledger.alias)
+                        .await?;
+                }
+                Some(LedgerAction::Copy) => {
+                    self.copy_ledger_to_container(docker, &container, &selected_ledger.alias)
+                        .await?;
+                }
                 Some(LedgerAction::Delete) => {
                     println!(
                         "\n{} {}",
@@ -976,8 +1772,8 @@ impl CliState {
                         .map_err(|e| FlockerError::UserInput(e.to_string()))?;
 
                     if confirmation == "delete" {
-                        docker
-                            .delete_ledger(container_id, &selected_ledger.path)
+                        ledger_operations
+                            .drop_ledger(&selected_ledger.alias)
                             .await?;
                         println!("\n{}", style("Ledger deleted successfully").green().bold());
                         // Break the loop to refresh ledger list
@@ -997,7 +1793,7 @@ impl CliState {
     }
 
     /// Display success message for container creation
-    pub fn display_success(&self, container: &ContainerInfo) {
+    pub fn display_success(&self, container: &ContainerInfo, remote_host: Option<&str>) {
         println!(
             "\n{}",
             style("Container started successfully!").green().bold()
@@ -1019,12 +1815,101 @@ impl CliState {
             );
         }
 
-        println!("\nFluree will be available at:");
-        println!(
-            "{}",
-            style(format!("http://localhost:{}", container.port))
-                .cyan()
-                .underlined()
-        );
+        if let Some(url) = container.url(remote_host) {
+            println!("\nFluree is available at:");
+            println!("{}", style(url).cyan().underlined());
+        }
+    }
+}
+
+/// Print the branches of a ledger
+fn print_branches(ledger: &LedgerInfo) {
+    if ledger.branches.is_empty() {
+        return;
     }
+    let rows: Vec<Vec<String>> = ledger.branches.iter().map(branch_row).collect();
+    println!();
+    print_table(&BRANCH_HEADERS, &rows);
+}
+
+/// Format ledgers as aligned menu entries
+fn ledger_menu_items(ledgers: &[LedgerInfo]) -> Vec<String> {
+    let raw_values: Vec<(String, String, String, Option<String>, String, String)> = ledgers
+        .iter()
+        .map(|ledger| {
+            let duration = format_duration_since(&ledger.last_commit_time)
+                .unwrap_or_else(|_| "unknown time ago".to_string());
+            let size = format_bytes(ledger.size);
+            let commit_count = ledger.commit_count.to_string();
+            let last_index = ledger.last_index.map(|i| i.to_string());
+            let flakes_count = ledger.flakes_count.to_string();
+            let alias = ledger.alias.clone();
+
+            (
+                alias,
+                duration,
+                commit_count,
+                last_index,
+                size,
+                flakes_count,
+            )
+        })
+        .collect();
+
+    // Step 2: Determine the max width for each field
+    let max_widths = raw_values.iter().fold(
+        (0, 0, 0, 0, 0, 0),
+        |(max_alias, max_duration, max_commits, max_index, max_size, max_flakes),
+         (alias, duration, commits, index, size, flakes)| {
+            (
+                max_alias.max(alias.len()),
+                max_duration.max(duration.len()),
+                max_commits.max(commits.len()),
+                max_index.max(index.as_ref().unwrap_or(&"None".to_string()).len()),
+                max_size.max(size.len()),
+                max_flakes.max(flakes.len()),
+            )
+        },
+    );
+
+    let (alias_w, duration_w, commits_w, index_w, size_w, flakes_w) = max_widths;
+
+    // Format ledger information for display
+    raw_values
+        .into_iter()
+        .zip(ledgers)
+        .map(|((alias, duration, commit_count, last_index, size, flakes_count), ledger)| {
+            format!(
+                "{:<alias_w$} Last commit: {:<duration_w$}  Commits: {:<commits_w$}  Last Indexed Commit: {:<index_w$}  Size: {:<size_w$}  Flakes: {:<flakes_w$}  Branches: {}",
+                style(alias).cyan(),
+                style(duration).yellow(),
+                style(commit_count.clone()).green(),
+                match last_index {
+                    Some(i) => if i == commit_count {
+                        style(i).green()
+                    } else {
+                        style(i).yellow()
+                    },
+                    None => style("None".to_string()).red(),
+                },
+                style(size).blue(),
+                style(flakes_count).blue(),
+                style(ledger.branches.len()).blue(),
+                alias_w = alias_w,
+                duration_w = duration_w,
+                commits_w = commits_w,
+                index_w = index_w,
+                size_w = size_w,
+                flakes_w = flakes_w,
+            )
+        })
+        .collect()
+}
+
+/// Non-empty, trimmed items of a comma-separated list
+fn split_list(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
 }
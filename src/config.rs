@@ -1,12 +1,23 @@
 //! Configuration management for Flocker.
 //!
 //! This module handles configuration settings for Fluree containers,
-//! including port mappings and volume mounts.
+//! including port mappings, volume mounts, resource limits, networking and
+//! the container command.
 
+use crate::cli::format_bytes;
+use crate::docker::{
+    CommandOptions, NetworkOptions, ResourceLimits, RestartPolicy, DEFAULT_JAVA_OPTS_VAR,
+};
 use crate::error::FlockerError;
 use crate::Result;
 use std::path::PathBuf;
 
+/// Smallest memory limit Docker accepts
+const MIN_MEMORY: u64 = 6 * 1024 * 1024;
+
+/// Smallest maximum heap size the JVM starts with
+const MIN_JAVA_HEAP: u64 = 2 * 1024 * 1024;
+
 /// Configuration for a Fluree container instance
 #[derive(Debug, Clone)]
 pub struct FlureeConfig {
@@ -18,6 +29,16 @@ pub struct FlureeConfig {
     pub config_mount: Option<PathBuf>,
     /// Name of the config file to use
     pub config_file: Option<PathBuf>,
+    /// Memory, CPU and Java heap limits
+    pub resources: ResourceLimits,
+    /// Environment variables, extra server arguments and entrypoint
+    pub command: CommandOptions,
+    /// When the daemon restarts the container
+    pub restart_policy: RestartPolicy,
+    /// Whether `flocker up` starts the container
+    pub autostart: bool,
+    /// Bind address, Docker networks and extra published ports
+    pub network: NetworkOptions,
 }
 
 impl Default for FlureeConfig {
@@ -27,6 +48,11 @@ impl Default for FlureeConfig {
             data_mount: None,
             config_mount: None,
             config_file: None,
+            resources: ResourceLimits::default(),
+            command: CommandOptions::default(),
+            restart_policy: RestartPolicy::default(),
+            autostart: false,
+            network: NetworkOptions::default(),
         }
     }
 }
@@ -44,9 +70,39 @@ impl FlureeConfig {
             data_mount,
             config_mount,
             config_file,
+            resources: ResourceLimits::default(),
+            command: CommandOptions::default(),
+            restart_policy: RestartPolicy::default(),
+            autostart: false,
+            network: NetworkOptions::default(),
         }
     }
 
+    /// Set memory, CPU and Java heap limits
+    pub fn with_resources(mut self, resources: ResourceLimits) -> Self {
+        self.resources = resources;
+        self
+    }
+
+    /// Set environment variables, extra server arguments and the entrypoint
+    pub fn with_command(mut self, command: CommandOptions) -> Self {
+        self.command = command;
+        self
+    }
+
+    /// Set the restart policy and whether `flocker up` starts the container
+    pub fn with_restart(mut self, restart_policy: RestartPolicy, autostart: bool) -> Self {
+        self.restart_policy = restart_policy;
+        self.autostart = autostart;
+        self
+    }
+
+    /// Set the bind address, Docker networks and extra published ports
+    pub fn with_network(mut self, network: NetworkOptions) -> Self {
+        self.network = network;
+        self
+    }
+
     /// Validate the configuration
     pub fn validate(&self) -> Result<()> {
         // Validate port number
@@ -112,6 +168,136 @@ impl FlureeConfig {
             ));
         }
 
+        self.validate_resources()?;
+        self.validate_command()?;
+        self.validate_network()
+    }
+
+    /// Validate memory, CPU and Java heap limits
+    fn validate_resources(&self) -> Result<()> {
+        let resources = &self.resources;
+        if let Some(memory) = resources.memory {
+            if memory < MIN_MEMORY {
+                return Err(FlockerError::Config(format!(
+                    "Memory limit must be at least {}",
+                    format_bytes(MIN_MEMORY)
+                )));
+            }
+        }
+
+        if let Some(cpus) = resources.cpus {
+            if !cpus.is_finite() || cpus <= 0.0 {
+                return Err(FlockerError::Config(
+                    "CPU limit must be a positive number".to_string(),
+                ));
+            }
+        }
+
+        if let Some(heap) = resources.java_heap {
+            if heap < MIN_JAVA_HEAP {
+                return Err(FlockerError::Config(format!(
+                    "Java heap must be at least {}",
+                    format_bytes(MIN_JAVA_HEAP)
+                )));
+            }
+            // The JVM needs memory beyond the heap for metaspace, threads and buffers
+            if let Some(memory) = resources.memory {
+                if heap >= memory {
+                    return Err(FlockerError::Config(format!(
+                        "Java heap ({}) must be smaller than the memory limit ({})",
+                        format_bytes(heap),
+                        format_bytes(memory)
+                    )));
+                }
+            }
+        }
+
+        if let Some(var) = &resources.java_opts_var {
+            if !is_env_name(var) {
+                return Err(FlockerError::Config(format!(
+                    "Invalid environment variable name for JVM options: {}",
+                    var
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate environment variables and the entrypoint
+    fn validate_command(&self) -> Result<()> {
+        for entry in &self.command.env {
+            match entry.split_once('=') {
+                Some((name, _)) if is_env_name(name) => {}
+                _ => {
+                    return Err(FlockerError::Config(format!(
+                        "Invalid environment variable '{}': expected NAME=value",
+                        entry
+                    )))
+                }
+            }
+        }
+
+        // The heap setting would be overwritten by, or overwrite, the same variable
+        if self.resources.java_heap.is_some() {
+            let java_opts_var = self
+                .resources
+                .java_opts_var
+                .as_deref()
+                .unwrap_or(DEFAULT_JAVA_OPTS_VAR);
+            if self.command.env_names().any(|name| name == java_opts_var) {
+                return Err(FlockerError::Config(format!(
+                    "{} is set both by the Java heap limit and as an environment variable; add -Xmx to the variable instead",
+                    java_opts_var
+                )));
+            }
+        }
+
+        if let Some(entrypoint) = &self.command.entrypoint {
+            if entrypoint.trim().is_empty() {
+                return Err(FlockerError::Config(
+                    "Entrypoint must not be empty".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate Docker network names, aliases and extra ports
+    fn validate_network(&self) -> Result<()> {
+        for network in &self.network.networks {
+            if !is_network_name(&network.name) {
+                return Err(FlockerError::Config(format!(
+                    "Invalid network name '{}'",
+                    network.name
+                )));
+            }
+            if let Some(alias) = network.aliases.iter().find(|alias| !is_network_name(alias)) {
+                return Err(FlockerError::Config(format!(
+                    "Invalid network alias '{}'",
+                    alias
+                )));
+            }
+        }
+
+        let mut host_ports = vec![self.host_port];
+        for mapping in &self.network.ports {
+            if mapping.host_port < 1024 {
+                return Err(FlockerError::Config(format!(
+                    "Host port must be greater than 1023: {}",
+                    mapping
+                )));
+            }
+            if host_ports.contains(&mapping.host_port) {
+                return Err(FlockerError::Config(format!(
+                    "Host port {} is mapped more than once",
+                    mapping.host_port
+                )));
+            }
+            host_ports.push(mapping.host_port);
+        }
+
         Ok(())
     }
 
@@ -123,10 +309,68 @@ impl FlureeConfig {
             data_mount_path: self.data_mount,
             config_mount_path: self.config_mount,
             config_file: self.config_file,
+            resources: self.resources,
+            command: self.command,
+            restart_policy: self.restart_policy,
+            network: self.network,
         }
     }
 }
 
+/// Whether `name` is a valid environment variable name
+fn is_env_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Whether `name` is a valid Docker network name or alias
+fn is_network_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// Parse a Docker network name or alias
+pub fn parse_network_name(value: &str) -> Result<String> {
+    if is_network_name(value) {
+        Ok(value.to_string())
+    } else {
+        Err(FlockerError::UserInput(format!(
+            "Invalid network name '{}': use letters, digits, '_', '.' and '-'",
+            value
+        )))
+    }
+}
+
+/// Parse a size like `512m`, `2g` or `1.5GiB` into bytes
+///
+/// Units are binary, as in Docker's `--memory`, and a plain number is bytes.
+pub fn parse_size(value: &str) -> Result<u64> {
+    let value = value.trim().to_ascii_lowercase();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let multiplier: u64 = match unit.trim() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1024,
+        "m" | "mb" | "mib" => 1024 * 1024,
+        "g" | "gb" | "gib" => 1024 * 1024 * 1024,
+        "t" | "tb" | "tib" => 1024 * 1024 * 1024 * 1024,
+        _ => 0,
+    };
+    match number.parse::<f64>() {
+        Ok(number) if multiplier > 0 && number.is_finite() => {
+            Ok((number * multiplier as f64).round() as u64)
+        }
+        _ => Err(FlockerError::UserInput(format!(
+            "Invalid size '{}': expected a number with an optional unit, like 512m or 2g",
+            value
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serial_test::parallel;
@@ -241,4 +485,148 @@ mod tests {
         // Change back to the original directory
         std::env::set_current_dir(original_dir).unwrap();
     }
+
+    #[test]
+    #[parallel]
+    fn test_parse_size() {
+        assert_eq!(parse_size("512").unwrap(), 512);
+        assert_eq!(parse_size("512m").unwrap(), 512 * 1024 * 1024);
+        assert_eq!(parse_size("2G").unwrap(), 2 * 1024 * 1024 * 1024);
+        assert_eq!(parse_size("1.5GiB").unwrap(), 1536 * 1024 * 1024);
+        assert!(parse_size("").is_err());
+        assert!(parse_size("lots").is_err());
+        assert!(parse_size("5x").is_err());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_resource_limits() {
+        let config = |resources| FlureeConfig::default().with_resources(resources);
+        let gb = 1024 * 1024 * 1024;
+
+        let valid = ResourceLimits {
+            memory: Some(2 * gb),
+            cpus: Some(1.5),
+            java_heap: Some(gb),
+            java_opts_var: Some("JAVA_TOOL_OPTIONS".to_string()),
+        };
+        assert!(config(valid.clone()).validate().is_ok());
+
+        let heap_too_large = ResourceLimits {
+            java_heap: Some(2 * gb),
+            ..valid.clone()
+        };
+        assert!(config(heap_too_large).validate().is_err());
+
+        let no_cpus = ResourceLimits {
+            cpus: Some(0.0),
+            ..valid.clone()
+        };
+        assert!(config(no_cpus).validate().is_err());
+
+        let tiny_memory = ResourceLimits {
+            memory: Some(1024),
+            java_heap: None,
+            ..valid.clone()
+        };
+        assert!(config(tiny_memory).validate().is_err());
+
+        let bad_var = ResourceLimits {
+            java_opts_var: Some("JAVA OPTS".to_string()),
+            ..valid
+        };
+        assert!(config(bad_var).validate().is_err());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_command_options() {
+        let config = |command| FlureeConfig::default().with_command(command);
+
+        let valid = CommandOptions {
+            env: vec![
+                "FLUREE_PROFILE=dev".to_string(),
+                "EMPTY=".to_string(),
+                "URL=http://x?a=b".to_string(),
+            ],
+            args: vec!["--log-level".to_string(), "debug".to_string()],
+            entrypoint: Some("/bin/sh".to_string()),
+        };
+        assert!(config(valid.clone()).validate().is_ok());
+
+        let missing_value = CommandOptions {
+            env: vec!["FLUREE_PROFILE".to_string()],
+            ..Default::default()
+        };
+        assert!(config(missing_value).validate().is_err());
+
+        let bad_name = CommandOptions {
+            env: vec!["1ST=x".to_string()],
+            ..Default::default()
+        };
+        assert!(config(bad_name).validate().is_err());
+
+        let empty_entrypoint = CommandOptions {
+            entrypoint: Some(" ".to_string()),
+            ..Default::default()
+        };
+        assert!(config(empty_entrypoint).validate().is_err());
+
+        let heap_conflict = FlureeConfig::default()
+            .with_resources(ResourceLimits {
+                java_heap: Some(512 * 1024 * 1024),
+                ..Default::default()
+            })
+            .with_command(CommandOptions {
+                env: vec!["JAVA_OPTS=-Xss1m".to_string()],
+                ..Default::default()
+            });
+        assert!(heap_conflict.validate().is_err());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_network_options() {
+        use crate::docker::{NetworkAttachment, PortMapping};
+
+        let config = |network| FlureeConfig::default().with_network(network);
+        let attachment = |name: &str, alias: &str| NetworkAttachment {
+            name: name.to_string(),
+            aliases: vec![alias.to_string()],
+        };
+        let ports = |ports: &[&str]| -> Vec<PortMapping> {
+            ports.iter().map(|port| port.parse().unwrap()).collect()
+        };
+
+        let valid = NetworkOptions {
+            networks: vec![attachment("app_net", "fluree.local")],
+            ports: ports(&["9000:9000"]),
+            ..Default::default()
+        };
+        assert!(config(valid).validate().is_ok());
+
+        let bad_alias = NetworkOptions {
+            networks: vec![attachment("app_net", "-fluree")],
+            ..Default::default()
+        };
+        assert!(config(bad_alias).validate().is_err());
+
+        let bad_name = NetworkOptions {
+            networks: vec![attachment("app net", "fluree")],
+            ..Default::default()
+        };
+        assert!(config(bad_name).validate().is_err());
+
+        let low_port = NetworkOptions {
+            ports: ports(&["80:8080"]),
+            ..Default::default()
+        };
+        assert!(config(low_port).validate().is_err());
+
+        let duplicate_port = NetworkOptions {
+            ports: ports(&["8090:9000"]),
+            ..Default::default()
+        };
+        assert!(config(duplicate_port).validate().is_err());
+    }
 }
@@ -0,0 +1,128 @@
+//! Docker CLI configuration.
+//!
+//! This module reads the parts of the Docker CLI's `config.json` that
+//! flocker needs, so it connects to the same daemon and uses the same
+//! registry credentials as `docker` does.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::{FlockerError, Result};
+
+/// Settings from the Docker CLI's `config.json`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DockerConfigFile {
+    /// Name of the context selected with `docker context use`
+    pub current_context: Option<String>,
+    /// Credentials stored by `docker login`, keyed by registry
+    #[serde(default)]
+    pub auths: HashMap<String, AuthEntry>,
+    /// Credential helper used for all registries, e.g. "desktop" or "osxkeychain"
+    pub creds_store: Option<String>,
+    /// Credential helpers for specific registries
+    #[serde(default)]
+    pub cred_helpers: HashMap<String, String>,
+}
+
+/// A registry entry in the `auths` section
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AuthEntry {
+    /// Base64 encoded `username:password`
+    pub auth: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    #[serde(rename = "identitytoken")]
+    pub identity_token: Option<String>,
+}
+
+impl DockerConfigFile {
+    /// Load `config.json` from the given Docker config directory
+    ///
+    /// A missing file is treated as an empty configuration, as the Docker
+    /// CLI does.
+    pub fn load(config_dir: &Path) -> Result<Self> {
+        let path = config_dir.join("config.json");
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => {
+                return Err(FlockerError::ConfigFile {
+                    message: "Failed to read Docker config".to_string(),
+                    path,
+                    source: e.into(),
+                })
+            }
+        };
+
+        serde_json::from_str(&content).map_err(|e| FlockerError::ConfigFile {
+            message: "Failed to parse Docker config".to_string(),
+            path,
+            source: e.into(),
+        })
+    }
+}
+
+/// The Docker CLI config directory: `$DOCKER_CONFIG`, or `~/.docker`
+pub fn docker_config_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("DOCKER_CONFIG").filter(|dir| !dir.is_empty()) {
+        return PathBuf::from(dir);
+    }
+    directories::BaseDirs::new()
+        .map(|dirs| dirs.home_dir().join(".docker"))
+        .unwrap_or_else(|| PathBuf::from(".docker"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    #[parallel]
+    fn test_load_docker_config() {
+        let dir = tempdir().unwrap();
+        assert!(DockerConfigFile::load(dir.path())
+            .unwrap()
+            .current_context
+            .is_none());
+
+        std::fs::write(
+            dir.path().join("config.json"),
+            r#"{"auths": {}, "currentContext": "remote"}"#,
+        )
+        .unwrap();
+        let config = DockerConfigFile::load(dir.path()).unwrap();
+        assert_eq!(config.current_context.as_deref(), Some("remote"));
+
+        std::fs::write(
+            dir.path().join("config.json"),
+            r#"{
+                "auths": {
+                    "https://index.docker.io/v1/": { "auth": "dXNlcjpwYXNz" },
+                    "registry.internal": {}
+                },
+                "credsStore": "desktop",
+                "credHelpers": { "123.dkr.ecr.us-east-1.amazonaws.com": "ecr-login" }
+            }"#,
+        )
+        .unwrap();
+        let config = DockerConfigFile::load(dir.path()).unwrap();
+        assert_eq!(config.auths.len(), 2);
+        assert_eq!(
+            config.auths["https://index.docker.io/v1/"].auth.as_deref(),
+            Some("dXNlcjpwYXNz")
+        );
+        assert_eq!(config.creds_store.as_deref(), Some("desktop"));
+        assert_eq!(
+            config.cred_helpers["123.dkr.ecr.us-east-1.amazonaws.com"],
+            "ecr-login"
+        );
+
+        std::fs::write(dir.path().join("config.json"), "not json").unwrap();
+        assert!(DockerConfigFile::load(dir.path()).is_err());
+    }
+}
@@ -0,0 +1,341 @@
+//! Registry credential resolution.
+//!
+//! This module finds credentials for a registry the same way the Docker
+//! CLI does, from the `credHelpers`, `credsStore` and `auths` sections of
+//! `config.json`, so images can be pulled from private registries.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bollard::auth::DockerCredentials;
+use serde::Deserialize;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use super::config_file::{docker_config_dir, AuthEntry, DockerConfigFile};
+use super::runtime::registry_host;
+use crate::{FlockerError, Result};
+
+/// Registry name used for repositories without a registry host
+pub const DOCKER_HUB_REGISTRY: &str = "docker.io";
+
+/// Server address the Docker CLI uses for Docker Hub credentials
+const DOCKER_HUB_SERVER: &str = "https://index.docker.io/v1/";
+
+/// Credentials for a single registry
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegistryCredentials {
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// OAuth refresh token, used instead of a password by some helpers
+    pub identity_token: Option<String>,
+}
+
+impl RegistryCredentials {
+    /// Username and password, if both are known
+    pub fn basic(&self) -> Option<(&str, &str)> {
+        Some((self.username.as_deref()?, self.password.as_deref()?))
+    }
+
+    fn from_auth_entry(entry: &AuthEntry) -> Option<Self> {
+        if let Some((username, password)) = entry
+            .auth
+            .as_deref()
+            .and_then(|auth| STANDARD.decode(auth.trim()).ok())
+            .and_then(|decoded| String::from_utf8(decoded).ok())
+            .and_then(|decoded| {
+                decoded
+                    .split_once(':')
+                    .map(|(u, p)| (u.to_string(), p.to_string()))
+            })
+        {
+            return Some(Self {
+                username: Some(username),
+                password: Some(password),
+                identity_token: entry.identity_token.clone(),
+            });
+        }
+
+        let credentials = Self {
+            username: entry.username.clone(),
+            password: entry.password.clone(),
+            identity_token: entry.identity_token.clone(),
+        };
+        (credentials != Self::default()).then_some(credentials)
+    }
+}
+
+impl From<RegistryCredentials> for DockerCredentials {
+    fn from(credentials: RegistryCredentials) -> Self {
+        DockerCredentials {
+            username: credentials.username,
+            password: credentials.password,
+            identitytoken: credentials.identity_token,
+            ..Default::default()
+        }
+    }
+}
+
+/// Output of `docker-credential-<helper> get`
+#[derive(Debug, Deserialize)]
+struct HelperOutput {
+    #[serde(rename = "Username")]
+    username: String,
+    #[serde(rename = "Secret")]
+    secret: String,
+}
+
+/// The registry a repository is pulled from
+pub fn registry_of(repository: &str) -> &str {
+    registry_host(repository).unwrap_or(DOCKER_HUB_REGISTRY)
+}
+
+/// Look up credentials for the registry `repository` lives in
+///
+/// Failures are logged and treated as anonymous access, so public images
+/// can still be pulled when a credential helper is broken. Credential
+/// helpers are run on a blocking thread, since they are separate programs.
+pub async fn credentials_for_repository(repository: &str) -> Option<RegistryCredentials> {
+    let registry = registry_of(repository).to_string();
+    let result = tokio::task::spawn_blocking({
+        let registry = registry.clone();
+        move || {
+            DockerConfigFile::load(&docker_config_dir())
+                .and_then(|config| resolve_credentials(&config, &registry))
+        }
+    })
+    .await
+    .unwrap_or_else(|e| Err(FlockerError::Docker(e.to_string())));
+    match result {
+        Ok(credentials) => credentials,
+        Err(e) => {
+            tracing::warn!("Could not read credentials for {}: {}", registry, e);
+            None
+        }
+    }
+}
+
+/// Resolve credentials for a registry host, e.g. `docker.io` or `registry.example.com:5000`
+pub fn resolve_credentials(
+    config: &DockerConfigFile,
+    registry: &str,
+) -> Result<Option<RegistryCredentials>> {
+    resolve_credentials_with(config, registry, run_credential_helper)
+}
+
+/// Resolve credentials using `helper` to run credential helper programs
+///
+/// Per-registry `credHelpers` take precedence over `credsStore`, which
+/// takes precedence over credentials stored directly in `auths`.
+fn resolve_credentials_with(
+    config: &DockerConfigFile,
+    registry: &str,
+    helper: impl Fn(&str, &str) -> Result<Option<RegistryCredentials>>,
+) -> Result<Option<RegistryCredentials>> {
+    let is_hub = matches!(
+        registry,
+        DOCKER_HUB_REGISTRY | "index.docker.io" | "registry-1.docker.io"
+    );
+    let server = if is_hub { DOCKER_HUB_SERVER } else { registry };
+
+    let specific_helper = config.cred_helpers.iter().find_map(|(key, name)| {
+        (hostname(key) == hostname(server) || (is_hub && hostname(key) == DOCKER_HUB_REGISTRY))
+            .then_some(name)
+    });
+    if let Some(name) = specific_helper {
+        return helper(name, server);
+    }
+
+    if let Some(name) = &config.creds_store {
+        if let Some(credentials) = helper(name, server)? {
+            return Ok(Some(credentials));
+        }
+    }
+
+    Ok(config
+        .auths
+        .iter()
+        .filter(|(key, _)| hostname(key) == hostname(server))
+        .find_map(|(_, entry)| RegistryCredentials::from_auth_entry(entry)))
+}
+
+/// Strip the scheme and path from a registry key, as the Docker CLI does
+fn hostname(key: &str) -> &str {
+    let key = key
+        .strip_prefix("https://")
+        .or_else(|| key.strip_prefix("http://"))
+        .unwrap_or(key);
+    key.split('/').next().unwrap_or(key)
+}
+
+/// Run `docker-credential-<helper> get` for a server
+fn run_credential_helper(helper: &str, server: &str) -> Result<Option<RegistryCredentials>> {
+    let program = format!("docker-credential-{}", helper);
+    let error = |message: String| FlockerError::Docker(format!("{} failed: {}", program, message));
+
+    let mut child = Command::new(&program)
+        .arg("get")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| error(e.to_string()))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(server.as_bytes())
+            .map_err(|e| error(e.to_string()))?;
+    }
+    let output = child.wait_with_output().map_err(|e| error(e.to_string()))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // Helpers report missing entries on stdout or stderr depending on the platform
+        if stdout.contains("credentials not found") || stderr.contains("credentials not found") {
+            return Ok(None);
+        }
+        return Err(error(format!("{}{}", stdout.trim(), stderr.trim())));
+    }
+
+    parse_helper_output(&stdout).map_err(error)
+}
+
+fn parse_helper_output(stdout: &str) -> std::result::Result<Option<RegistryCredentials>, String> {
+    let output: HelperOutput = serde_json::from_str(stdout).map_err(|e| e.to_string())?;
+    // A username of "<token>" means the secret is an identity token
+    Ok(Some(if output.username == "<token>" {
+        RegistryCredentials {
+            identity_token: Some(output.secret),
+            ..Default::default()
+        }
+    } else {
+        RegistryCredentials {
+            username: Some(output.username),
+            password: Some(output.secret),
+            identity_token: None,
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+
+    use super::*;
+
+    fn config(json: &str) -> DockerConfigFile {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn no_helper(_: &str, _: &str) -> Result<Option<RegistryCredentials>> {
+        panic!("No credential helper should be run")
+    }
+
+    #[test]
+    #[parallel]
+    fn test_credentials_from_auths() {
+        // "user:pass"
+        let config = config(
+            r#"{"auths": {
+                "https://index.docker.io/v1/": { "auth": "dXNlcjpwYXNz" },
+                "registry.internal:5000": { "username": "ci", "password": "secret" },
+                "empty.example.com": {}
+            }}"#,
+        );
+
+        let hub = resolve_credentials_with(&config, "docker.io", no_helper)
+            .unwrap()
+            .unwrap();
+        assert_eq!(hub.basic(), Some(("user", "pass")));
+
+        let internal = resolve_credentials_with(&config, "registry.internal:5000", no_helper)
+            .unwrap()
+            .unwrap();
+        assert_eq!(internal.basic(), Some(("ci", "secret")));
+
+        assert!(
+            resolve_credentials_with(&config, "empty.example.com", no_helper)
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            resolve_credentials_with(&config, "other.example.com", no_helper)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    #[parallel]
+    fn test_credential_helper_precedence() {
+        let config = config(
+            r#"{
+                "auths": { "registry.internal": { "auth": "dXNlcjpwYXNz" } },
+                "credsStore": "desktop",
+                "credHelpers": { "registry.internal": "pass" }
+            }"#,
+        );
+
+        let helper = |name: &str, server: &str| {
+            Ok(Some(RegistryCredentials {
+                username: Some(name.to_string()),
+                password: Some(server.to_string()),
+                identity_token: None,
+            }))
+        };
+
+        let credentials = resolve_credentials_with(&config, "registry.internal", helper)
+            .unwrap()
+            .unwrap();
+        assert_eq!(credentials.basic(), Some(("pass", "registry.internal")));
+
+        let credentials = resolve_credentials_with(&config, "docker.io", helper)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            credentials.basic(),
+            Some(("desktop", "https://index.docker.io/v1/"))
+        );
+    }
+
+    #[test]
+    #[parallel]
+    fn test_creds_store_falls_back_to_auths() {
+        let config = config(
+            r#"{
+                "auths": { "registry.internal": { "auth": "dXNlcjpwYXNz" } },
+                "credsStore": "desktop"
+            }"#,
+        );
+        let credentials = resolve_credentials_with(&config, "registry.internal", |_, _| Ok(None))
+            .unwrap()
+            .unwrap();
+        assert_eq!(credentials.basic(), Some(("user", "pass")));
+    }
+
+    #[test]
+    #[parallel]
+    fn test_parse_helper_output() {
+        let credentials =
+            parse_helper_output(r#"{"ServerURL":"r.example.com","Username":"me","Secret":"pw"}"#)
+                .unwrap()
+                .unwrap();
+        assert_eq!(credentials.basic(), Some(("me", "pw")));
+
+        let credentials = parse_helper_output(r#"{"Username":"<token>","Secret":"refresh"}"#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(credentials.identity_token.as_deref(), Some("refresh"));
+        assert!(credentials.basic().is_none());
+
+        assert!(parse_helper_output("not json").is_err());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_registry_of() {
+        assert_eq!(registry_of("fluree/server"), "docker.io");
+        assert_eq!(
+            registry_of("registry.internal:5000/team/fluree"),
+            "registry.internal:5000"
+        );
+    }
+}
@@ -0,0 +1,500 @@
+//! Docker daemon endpoint resolution.
+//!
+//! This module decides which Docker daemon to talk to, following the same
+//! precedence as the Docker CLI:
+//! 1. The `--host` flag
+//! 2. `DOCKER_HOST` (with `DOCKER_TLS_VERIFY` and `DOCKER_CERT_PATH`)
+//! 3. The context named by `DOCKER_CONTEXT`, or `currentContext` in `config.json`
+//! 4. The platform's default local socket
+
+use bollard::{Docker, API_DEFAULT_VERSION};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::config_file::{docker_config_dir, DockerConfigFile};
+use crate::{FlockerError, Result};
+
+/// Seconds to wait for the daemon before a request fails
+const TIMEOUT_SECS: u64 = 120;
+
+/// Name of the built-in context that uses `DOCKER_HOST` or the default socket
+const DEFAULT_CONTEXT: &str = "default";
+
+#[cfg(unix)]
+pub(crate) const DEFAULT_HOST: &str = "unix:///var/run/docker.sock";
+#[cfg(windows)]
+pub(crate) const DEFAULT_HOST: &str = "npipe:////./pipe/docker_engine";
+
+/// Client certificate files for a TLS endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsFiles {
+    pub ca: PathBuf,
+    pub cert: PathBuf,
+    pub key: PathBuf,
+}
+
+impl TlsFiles {
+    /// The `ca.pem`, `cert.pem` and `key.pem` files in a directory
+    fn in_dir(dir: &Path) -> Self {
+        Self {
+            ca: dir.join("ca.pem"),
+            cert: dir.join("cert.pem"),
+            key: dir.join("key.pem"),
+        }
+    }
+}
+
+/// Where an endpoint setting came from, for error messages
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EndpointSource {
+    Flag,
+    /// An environment variable such as `DOCKER_HOST`
+    Environment(&'static str),
+    /// A runtime socket found on disk
+    Socket,
+    Context(String),
+    Default,
+}
+
+/// A resolved Docker daemon endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DockerEndpoint {
+    /// Daemon address, e.g. `unix:///var/run/docker.sock` or `tcp://host:2376`
+    pub host: String,
+    pub tls: Option<TlsFiles>,
+    pub source: EndpointSource,
+}
+
+impl fmt::Display for DockerEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.source {
+            EndpointSource::Flag => write!(f, "{} (from --host)", self.host),
+            EndpointSource::Environment(var) => write!(f, "{} (from {})", self.host, var),
+            EndpointSource::Context(name) => write!(f, "{} (from context '{}')", self.host, name),
+            EndpointSource::Socket | EndpointSource::Default => write!(f, "{}", self.host),
+        }
+    }
+}
+
+/// Environment settings that affect endpoint resolution
+#[derive(Debug, Clone, Default)]
+pub struct EndpointEnv {
+    pub docker_host: Option<String>,
+    pub docker_tls_verify: bool,
+    pub docker_cert_path: Option<PathBuf>,
+    pub docker_context: Option<String>,
+    pub config_dir: PathBuf,
+    /// Podman's equivalent of `DOCKER_HOST`
+    pub container_host: Option<String>,
+    pub xdg_runtime_dir: Option<PathBuf>,
+}
+
+impl EndpointEnv {
+    /// Read the settings from the process environment
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        Self {
+            docker_host: var("DOCKER_HOST"),
+            // The Docker CLI enables TLS when DOCKER_TLS_VERIFY is set to anything non-empty
+            docker_tls_verify: var("DOCKER_TLS_VERIFY").is_some(),
+            docker_cert_path: var("DOCKER_CERT_PATH").map(PathBuf::from),
+            docker_context: var("DOCKER_CONTEXT"),
+            config_dir: docker_config_dir(),
+            container_host: var("CONTAINER_HOST"),
+            xdg_runtime_dir: var("XDG_RUNTIME_DIR").map(PathBuf::from),
+        }
+    }
+}
+
+/// Context metadata stored in `contexts/meta/<hash>/meta.json`
+#[derive(Debug, Deserialize)]
+struct ContextMeta {
+    #[serde(rename = "Endpoints", default)]
+    endpoints: std::collections::HashMap<String, ContextEndpoint>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContextEndpoint {
+    #[serde(rename = "Host")]
+    host: Option<String>,
+}
+
+/// Resolve the endpoint to connect to, given an optional `--host` value
+pub fn resolve_endpoint(host_flag: Option<&str>, env: &EndpointEnv) -> Result<DockerEndpoint> {
+    if let Some(host) = host_flag {
+        return Ok(env_endpoint(host, EndpointSource::Flag, env));
+    }
+
+    if let Some(host) = &env.docker_host {
+        return Ok(env_endpoint(
+            host,
+            EndpointSource::Environment("DOCKER_HOST"),
+            env,
+        ));
+    }
+
+    let context = match &env.docker_context {
+        Some(context) => Some(context.clone()),
+        None => DockerConfigFile::load(&env.config_dir)?.current_context,
+    };
+    if let Some(context) = context.filter(|name| name != DEFAULT_CONTEXT) {
+        return context_endpoint(&context, &env.config_dir);
+    }
+
+    Ok(DockerEndpoint {
+        host: DEFAULT_HOST.to_string(),
+        tls: None,
+        source: EndpointSource::Default,
+    })
+}
+
+/// An endpoint given directly, using TLS settings from the environment
+pub(crate) fn env_endpoint(
+    host: &str,
+    source: EndpointSource,
+    env: &EndpointEnv,
+) -> DockerEndpoint {
+    let tls = (env.docker_tls_verify || host.starts_with("https://")).then(|| {
+        let dir = env
+            .docker_cert_path
+            .clone()
+            .unwrap_or_else(|| env.config_dir.clone());
+        TlsFiles::in_dir(&dir)
+    });
+    DockerEndpoint {
+        host: host.to_string(),
+        tls,
+        source,
+    }
+}
+
+/// The endpoint stored in a named Docker context
+fn context_endpoint(name: &str, config_dir: &Path) -> Result<DockerEndpoint> {
+    let hash = format!("{:x}", Sha256::digest(name.as_bytes()));
+    let meta_path = config_dir
+        .join("contexts")
+        .join("meta")
+        .join(&hash)
+        .join("meta.json");
+
+    let content = std::fs::read_to_string(&meta_path).map_err(|e| {
+        FlockerError::Docker(format!(
+            "Docker context '{}' not found ({}): {}",
+            name,
+            meta_path.display(),
+            e
+        ))
+    })?;
+    let meta: ContextMeta =
+        serde_json::from_str(&content).map_err(|e| FlockerError::ConfigFile {
+            message: format!("Failed to parse Docker context '{}'", name),
+            path: meta_path.clone(),
+            source: e.into(),
+        })?;
+
+    let host = meta
+        .endpoints
+        .get("docker")
+        .and_then(|endpoint| endpoint.host.clone())
+        .ok_or_else(|| {
+            FlockerError::Docker(format!("Docker context '{}' has no Docker endpoint", name))
+        })?;
+
+    // TLS material for a context lives in contexts/tls/<hash>/docker
+    let tls_dir = config_dir
+        .join("contexts")
+        .join("tls")
+        .join(&hash)
+        .join("docker");
+    let tls = tls_dir
+        .join("ca.pem")
+        .exists()
+        .then(|| TlsFiles::in_dir(&tls_dir));
+
+    Ok(DockerEndpoint {
+        host,
+        tls,
+        source: EndpointSource::Context(name.to_string()),
+    })
+}
+
+impl DockerEndpoint {
+    /// Whether the daemon runs on this machine, so it publishes ports here
+    pub fn is_local(&self) -> bool {
+        self.remote_host().is_none()
+    }
+
+    /// Host name of a daemon on another machine, where its published ports
+    /// are reached
+    ///
+    /// Returns `None` for sockets, pipes and loopback addresses. IPv6
+    /// addresses keep their brackets, so they can be used in URLs.
+    pub fn remote_host(&self) -> Option<&str> {
+        let host = self.host.as_str();
+        if host.starts_with("unix://") || host.starts_with('/') || host.starts_with("npipe://") {
+            return None;
+        }
+        let address = host.split_once("://").map_or(host, |(_, address)| address);
+        let address = address.trim_end_matches('/');
+        let address = address
+            .rsplit_once('@')
+            .map_or(address, |(_, address)| address);
+        let hostname = address
+            .rsplit_once(':')
+            .filter(|(_, port)| port.chars().all(|c| c.is_ascii_digit()))
+            .map_or(address, |(hostname, _)| hostname);
+        let loopback = matches!(
+            hostname.trim_start_matches('[').trim_end_matches(']'),
+            "localhost" | "127.0.0.1" | "::1"
+        );
+        (!loopback).then_some(hostname)
+    }
+
+    /// Create a client for this endpoint
+    ///
+    /// This does not contact the daemon; use `Docker::ping` to check it is reachable.
+    pub fn connect(&self) -> Result<Docker> {
+        let error = |e: bollard::errors::Error| {
+            FlockerError::Docker(format!("Failed to connect to Docker at {}: {}", self, e))
+        };
+        let host = self.host.as_str();
+
+        #[cfg(unix)]
+        if host.starts_with("unix://") || host.starts_with('/') {
+            return Docker::connect_with_unix(host, TIMEOUT_SECS, API_DEFAULT_VERSION)
+                .map_err(error);
+        }
+
+        #[cfg(windows)]
+        if host.starts_with("npipe://") {
+            return Docker::connect_with_named_pipe(host, TIMEOUT_SECS, API_DEFAULT_VERSION)
+                .map_err(error);
+        }
+
+        if host.starts_with("tcp://") || host.starts_with("http://") || host.starts_with("https://")
+        {
+            return match &self.tls {
+                Some(tls) => Docker::connect_with_ssl(
+                    host,
+                    &tls.key,
+                    &tls.cert,
+                    &tls.ca,
+                    TIMEOUT_SECS,
+                    API_DEFAULT_VERSION,
+                ),
+                None => Docker::connect_with_http(host, TIMEOUT_SECS, API_DEFAULT_VERSION),
+            }
+            .map_err(error);
+        }
+
+        Err(FlockerError::Docker(format!(
+            "Unsupported Docker endpoint {}: expected a unix://, npipe:// or tcp:// address",
+            self
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn env_in(config_dir: &Path) -> EndpointEnv {
+        EndpointEnv {
+            config_dir: config_dir.to_path_buf(),
+            ..Default::default()
+        }
+    }
+
+    fn write_context(config_dir: &Path, name: &str, host: &str, with_tls: bool) {
+        let hash = format!("{:x}", Sha256::digest(name.as_bytes()));
+        let meta_dir = config_dir.join("contexts/meta").join(&hash);
+        std::fs::create_dir_all(&meta_dir).unwrap();
+        std::fs::write(
+            meta_dir.join("meta.json"),
+            format!(
+                r#"{{"Name":"{}","Metadata":{{}},"Endpoints":{{"docker":{{"Host":"{}","SkipTLSVerify":false}}}}}}"#,
+                name, host
+            ),
+        )
+        .unwrap();
+        if with_tls {
+            let tls_dir = config_dir.join("contexts/tls").join(&hash).join("docker");
+            std::fs::create_dir_all(&tls_dir).unwrap();
+            std::fs::write(tls_dir.join("ca.pem"), "").unwrap();
+        }
+    }
+
+    #[test]
+    #[parallel]
+    fn test_default_endpoint() {
+        let dir = tempdir().unwrap();
+        let endpoint = resolve_endpoint(None, &env_in(dir.path())).unwrap();
+        assert_eq!(endpoint.host, DEFAULT_HOST);
+        assert_eq!(endpoint.source, EndpointSource::Default);
+        assert!(endpoint.tls.is_none());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_flag_overrides_environment() {
+        let dir = tempdir().unwrap();
+        let env = EndpointEnv {
+            docker_host: Some("tcp://env:2375".to_string()),
+            ..env_in(dir.path())
+        };
+
+        let endpoint = resolve_endpoint(Some("unix:///tmp/docker.sock"), &env).unwrap();
+        assert_eq!(endpoint.host, "unix:///tmp/docker.sock");
+        assert_eq!(endpoint.source, EndpointSource::Flag);
+
+        let endpoint = resolve_endpoint(None, &env).unwrap();
+        assert_eq!(endpoint.host, "tcp://env:2375");
+        assert_eq!(endpoint.to_string(), "tcp://env:2375 (from DOCKER_HOST)");
+    }
+
+    #[test]
+    #[parallel]
+    fn test_tls_from_environment() {
+        let dir = tempdir().unwrap();
+        let env = EndpointEnv {
+            docker_host: Some("tcp://remote:2376".to_string()),
+            docker_tls_verify: true,
+            docker_cert_path: Some(PathBuf::from("/certs")),
+            ..env_in(dir.path())
+        };
+        let endpoint = resolve_endpoint(None, &env).unwrap();
+        assert_eq!(
+            endpoint.tls,
+            Some(TlsFiles {
+                ca: PathBuf::from("/certs/ca.pem"),
+                cert: PathBuf::from("/certs/cert.pem"),
+                key: PathBuf::from("/certs/key.pem"),
+            })
+        );
+
+        // Without DOCKER_CERT_PATH, certificates are read from the config dir
+        let env = EndpointEnv {
+            docker_cert_path: None,
+            ..env
+        };
+        let endpoint = resolve_endpoint(None, &env).unwrap();
+        assert_eq!(endpoint.tls.unwrap().ca, dir.path().join("ca.pem"));
+    }
+
+    #[test]
+    #[parallel]
+    fn test_current_context_from_config() {
+        let dir = tempdir().unwrap();
+        write_context(dir.path(), "remote", "tcp://10.0.0.5:2376", true);
+        std::fs::write(
+            dir.path().join("config.json"),
+            r#"{"currentContext": "remote"}"#,
+        )
+        .unwrap();
+
+        let endpoint = resolve_endpoint(None, &env_in(dir.path())).unwrap();
+        assert_eq!(endpoint.host, "tcp://10.0.0.5:2376");
+        assert_eq!(
+            endpoint.source,
+            EndpointSource::Context("remote".to_string())
+        );
+        assert!(endpoint.tls.unwrap().ca.ends_with("docker/ca.pem"));
+    }
+
+    #[test]
+    #[parallel]
+    fn test_docker_context_overrides_config() {
+        let dir = tempdir().unwrap();
+        write_context(
+            dir.path(),
+            "rootless",
+            "unix:///run/user/1000/docker.sock",
+            false,
+        );
+        std::fs::write(
+            dir.path().join("config.json"),
+            r#"{"currentContext": "missing"}"#,
+        )
+        .unwrap();
+
+        let env = EndpointEnv {
+            docker_context: Some("rootless".to_string()),
+            ..env_in(dir.path())
+        };
+        let endpoint = resolve_endpoint(None, &env).unwrap();
+        assert_eq!(endpoint.host, "unix:///run/user/1000/docker.sock");
+        assert!(endpoint.tls.is_none());
+
+        // The "default" context means the default socket
+        let env = EndpointEnv {
+            docker_context: Some("default".to_string()),
+            ..env_in(dir.path())
+        };
+        assert_eq!(
+            resolve_endpoint(None, &env).unwrap().source,
+            EndpointSource::Default
+        );
+
+        // A missing context is an error that names it
+        let error = resolve_endpoint(None, &env_in(dir.path())).unwrap_err();
+        assert!(error.to_string().contains("'missing'"));
+    }
+
+    #[test]
+    #[parallel]
+    fn test_unsupported_scheme() {
+        let endpoint = DockerEndpoint {
+            host: "ssh://user@host".to_string(),
+            tls: None,
+            source: EndpointSource::Flag,
+        };
+        let error = endpoint.connect().unwrap_err();
+        assert!(error.to_string().contains("ssh://user@host (from --host)"));
+    }
+
+    #[test]
+    #[parallel]
+    fn test_is_local() {
+        let endpoint = |host: &str| DockerEndpoint {
+            host: host.to_string(),
+            tls: None,
+            source: EndpointSource::Flag,
+        };
+        assert!(endpoint("unix:///var/run/docker.sock").is_local());
+        assert!(endpoint("npipe:////./pipe/docker_engine").is_local());
+        assert!(endpoint("tcp://localhost:2375").is_local());
+        assert!(endpoint("tcp://127.0.0.1:2376/").is_local());
+        assert!(endpoint("tcp://[::1]:2375").is_local());
+        assert!(!endpoint("tcp://build-box:2376").is_local());
+        assert!(!endpoint("https://10.0.0.5:2376").is_local());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_remote_host() {
+        let endpoint = |host: &str| DockerEndpoint {
+            host: host.to_string(),
+            tls: None,
+            source: EndpointSource::Flag,
+        };
+        assert_eq!(endpoint("unix:///var/run/docker.sock").remote_host(), None);
+        assert_eq!(endpoint("tcp://localhost:2375").remote_host(), None);
+        assert_eq!(
+            endpoint("tcp://build-box:2376").remote_host(),
+            Some("build-box")
+        );
+        assert_eq!(
+            endpoint("ssh://deploy@build-box").remote_host(),
+            Some("build-box")
+        );
+        assert_eq!(
+            endpoint("tcp://[fd00::1]:2376/").remote_host(),
+            Some("[fd00::1]")
+        );
+    }
+}
@@ -1,21 +1,31 @@
 use async_trait::async_trait;
 use bollard::container::{
-    Config, CreateContainerOptions, InspectContainerOptions, ListContainersOptions,
-    RemoveContainerOptions, StartContainerOptions, StopContainerOptions,
+    Config, CreateContainerOptions, InspectContainerOptions, KillContainerOptions,
+    ListContainersOptions, RemoveContainerOptions, RestartContainerOptions, StartContainerOptions,
+    StopContainerOptions,
 };
+use bollard::network::{ConnectNetworkOptions, CreateNetworkOptions, InspectNetworkOptions};
 use bollard::Docker;
 use chrono::TimeZone;
-#[allow(unused_imports)]
-use futures_util::stream::StreamExt;
-use num_format::{Locale, ToFormattedString};
+use futures_util::stream::{BoxStream, StreamExt};
 use std::collections::HashMap;
+use std::net::IpAddr;
 use std::path::PathBuf;
 
 use crate::cli::hub::Tag;
 use crate::error::FlockerError;
+use crate::fluree::nameservice::ledger_from_file;
 use crate::state::ContainerInfo;
-use crate::{ContainerStatus, Result};
-
+use crate::{ContainerStatus, Health, Result};
+
+use super::credentials::credentials_for_repository;
+use super::endpoint::EndpointEnv;
+use super::ports::is_host_port_free;
+use super::readiness::READINESS_PATH;
+use super::runtime::{
+    is_repository_reference, normalize_image_reference, registry_host, resolve_runtime_endpoint,
+    Runtime,
+};
 use super::types::*;
 
 /// Docker operations trait
@@ -28,11 +38,40 @@ pub trait DockerOperations {
     async fn start_container(&self, container_id: &str) -> Result<()>;
 
     /// Stop a running container
-    async fn stop_container(&self, container_id: &str) -> Result<()>;
+    ///
+    /// The container is killed if it hasn't exited `timeout` seconds after
+    /// being asked to stop; without a timeout the daemon's default is used.
+    async fn stop_container(&self, container_id: &str, timeout: Option<u64>) -> Result<()>;
+
+    /// Stop and start a container again, with the same timeout as stopping
+    async fn restart_container(&self, container_id: &str, timeout: Option<u64>) -> Result<()>;
+
+    /// Suspend all processes in a running container
+    async fn pause_container(&self, container_id: &str) -> Result<()>;
+
+    /// Resume a paused container
+    async fn unpause_container(&self, container_id: &str) -> Result<()>;
+
+    /// Send a signal (e.g. `SIGKILL` or `9`) to the main process of a container
+    async fn kill_container(&self, container_id: &str, signal: &str) -> Result<()>;
 
     /// Remove a container
     async fn remove_container(&self, container_id: &str) -> Result<()>;
 
+    /// Check whether a host port is published by a running container or,
+    /// when the daemon runs on this machine, held by another process
+    async fn is_port_in_use(&self, address: IpAddr, port: u16) -> Result<bool>;
+
+    /// Ask the server in a container whether it accepts requests, from
+    /// inside the container
+    ///
+    /// Returns `None` if the image has no HTTP client to ask with.
+    async fn probe_server(&self, container_id: &str) -> Result<Option<bool>>;
+
+    /// Host name of the daemon when it runs on another machine, where its
+    /// published ports are reached; `None` for a daemon on this machine
+    fn remote_host(&self) -> Option<String>;
+
     /// Create and start a new container
     async fn create_and_start_container(
         &self,
@@ -41,6 +80,9 @@ pub trait DockerOperations {
         name: &str,
     ) -> Result<ContainerInfo>;
 
+    /// Attach a container to a network, creating the network if it doesn't exist
+    async fn connect_network(&self, container_id: &str, network: &NetworkAttachment) -> Result<()>;
+
     /// List ledgers in a container
     async fn list_ledgers(&self, container_id: &str) -> Result<Vec<LedgerInfo>>;
 
@@ -50,64 +92,91 @@ pub trait DockerOperations {
     /// Delete a ledger
     async fn delete_ledger(&self, container_id: &str, path: &str) -> Result<()>;
 
-    /// Get container stats
-    async fn get_container_stats(&self, container_id: &str) -> Result<String>;
+    /// Download a file or directory from a container as a tar archive
+    async fn download_from_container(&self, container_id: &str, path: &str) -> Result<Vec<u8>>;
+
+    /// Extract a tar archive into a directory of a container
+    async fn upload_to_container(
+        &self,
+        container_id: &str,
+        path: &str,
+        archive: Vec<u8>,
+    ) -> Result<()>;
+
+    /// Get a snapshot of container resource usage
+    async fn get_container_stats(&self, container_id: &str) -> Result<ContainerStats>;
+
+    /// Stream resource usage for a container, roughly one sample per second
+    fn stream_container_stats(
+        &self,
+        container_id: &str,
+    ) -> BoxStream<'static, Result<ContainerStats>>;
 
     /// Get container logs
-    async fn get_container_logs(&self, container_id: &str, tail: Option<&str>) -> Result<String>;
+    async fn get_container_logs(&self, container_id: &str, options: &LogOptions) -> Result<String>;
+
+    /// Stream container logs as they are written, one formatted line per item
+    fn follow_container_logs(
+        &self,
+        container_id: &str,
+        options: &LogOptions,
+    ) -> BoxStream<'static, Result<String>>;
 
     /// Pull a Docker image
-    async fn pull_image(&self, tag: &str) -> Result<()>;
+    async fn pull_image(
+        &self,
+        repository: &str,
+        tag: &str,
+        progress: &(dyn Fn(PullProgress) + Send + Sync),
+    ) -> Result<()>;
 
     /// Get image by tag
-    async fn get_image_by_tag(&self, tag_str: &str) -> Result<FlureeImage>;
+    async fn get_image_by_tag(&self, repository: &str, tag_str: &str) -> Result<FlureeImage>;
 
-    /// List local images
-    async fn list_local_images(&self) -> Result<Vec<FlureeImage>>;
+    /// List local images of a repository
+    async fn list_local_images(&self, repository: &str) -> Result<Vec<FlureeImage>>;
 }
 
 /// Docker operations manager
 pub struct DockerManager {
     docker: Docker,
+    runtime: Runtime,
+    /// Host of the daemon if it publishes ports on another machine
+    remote_host: Option<String>,
 }
 
 impl DockerManager {
-    /// Create a new DockerManager instance
+    /// Create a new DockerManager instance using the endpoint from the environment
     pub async fn new() -> Result<Self> {
-        let docker = Docker::connect_with_unix_defaults()
-            .map_err(|e| FlockerError::Docker(format!("Failed to connect to Docker: {}", e)))?;
-        Ok(Self { docker })
+        Self::connect(None, None).await
     }
 
-    /// Check if a port is already in use by another container
-    pub async fn is_port_in_use(&self, port: u16) -> Result<bool> {
-        let mut filters = HashMap::new();
-        filters.insert(String::from("status"), vec![String::from("running")]);
-
-        let options = Some(ListContainersOptions {
-            filters,
-            ..Default::default()
-        });
-
-        let containers = self
-            .docker
-            .list_containers(options)
-            .await
-            .map_err(|e| FlockerError::Docker(format!("Failed to list containers: {}", e)))?;
-
-        for container in containers {
-            if let Some(ports) = container.ports {
-                for port_mapping in ports {
-                    if let Some(public_port) = port_mapping.public_port {
-                        if public_port == port {
-                            return Ok(true);
-                        }
-                    }
-                }
-            }
-        }
+    /// Connect to the daemon at `host`, or the endpoint from the environment
+    /// and Docker contexts if no host is given
+    ///
+    /// The runtime is detected from the endpoint unless one is given.
+    pub async fn connect(host: Option<&str>, runtime: Option<Runtime>) -> Result<Self> {
+        let (endpoint, runtime) =
+            resolve_runtime_endpoint(host, runtime, &EndpointEnv::from_env())?;
+        tracing::debug!("Connecting to {} at {}", runtime, endpoint);
+
+        let docker = endpoint.connect()?;
+        docker.ping().await.map_err(|e| {
+            FlockerError::Docker(format!(
+                "Failed to connect to {} at {}: {}",
+                runtime, endpoint, e
+            ))
+        })?;
+        Ok(Self {
+            docker,
+            runtime,
+            remote_host: endpoint.remote_host().map(str::to_string),
+        })
+    }
 
-        Ok(false)
+    /// The container runtime behind the API
+    pub fn runtime(&self) -> Runtime {
+        self.runtime
     }
 }
 
@@ -121,59 +190,18 @@ impl DockerOperations for DockerManager {
         {
             Ok(container) => {
                 tracing::debug!("Container: {:#?}", container);
-                let state = container.state.unwrap_or_default();
-                let running = state.running.unwrap_or(false);
-
-                let name = container
-                    .name
-                    .unwrap_or_default()
-                    .trim_start_matches('/')
-                    .to_string();
-                let started_at = state.started_at;
-
-                if running {
-                    let host_config = container.host_config.unwrap_or_default();
-
-                    // Extract port mapping
-                    let port = host_config
-                        .port_bindings
-                        .and_then(|bindings| {
-                            bindings
-                                .get("8090/tcp")
-                                .and_then(|binding| binding.as_ref())
-                                .and_then(|binding| binding.first())
-                                .and_then(|port| port.host_port.as_ref())
-                                .and_then(|port| port.parse().ok())
-                        })
-                        .unwrap_or(8090);
-
-                    // Extract data directory
-                    let data_dir = host_config
-                        .binds
-                        .clone()
-                        .and_then(|binds| binds.first().cloned());
-                    let config_dir = host_config.binds.and_then(|binds| binds.get(1).cloned());
-                    tracing::debug!("Data dir: {:?}", data_dir);
-                    Ok(ContainerStatus::Running {
-                        id: container_id.to_string(),
-                        name,
-                        port,
-                        data_dir,
-                        config_dir,
-                        started_at,
-                    })
-                } else {
-                    Ok(ContainerStatus::Stopped {
-                        id: container_id.to_string(),
-                        name,
-                        last_start: started_at,
-                    })
-                }
+                Ok(Self::status_from_inspect(container_id, container))
             }
-            Err(e) => {
-                tracing::debug!("Container not found: {:#?}", e);
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => {
+                tracing::debug!("Container not found: {}", container_id);
                 Ok(ContainerStatus::NotFound)
             }
+            Err(e) => Err(FlockerError::Docker(format!(
+                "Failed to inspect container {}: {}",
+                container_id, e
+            ))),
         }
     }
 
@@ -185,14 +213,48 @@ impl DockerOperations for DockerManager {
         Ok(())
     }
 
-    async fn stop_container(&self, container_id: &str) -> Result<()> {
+    async fn stop_container(&self, container_id: &str, timeout: Option<u64>) -> Result<()> {
+        let options = timeout.map(|t| StopContainerOptions { t: t as i64 });
         self.docker
-            .stop_container(container_id, None::<StopContainerOptions>)
+            .stop_container(container_id, options)
             .await
             .map_err(|e| FlockerError::Docker(format!("Failed to stop container: {}", e)))?;
         Ok(())
     }
 
+    async fn restart_container(&self, container_id: &str, timeout: Option<u64>) -> Result<()> {
+        let options = timeout.map(|t| RestartContainerOptions { t: t as isize });
+        self.docker
+            .restart_container(container_id, options)
+            .await
+            .map_err(|e| FlockerError::Docker(format!("Failed to restart container: {}", e)))?;
+        Ok(())
+    }
+
+    async fn pause_container(&self, container_id: &str) -> Result<()> {
+        self.docker
+            .pause_container(container_id)
+            .await
+            .map_err(|e| FlockerError::Docker(format!("Failed to pause container: {}", e)))?;
+        Ok(())
+    }
+
+    async fn unpause_container(&self, container_id: &str) -> Result<()> {
+        self.docker
+            .unpause_container(container_id)
+            .await
+            .map_err(|e| FlockerError::Docker(format!("Failed to unpause container: {}", e)))?;
+        Ok(())
+    }
+
+    async fn kill_container(&self, container_id: &str, signal: &str) -> Result<()> {
+        self.docker
+            .kill_container(container_id, Some(KillContainerOptions { signal }))
+            .await
+            .map_err(|e| FlockerError::Docker(format!("Failed to kill container: {}", e)))?;
+        Ok(())
+    }
+
     async fn remove_container(&self, container_id: &str) -> Result<()> {
         let options = Some(RemoveContainerOptions {
             force: true,
@@ -206,31 +268,91 @@ impl DockerOperations for DockerManager {
         Ok(())
     }
 
+    async fn is_port_in_use(&self, address: IpAddr, port: u16) -> Result<bool> {
+        let mut filters = HashMap::new();
+        filters.insert(String::from("status"), vec![String::from("running")]);
+
+        let options = Some(ListContainersOptions {
+            filters,
+            ..Default::default()
+        });
+
+        let containers = self
+            .docker
+            .list_containers(options)
+            .await
+            .map_err(|e| FlockerError::Docker(format!("Failed to list containers: {}", e)))?;
+
+        let published = containers
+            .iter()
+            .flat_map(|container| container.ports.iter().flatten())
+            .any(|mapping| mapping.public_port == Some(port));
+        if published {
+            return Ok(true);
+        }
+
+        Ok(self.remote_host.is_none() && !is_host_port_free(address, port))
+    }
+
+    fn remote_host(&self) -> Option<String> {
+        self.remote_host.clone()
+    }
+
+    async fn probe_server(&self, container_id: &str) -> Result<Option<bool>> {
+        let url = format!("http://localhost:8090{}", READINESS_PATH);
+        // Print the status code with curl or wget, whichever the image has
+        let script = format!(
+            "if command -v curl >/dev/null 2>&1; then curl -s -o /dev/null -w '%{{http_code}}' {url}; \
+             elif command -v wget >/dev/null 2>&1; then wget -S -q -O /dev/null {url} 2>&1 | awk '/HTTP\\//{{code=$2}} END{{print code}}'; \
+             else echo none; fi",
+            url = url
+        );
+        let output = self
+            .exec_command(container_id, vec!["sh", "-c", &script])
+            .await?;
+        match output.trim() {
+            "none" => Ok(None),
+            code => Ok(Some(
+                code.parse::<u16>()
+                    .is_ok_and(|code| (100..500).contains(&code)),
+            )),
+        }
+    }
+
     async fn create_and_start_container(
         &self,
         image_tag: &Tag,
         config: &ContainerConfig,
         name: &str,
     ) -> Result<ContainerInfo> {
-        // Check if port is already in use
-        if self.is_port_in_use(config.host_port).await? {
-            return Err(FlockerError::Docker(format!(
-                "Port {} is already in use by another container",
-                config.host_port
-            )));
-        }
-
-        let mut exposed_ports = HashMap::new();
-        exposed_ports.insert(format!("{}/tcp", config.container_port), HashMap::new());
-
-        let mut port_bindings = HashMap::new();
-        port_bindings.insert(
-            format!("{}/tcp", config.container_port),
-            Some(vec![bollard::models::PortBinding {
-                host_ip: Some(String::from("0.0.0.0")),
-                host_port: Some(config.host_port.to_string()),
-            }]),
+        // Check if any published port is already in use
+        let network = &config.network;
+        let ports = std::iter::once((config.host_port, config.container_port)).chain(
+            network
+                .ports
+                .iter()
+                .map(|mapping| (mapping.host_port, mapping.container_port)),
         );
+        let mut exposed_ports = HashMap::new();
+        let mut port_bindings: bollard::models::PortMap = HashMap::new();
+        for (host_port, container_port) in ports {
+            if self.is_port_in_use(network.bind_address, host_port).await? {
+                return Err(FlockerError::Docker(format!(
+                    "Port {} is already in use by another container or process",
+                    host_port
+                )));
+            }
+            let key = format!("{}/tcp", container_port);
+            exposed_ports.insert(key.clone(), HashMap::new());
+            port_bindings
+                .entry(key)
+                .or_default()
+                .get_or_insert_with(Vec::new)
+                .push(bollard::models::PortBinding {
+                    host_ip: Some(network.bind_address.to_string()),
+                    host_port: Some(host_port.to_string()),
+                });
+        }
 
         // Prepare bind mounts
         let mut binds = Vec::new();
@@ -245,7 +367,7 @@ impl DockerOperations for DockerManager {
         // Add data mount if specified
         if let Some(path) = &config.data_mount_path {
             let path_str = path_to_mount_string(path)?;
-            binds.push(format!("{}:/opt/fluree-server/data:rw", path_str));
+            binds.push(format!("{}:{}:rw", path_str, CONTAINER_DATA_DIR));
         }
 
         // Add config mount if specified
@@ -254,29 +376,47 @@ impl DockerOperations for DockerManager {
             binds.push(format!("{}:/opt/fluree-server/resources:ro", path_str));
         }
 
+        let resources = &config.resources;
         let host_config = bollard::models::HostConfig {
             port_bindings: Some(port_bindings),
             binds: if !binds.is_empty() { Some(binds) } else { None },
+            memory: resources.memory.map(|memory| memory as i64),
+            nano_cpus: resources.nano_cpus(),
+            restart_policy: (!config.restart_policy.is_no())
+                .then(|| config.restart_policy.to_docker()),
             ..Default::default()
         };
 
-        // Prepare container config with optional command for config file
-        let cmd = config.config_file.as_ref().map(|config_file| {
-            vec![format!(
-                "--config=./resources/{}",
-                config_file
-                    .file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-                    .to_string()
-            )]
-        });
+        // Prepare the command: the config file option, then any extra server arguments
+        let mut cmd: Vec<String> = config
+            .config_file
+            .iter()
+            .map(|config_file| {
+                format!(
+                    "--config=./resources/{}",
+                    config_file
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                )
+            })
+            .collect();
+        cmd.extend(config.command.args.iter().cloned());
+
+        let mut env: Vec<String> = resources.java_env().into_iter().collect();
+        env.extend(config.command.env.iter().cloned());
 
         let container_config = Config {
             image: Some(image_tag.name().to_string()),
             exposed_ports: Some(exposed_ports),
             host_config: Some(host_config),
-            cmd,
+            cmd: if !cmd.is_empty() { Some(cmd) } else { None },
+            env: if !env.is_empty() { Some(env) } else { None },
+            entrypoint: config
+                .command
+                .entrypoint
+                .as_ref()
+                .map(|entrypoint| vec![entrypoint.clone()]),
             ..Default::default()
         };
 
@@ -291,6 +431,15 @@ impl DockerOperations for DockerManager {
             .await
             .map_err(|e| FlockerError::Docker(format!("Failed to create container: {}", e)))?;
 
+        // Networks are joined before starting, so Fluree is reachable by its
+        // aliases as soon as it runs
+        for attachment in &network.networks {
+            if let Err(e) = self.connect_network(&container.id, attachment).await {
+                let _ = self.remove_container(&container.id).await;
+                return Err(e);
+            }
+        }
+
         self.docker
             .start_container(&container.id, None::<StartContainerOptions<String>>)
             .await
@@ -316,134 +465,127 @@ impl DockerOperations for DockerManager {
             data_dir,
             config_dir,
             image_tag.name().to_string(),
-        );
+        )
+        .with_resources(config.resources.clone())
+        .with_command(config.command.clone())
+        .with_restart_policy(config.restart_policy)
+        .with_network(config.network.clone());
 
         Ok(info)
     }
 
-    async fn get_container_stats(&self, container_id: &str) -> Result<String> {
-        use crate::cli::{format_bytes, Column, TableFormatter};
+    async fn connect_network(&self, container_id: &str, network: &NetworkAttachment) -> Result<()> {
+        match self
+            .docker
+            .inspect_network(&network.name, None::<InspectNetworkOptions<String>>)
+            .await
+        {
+            Ok(_) => {}
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => {
+                tracing::debug!("Creating network: {}", network.name);
+                self.docker
+                    .create_network(CreateNetworkOptions {
+                        name: network.name.as_str(),
+                        driver: "bridge",
+                        check_duplicate: true,
+                        ..Default::default()
+                    })
+                    .await
+                    .map_err(|e| {
+                        FlockerError::Docker(format!(
+                            "Failed to create network {}: {}",
+                            network.name, e
+                        ))
+                    })?;
+            }
+            Err(e) => {
+                return Err(FlockerError::Docker(format!(
+                    "Failed to inspect network {}: {}",
+                    network.name, e
+                )))
+            }
+        }
+
+        let options = ConnectNetworkOptions {
+            container: container_id,
+            endpoint_config: bollard::models::EndpointSettings {
+                aliases: (!network.aliases.is_empty()).then(|| network.aliases.clone()),
+                ..Default::default()
+            },
+        };
+        self.docker
+            .connect_network(&network.name, options)
+            .await
+            .map_err(|e| {
+                FlockerError::Docker(format!(
+                    "Failed to connect to network {}: {}",
+                    network.name, e
+                ))
+            })?;
+        Ok(())
+    }
 
+    async fn get_container_stats(&self, container_id: &str) -> Result<ContainerStats> {
+        // Podman's one-shot stats have no previous CPU sample to compute a
+        // percentage from, so read the second sample of a stream instead
+        let podman = self.runtime == Runtime::Podman;
         let options = bollard::container::StatsOptions {
-            stream: false,
+            stream: podman,
             ..Default::default()
         };
 
         let mut stats = self.docker.stats(container_id, Some(options));
-
-        if let Some(result) = futures_util::StreamExt::next(&mut stats).await {
-            match result {
-                Ok(stats) => {
-                    // Calculate CPU percentage
-                    let cpu_percent = if stats.cpu_stats.system_cpu_usage.is_some()
-                        && stats.precpu_stats.system_cpu_usage.is_some()
-                    {
-                        let cpu_delta = stats.cpu_stats.cpu_usage.total_usage as f64
-                            - stats.precpu_stats.cpu_usage.total_usage as f64;
-                        let system_delta = stats.cpu_stats.system_cpu_usage.unwrap() as f64
-                            - stats.precpu_stats.system_cpu_usage.unwrap() as f64;
-                        if system_delta > 0.0 && cpu_delta > 0.0 {
-                            (cpu_delta / system_delta)
-                                * 100.0
-                                * stats.cpu_stats.online_cpus.unwrap_or(1) as f64
-                        } else {
-                            0.0
-                        }
-                    } else {
-                        0.0
-                    };
-
-                    // Get memory stats
-                    let mem_usage = stats.memory_stats.usage.unwrap_or(0);
-                    let mem_limit = stats.memory_stats.limit.unwrap_or(1);
-                    let mem_percent = (mem_usage as f64 / mem_limit as f64) * 100.0;
-
-                    use crate::cli::terminal::get_terminal_width;
-
-                    // Get terminal width and calculate column widths
-                    let term_width = get_terminal_width() as usize;
-                    let id_width = (term_width * 15) / 100;
-                    let cpu_width = (term_width * 10) / 100;
-                    let usage_width = (term_width * 10) / 100;
-                    let limit_width = (term_width * 10) / 100;
-                    let percent_width = (term_width * 10) / 100;
-
-                    // Helper function to truncate strings
-                    fn truncate(s: &str, width: usize) -> String {
-                        if s.len() > width {
-                            format!("{}...", &s[..width.saturating_sub(3)])
-                        } else {
-                            s.to_string()
-                        }
-                    }
-
-                    // Create table formatter with dynamic widths
-                    let formatter = TableFormatter::new(vec![
-                        Column::new("CONTAINER ID", id_width),
-                        Column::new("CPU %", cpu_width),
-                        Column::new("MEM USAGE", usage_width),
-                        Column::new("MEM LIMIT", limit_width),
-                        Column::new("MEM %", percent_width),
-                    ]);
-
-                    // Format output with truncation
-                    let mut output = String::new();
-                    formatter.print_header();
-                    formatter.print_row(&[
-                        truncate(&container_id[..12], id_width),
-                        truncate(&format!("{:.2}%", cpu_percent), cpu_width),
-                        truncate(&format_bytes(mem_usage), usage_width),
-                        truncate(&format_bytes(mem_limit), limit_width),
-                        truncate(&format!("{:.1}%", mem_percent), percent_width),
-                    ]);
-
-                    output.push('\n'); // Add extra line for spacing
-
-                    Ok(output)
-                }
-                Err(e) => Err(FlockerError::Docker(format!(
+        if podman {
+            if let Some(Err(e)) = futures_util::StreamExt::next(&mut stats).await {
+                return Err(FlockerError::Docker(format!(
                     "Failed to get container stats: {}",
                     e
-                ))),
+                )));
             }
-        } else {
-            Err(FlockerError::Docker("No stats received".to_string()))
+        }
+
+        match futures_util::StreamExt::next(&mut stats).await {
+            Some(Ok(stats)) => Ok(ContainerStats::from(&stats)),
+            Some(Err(e)) => Err(FlockerError::Docker(format!(
+                "Failed to get container stats: {}",
+                e
+            ))),
+            None => Err(FlockerError::Docker("No stats received".to_string())),
         }
     }
 
-    async fn get_container_logs(&self, container_id: &str, tail: Option<&str>) -> Result<String> {
-        let options = Some(bollard::container::LogsOptions::<String> {
-            stdout: true,
-            stderr: true,
-            tail: tail
-                .map(|t| t.to_string())
-                .unwrap_or_else(|| "1000".to_string()),
-            timestamps: true,
+    fn stream_container_stats(
+        &self,
+        container_id: &str,
+    ) -> BoxStream<'static, Result<ContainerStats>> {
+        let options = bollard::container::StatsOptions {
+            stream: true,
             ..Default::default()
-        });
+        };
+
+        self.docker
+            .stats(container_id, Some(options))
+            .map(|result| {
+                result
+                    .map(|stats| ContainerStats::from(&stats))
+                    .map_err(|e| {
+                        FlockerError::Docker(format!("Failed to get container stats: {}", e))
+                    })
+            })
+            .boxed()
+    }
 
-        let mut logs = self.docker.logs(container_id, options);
+    async fn get_container_logs(&self, container_id: &str, options: &LogOptions) -> Result<String> {
+        let mut logs = self
+            .docker
+            .logs(container_id, Some(Self::logs_options(options, false)));
         let mut log_lines = Vec::new();
 
         while let Some(log) = futures_util::StreamExt::next(&mut logs).await {
             match log {
-                Ok(log) => {
-                    let log_str = log.to_string();
-                    // Docker timestamps are in format "2024-02-08T21:56:23.123456789Z"
-                    if let Some(timestamp_end) = log_str.find(' ') {
-                        if let Ok(timestamp) =
-                            chrono::DateTime::parse_from_rfc3339(&log_str[..timestamp_end])
-                        {
-                            let formatted_time = timestamp.format("%Y-%m-%d %H:%M:%S%.3f");
-                            let message = &log_str[timestamp_end + 1..];
-                            log_lines.push(format!("[{}] {}", formatted_time, message));
-                        } else {
-                            log_lines.push(log_str);
-                        }
-                    } else {
-                        log_lines.push(log_str);
-                    }
-                }
+                Ok(log) => log_lines.push(Self::format_log_line(&log.to_string())),
                 Err(e) => {
                     return Err(FlockerError::Docker(format!(
                         "Failed to get container logs: {}",
@@ -453,15 +595,34 @@ impl DockerOperations for DockerManager {
             }
         }
 
-        // Reverse the lines so newest are at the bottom
         Ok(log_lines.join(""))
     }
 
+    fn follow_container_logs(
+        &self,
+        container_id: &str,
+        options: &LogOptions,
+    ) -> BoxStream<'static, Result<String>> {
+        self.docker
+            .logs(container_id, Some(Self::logs_options(options, true)))
+            .map(|log| {
+                log.map(|log| {
+                    Self::format_log_line(&log.to_string())
+                        .trim_end_matches(['\r', '\n'])
+                        .to_string()
+                })
+                .map_err(|e| {
+                    FlockerError::Docker(format!("Failed to follow container logs: {}", e))
+                })
+            })
+            .boxed()
+    }
+
     async fn list_ledgers(&self, container_id: &str) -> Result<Vec<LedgerInfo>> {
         // First, find all .json files recursively (excluding commit directory)
         let find_cmd = vec![
             "find",
-            "/opt/fluree-server/data",
+            CONTAINER_DATA_DIR,
             "-type",
             "f",
             "-name",
@@ -489,66 +650,7 @@ impl DockerOperations for DockerManager {
             let cat_cmd = vec!["cat", path];
             let json_content = self.exec_command(container_id, cat_cmd).await?;
 
-            // Parse the JSON content
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&json_content) {
-                if let Some(ledger_alias) = json.get("ledgerAlias").and_then(|v| v.as_str()) {
-                    let last_commit_time = json
-                        .get("branches")
-                        .and_then(|b| b.get(0))
-                        .and_then(|b| b.get("commit"))
-                        .and_then(|c| c.get("time"))
-                        .and_then(|t| t.as_str())
-                        .unwrap_or("unknown");
-
-                    let commit_count = json
-                        .get("branches")
-                        .and_then(|b| b.get(0))
-                        .and_then(|b| b.get("commit"))
-                        .and_then(|c| c.get("data"))
-                        .and_then(|d| d.get("t"))
-                        .and_then(|t| t.as_u64())
-                        .unwrap_or(0);
-
-                    let size = json
-                        .get("branches")
-                        .and_then(|b| b.get(0))
-                        .and_then(|b| b.get("commit"))
-                        .and_then(|c| c.get("data"))
-                        .and_then(|d| d.get("size"))
-                        .and_then(|s| s.as_u64())
-                        .unwrap_or(0);
-
-                    let flakes_count = json
-                        .get("branches")
-                        .and_then(|b| b.get(0))
-                        .and_then(|b| b.get("commit"))
-                        .and_then(|c| c.get("data"))
-                        .and_then(|d| d.get("flakes"))
-                        .and_then(|f| f.as_u64())
-                        .unwrap_or(0);
-
-                    let flakes_count = flakes_count.to_formatted_string(&Locale::en);
-
-                    let last_index = json
-                        .get("branches")
-                        .and_then(|b| b.get(0))
-                        .and_then(|b| b.get("commit"))
-                        .and_then(|b| b.get("index"))
-                        .and_then(|b| b.get("data"))
-                        .and_then(|i| i.get("t"))
-                        .and_then(|t| t.as_u64());
-
-                    ledgers.push(LedgerInfo {
-                        alias: ledger_alias.to_string(),
-                        last_commit_time: last_commit_time.to_string(),
-                        commit_count,
-                        size,
-                        path: path.to_string(),
-                        flakes_count,
-                        last_index,
-                    });
-                }
-            }
+            ledgers.extend(ledger_from_file(&json_content, path));
         }
 
         Ok(ledgers)
@@ -581,24 +683,54 @@ impl DockerOperations for DockerManager {
         Ok(())
     }
 
-    async fn pull_image(&self, tag: &str) -> Result<()> {
+    async fn download_from_container(&self, container_id: &str, path: &str) -> Result<Vec<u8>> {
+        let options = Some(bollard::container::DownloadFromContainerOptions { path });
+        let mut stream = self.docker.download_from_container(container_id, options);
+        let mut archive = Vec::new();
+        while let Some(chunk) = futures_util::StreamExt::next(&mut stream).await {
+            let chunk = chunk
+                .map_err(|e| FlockerError::Docker(format!("Failed to download {}: {}", path, e)))?;
+            archive.extend_from_slice(&chunk);
+        }
+        Ok(archive)
+    }
+
+    async fn upload_to_container(
+        &self,
+        container_id: &str,
+        path: &str,
+        archive: Vec<u8>,
+    ) -> Result<()> {
+        let options = Some(bollard::container::UploadToContainerOptions {
+            path,
+            no_overwrite_dir_non_dir: "true",
+        });
+        self.docker
+            .upload_to_container(container_id, options, archive.into())
+            .await
+            .map_err(|e| FlockerError::Docker(format!("Failed to upload to {}: {}", path, e)))
+    }
+
+    async fn pull_image(
+        &self,
+        repository: &str,
+        tag: &str,
+        progress: &(dyn Fn(PullProgress) + Send + Sync),
+    ) -> Result<()> {
         let options = Some(bollard::image::CreateImageOptions {
-            from_image: "fluree/server",
+            from_image: repository,
             tag,
             ..Default::default()
         });
 
-        let mut pull_stream = self.docker.create_image(options, None, None);
+        let credentials = credentials_for_repository(repository).await.map(Into::into);
+        let mut pull_stream = self.docker.create_image(options, None, credentials);
 
         while let Some(info) = futures_util::StreamExt::next(&mut pull_stream).await {
             match info {
                 Ok(output) => {
-                    if let Some(status) = output.status {
-                        if let Some(progress) = output.progress {
-                            println!("{}: {}", status, progress);
-                        } else {
-                            println!("{}", status);
-                        }
+                    if let Some(event) = Self::pull_progress(output) {
+                        progress(event);
                     }
                 }
                 Err(e) => {
@@ -610,20 +742,34 @@ impl DockerOperations for DockerManager {
         Ok(())
     }
 
-    async fn get_image_by_tag(&self, tag_str: &str) -> Result<FlureeImage> {
-        let mut filters = HashMap::new();
-        filters.insert(
-            String::from("reference"),
-            vec![String::from("fluree/server")],
-        );
+    async fn get_image_by_tag(&self, repository: &str, tag_str: &str) -> Result<FlureeImage> {
+        let tag_full_name = format!("{}:{}", repository, tag_str);
 
-        let tag_full_name = format!("fluree/server:{}", tag_str);
+        // Podman keeps locally built images under localhost/
+        let mut candidates = vec![tag_full_name.clone()];
+        if self.runtime == Runtime::Podman && registry_host(repository).is_none() {
+            candidates.push(format!("localhost/{}", tag_full_name));
+        }
 
-        let image = self
-            .docker
-            .inspect_image(&tag_full_name)
-            .await
-            .map_err(|e| FlockerError::Docker(format!("Failed to get image: {}", e)))?;
+        let mut found = None;
+        let mut first_error = None;
+        for candidate in candidates {
+            match self.docker.inspect_image(&candidate).await {
+                Ok(image) => {
+                    found = Some((candidate, image));
+                    break;
+                }
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        let (tag_full_name, image) = found.ok_or_else(|| {
+            FlockerError::Docker(format!(
+                "Failed to get image: {}",
+                first_error.map(|e| e.to_string()).unwrap_or_default()
+            ))
+        })?;
 
         let created_string = image.created.unwrap_or("<unknown>".to_string());
         let created = chrono::DateTime::parse_from_rfc3339(&created_string)
@@ -644,12 +790,13 @@ impl DockerOperations for DockerManager {
         })
     }
 
-    async fn list_local_images(&self) -> Result<Vec<FlureeImage>> {
+    async fn list_local_images(&self, repository: &str) -> Result<Vec<FlureeImage>> {
+        // Podman matches reference filters against the fully qualified name,
+        // so filter on our side instead
         let mut filters = HashMap::new();
-        filters.insert(
-            String::from("reference"),
-            vec![String::from("fluree/server*")],
-        );
+        if self.runtime == Runtime::Docker {
+            filters.insert(String::from("reference"), vec![format!("{}*", repository)]);
+        }
 
         let options = bollard::image::ListImagesOptions {
             filters,
@@ -658,25 +805,161 @@ impl DockerOperations for DockerManager {
 
         let images = self.docker.list_images(Some(options)).await.map_err(|e| {
             FlockerError::Docker(format!(
-                "Failed to list images. Is the docker daemon running? ({})",
+                "Failed to list images. Is the {} daemon running? ({})",
+                self.runtime.to_string().to_lowercase(),
                 e
             ))
         })?;
 
         tracing::debug!("Images: {:#?}", images);
 
+        Ok(Self::fluree_images(images, repository))
+    }
+}
+
+impl DockerManager {
+    /// Build a container status from an inspect response
+    ///
+    /// Podman reports names without the leading '/', and may only report
+    /// published ports under `NetworkSettings` and bind mounts under `Mounts`.
+    fn status_from_inspect(
+        container_id: &str,
+        container: bollard::models::ContainerInspectResponse,
+    ) -> ContainerStatus {
+        use bollard::models::{ContainerStateStatusEnum as StateStatus, HealthStatusEnum};
+
+        let state = container.state.unwrap_or_default();
+        let id = container_id.to_string();
+        let name = container
+            .name
+            .unwrap_or_default()
+            .trim_start_matches('/')
+            .to_string();
+        let started_at = state.started_at;
+        // Docker reports the zero time for containers that never finished
+        let finished_at = state
+            .finished_at
+            .filter(|time| !time.is_empty() && !time.starts_with("0001-"));
+
+        // Older engines don't report a status, only the boolean flags
+        let status = state.status.unwrap_or(match state.running {
+            Some(true) => StateStatus::RUNNING,
+            _ if finished_at.is_some() => StateStatus::EXITED,
+            _ => StateStatus::CREATED,
+        });
+        match status {
+            StateStatus::RUNNING => {}
+            StateStatus::PAUSED => {
+                return ContainerStatus::Paused {
+                    id,
+                    name,
+                    started_at,
+                }
+            }
+            StateStatus::RESTARTING => {
+                return ContainerStatus::Restarting {
+                    id,
+                    name,
+                    restart_count: container.restart_count.unwrap_or(0),
+                }
+            }
+            StateStatus::EXITED => {
+                return ContainerStatus::Exited {
+                    id,
+                    name,
+                    code: state.exit_code.unwrap_or(0),
+                    oom_killed: state.oom_killed.unwrap_or(false),
+                    finished_at,
+                    last_start: started_at,
+                }
+            }
+            StateStatus::DEAD | StateStatus::REMOVING => return ContainerStatus::Dead { id, name },
+            StateStatus::CREATED | StateStatus::EMPTY => {
+                return ContainerStatus::Stopped {
+                    id,
+                    name,
+                    last_start: started_at,
+                }
+            }
+        }
+
+        let health = match state.health.and_then(|health| health.status) {
+            Some(HealthStatusEnum::STARTING) => Some(Health::Starting),
+            Some(HealthStatusEnum::HEALTHY) => Some(Health::Healthy),
+            Some(HealthStatusEnum::UNHEALTHY) => Some(Health::Unhealthy),
+            _ => None,
+        };
+
+        let host_config = container.host_config.unwrap_or_default();
+
+        // Extract port mapping
+        let host_port = |bindings: &bollard::models::PortMap| {
+            bindings
+                .get("8090/tcp")
+                .and_then(|binding| binding.as_ref())
+                .and_then(|binding| binding.first())
+                .and_then(|port| port.host_port.as_ref())
+                .and_then(|port| port.parse().ok())
+        };
+        let port = host_config
+            .port_bindings
+            .as_ref()
+            .and_then(host_port)
+            .or_else(|| {
+                container
+                    .network_settings
+                    .as_ref()
+                    .and_then(|settings| settings.ports.as_ref())
+                    .and_then(host_port)
+            })
+            .unwrap_or(8090);
+
+        // Extract data and config directories
+        let binds = host_config
+            .binds
+            .filter(|binds| !binds.is_empty())
+            .unwrap_or_else(|| {
+                container
+                    .mounts
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|mount| Some(format!("{}:{}", mount.source?, mount.destination?)))
+                    .collect()
+            });
+        let data_dir = binds.first().cloned();
+        let config_dir = binds.get(1).cloned();
+        tracing::debug!("Data dir: {:?}", data_dir);
+
+        ContainerStatus::Running {
+            id,
+            name,
+            port,
+            data_dir,
+            config_dir,
+            started_at,
+            health,
+        }
+    }
+
+    /// Collect Fluree images of `repository` from an image listing
+    fn fluree_images(
+        images: Vec<bollard::models::ImageSummary>,
+        repository: &str,
+    ) -> Vec<FlureeImage> {
         let mut fluree_images = Vec::new();
         for image in images {
-            for tag in image.repo_tags {
-                if tag.starts_with("fluree/server:") {
-                    let created_i64 = image.created;
+            for tag in &image.repo_tags {
+                if is_repository_reference(tag, repository) {
                     let created = chrono::Utc
-                        .timestamp_opt(created_i64, 0)
+                        .timestamp_opt(image.created, 0)
                         .single()
                         .unwrap_or_else(chrono::Utc::now);
 
                     fluree_images.push(FlureeImage {
-                        tag: Tag::new(tag, created.to_rfc3339()),
+                        tag: Tag::new(
+                            normalize_image_reference(tag).to_string(),
+                            created.to_rfc3339(),
+                        ),
                         id: image.id.clone(),
                         created,
                         size: image.size as u64,
@@ -684,12 +967,52 @@ impl DockerOperations for DockerManager {
                 }
             }
         }
+        fluree_images
+    }
 
-        Ok(fluree_images)
+    /// Build bollard log options, always including timestamps for formatting
+    fn logs_options(options: &LogOptions, follow: bool) -> bollard::container::LogsOptions<String> {
+        bollard::container::LogsOptions {
+            follow,
+            stdout: true,
+            stderr: true,
+            since: options.since.unwrap_or(0),
+            until: options.until.unwrap_or(0),
+            timestamps: true,
+            tail: options.tail.clone(),
+        }
+    }
+
+    /// Convert a pull stream message into a progress event
+    ///
+    /// Messages about the whole image, like "Pulling from fluree/server",
+    /// carry the tag in their ID field rather than a layer ID.
+    fn pull_progress(info: bollard::models::CreateImageInfo) -> Option<PullProgress> {
+        let status = info.status?;
+        let layer = info.id.filter(|_| !status.starts_with("Pulling from"));
+        let detail = info.progress_detail.unwrap_or_default();
+        let bytes = |value: Option<i64>| value.and_then(|value| u64::try_from(value).ok());
+        Some(PullProgress {
+            layer,
+            status,
+            current: bytes(detail.current),
+            total: bytes(detail.total),
+        })
+    }
+
+    /// Replace Docker's RFC 3339 timestamp prefix with a shorter local format
+    fn format_log_line(log_str: &str) -> String {
+        // Docker timestamps are in format "2024-02-08T21:56:23.123456789Z"
+        if let Some(timestamp_end) = log_str.find(' ') {
+            if let Ok(timestamp) = chrono::DateTime::parse_from_rfc3339(&log_str[..timestamp_end]) {
+                let formatted_time = timestamp.format("%Y-%m-%d %H:%M:%S%.3f");
+                let message = &log_str[timestamp_end + 1..];
+                return format!("[{}] {}", formatted_time, message);
+            }
+        }
+        log_str.to_string()
     }
-}
 
-impl DockerManager {
     /// Execute a command in a container and return the output
     async fn exec_command(&self, container_id: &str, cmd: Vec<&str>) -> Result<String> {
         let exec = self
@@ -716,7 +1039,11 @@ impl DockerManager {
             bollard::exec::StartExecResults::Attached { mut output, .. } => {
                 let mut result = String::new();
                 while let Some(Ok(msg)) = futures_util::StreamExt::next(&mut output).await {
-                    result.push_str(&msg.to_string());
+                    // Runtime warnings (common with Podman) go to stderr and
+                    // would corrupt the output we parse
+                    if !matches!(msg, bollard::container::LogOutput::StdErr { .. }) {
+                        result.push_str(&msg.to_string());
+                    }
                 }
                 Ok(result)
             }
@@ -724,3 +1051,209 @@ impl DockerManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+
+    use super::*;
+
+    /// Trimmed `GET /containers/{id}/json` response from Podman's compat API
+    const PODMAN_INSPECT: &str = r#"{
+        "Id": "4c1f6d3e2b",
+        "Name": "fluree-dev",
+        "State": {
+            "Status": "running",
+            "Running": true,
+            "StartedAt": "2024-02-08T21:56:23.123456789Z"
+        },
+        "HostConfig": {
+            "Binds": [],
+            "PortBindings": {}
+        },
+        "NetworkSettings": {
+            "Ports": {
+                "8090/tcp": [{ "HostIp": "", "HostPort": "9090" }]
+            }
+        },
+        "Mounts": [
+            {
+                "Type": "bind",
+                "Source": "/home/dev/fluree-data",
+                "Destination": "/opt/fluree-server/data",
+                "RW": true
+            }
+        ]
+    }"#;
+
+    /// Trimmed `GET /images/json` response from Podman's compat API
+    const PODMAN_IMAGES: &str = r#"[
+        {
+            "Id": "sha256:aaa",
+            "ParentId": "",
+            "RepoTags": ["docker.io/fluree/server:latest", "docker.io/fluree/server:v3.0.0"],
+            "RepoDigests": [],
+            "Created": 1707429383,
+            "Size": 512000000,
+            "SharedSize": 0,
+            "Labels": {},
+            "Containers": 1
+        },
+        {
+            "Id": "sha256:bbb",
+            "ParentId": "",
+            "RepoTags": ["localhost/fluree/server:dev", "docker.io/library/alpine:3"],
+            "RepoDigests": [],
+            "Created": 1707429383,
+            "Size": 1000,
+            "SharedSize": 0,
+            "Labels": {},
+            "Containers": 0
+        }
+    ]"#;
+
+    #[test]
+    #[parallel]
+    fn test_podman_running_status() {
+        let container = serde_json::from_str(PODMAN_INSPECT).unwrap();
+        match DockerManager::status_from_inspect("4c1f6d3e2b", container) {
+            ContainerStatus::Running {
+                name,
+                port,
+                data_dir,
+                config_dir,
+                started_at,
+                ..
+            } => {
+                assert_eq!(name, "fluree-dev");
+                assert_eq!(port, 9090);
+                assert_eq!(
+                    data_dir.as_deref(),
+                    Some("/home/dev/fluree-data:/opt/fluree-server/data")
+                );
+                assert!(config_dir.is_none());
+                assert!(started_at.is_some());
+            }
+            status => panic!("Expected running status, got {:?}", status),
+        }
+    }
+
+    #[test]
+    #[parallel]
+    fn test_docker_stopped_status() {
+        let container = serde_json::from_value(serde_json::json!({
+            "Name": "/fluree-dev",
+            "State": { "Running": false, "StartedAt": "2024-02-08T21:56:23Z" }
+        }))
+        .unwrap();
+        match DockerManager::status_from_inspect("abc", container) {
+            ContainerStatus::Stopped { name, .. } => assert_eq!(name, "fluree-dev"),
+            status => panic!("Expected stopped status, got {:?}", status),
+        }
+    }
+
+    #[test]
+    #[parallel]
+    fn test_docker_exited_status() {
+        let container = serde_json::from_value(serde_json::json!({
+            "Name": "/fluree-dev",
+            "State": {
+                "Status": "exited",
+                "Running": false,
+                "OOMKilled": true,
+                "ExitCode": 137,
+                "StartedAt": "2024-02-08T21:56:23Z",
+                "FinishedAt": "2024-02-08T22:10:00Z"
+            }
+        }))
+        .unwrap();
+        match DockerManager::status_from_inspect("abc", container) {
+            ContainerStatus::Exited {
+                code,
+                oom_killed,
+                finished_at,
+                ..
+            } => {
+                assert_eq!(code, 137);
+                assert!(oom_killed);
+                assert_eq!(finished_at.as_deref(), Some("2024-02-08T22:10:00Z"));
+            }
+            status => panic!("Expected exited status, got {:?}", status),
+        }
+    }
+
+    #[test]
+    #[parallel]
+    fn test_docker_paused_and_health_status() {
+        let container = serde_json::from_value(serde_json::json!({
+            "Name": "/fluree-dev",
+            "State": { "Status": "paused", "Running": true, "Paused": true }
+        }))
+        .unwrap();
+        assert!(matches!(
+            DockerManager::status_from_inspect("abc", container),
+            ContainerStatus::Paused { .. }
+        ));
+
+        let container = serde_json::from_value(serde_json::json!({
+            "Name": "/fluree-dev",
+            "State": {
+                "Status": "running",
+                "Running": true,
+                "Health": { "Status": "unhealthy", "FailingStreak": 3 }
+            }
+        }))
+        .unwrap();
+        match DockerManager::status_from_inspect("abc", container) {
+            ContainerStatus::Running { health, .. } => {
+                assert_eq!(health, Some(Health::Unhealthy))
+            }
+            status => panic!("Expected running status, got {:?}", status),
+        }
+    }
+
+    #[test]
+    #[parallel]
+    fn test_podman_image_references() {
+        let images = serde_json::from_str(PODMAN_IMAGES).unwrap();
+        let tags: Vec<String> = DockerManager::fluree_images(images, DEFAULT_IMAGE_REPOSITORY)
+            .into_iter()
+            .map(|image| image.tag.name)
+            .collect();
+        assert_eq!(
+            tags,
+            vec![
+                "fluree/server:latest",
+                "fluree/server:v3.0.0",
+                "localhost/fluree/server:dev"
+            ]
+        );
+    }
+
+    #[test]
+    #[parallel]
+    fn test_pull_progress() {
+        let info = |json: &str| DockerManager::pull_progress(serde_json::from_str(json).unwrap());
+
+        assert_eq!(
+            info(r#"{"status":"Pulling from fluree/server","id":"latest"}"#),
+            Some(PullProgress {
+                layer: None,
+                status: "Pulling from fluree/server".to_string(),
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            info(
+                r#"{"status":"Downloading","id":"a1b2c3","progressDetail":{"current":1024,"total":4096},"progress":"[==>   ]"}"#
+            ),
+            Some(PullProgress {
+                layer: Some("a1b2c3".to_string()),
+                status: "Downloading".to_string(),
+                current: Some(1024),
+                total: Some(4096),
+            })
+        );
+        assert_eq!(info(r#"{"id":"a1b2c3"}"#), None);
+    }
+}
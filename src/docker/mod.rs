@@ -4,9 +4,27 @@
 //! - Listing and searching Fluree images
 //! - Creating and managing containers
 //! - Executing commands within containers
+//! - Resolving the Docker daemon endpoint from flags, environment and contexts
+//! - Podman compatibility
+//! - Waiting for a started server to accept requests
+//! - Finding free host ports for new containers
+//! - Registry credentials for private image repositories
 
+pub mod config_file;
+pub mod credentials;
+pub mod endpoint;
 pub mod manager;
+pub mod ports;
+pub mod readiness;
+pub mod runtime;
 pub mod types;
 
+pub use self::endpoint::DockerEndpoint;
 pub use self::manager::{DockerManager, DockerOperations};
-pub use self::types::{ContainerConfig, FlureeImage, LedgerInfo};
+pub use self::runtime::Runtime;
+pub use self::types::{
+    BranchInfo, CommandOptions, ContainerConfig, ContainerStats, FlureeImage, LedgerInfo,
+    LogOptions, NetworkAttachment, NetworkOptions, PortMapping, PullProgress, ResourceLimits,
+    RestartPolicy, CONTAINER_DATA_DIR, DEFAULT_BIND_ADDRESS, DEFAULT_IMAGE_REPOSITORY,
+    DEFAULT_JAVA_OPTS_VAR, DEFAULT_KILL_SIGNAL,
+};
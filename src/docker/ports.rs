@@ -0,0 +1,188 @@
+//! Host port selection.
+//!
+//! A host port can be published by a running container, reserved by a
+//! stopped flocker container that publishes it again when started, or held
+//! by another process on the host. This module checks all three, and finds
+//! the next free port for new containers.
+
+use std::fmt;
+use std::io::ErrorKind;
+use std::net::{IpAddr, TcpListener};
+
+use super::manager::DockerOperations;
+use crate::config::FlureeConfig;
+use crate::state::{ContainerInfo, State};
+use crate::{FlockerError, Result};
+
+/// Host port suggested for the first container
+pub const DEFAULT_PORT: u16 = 8090;
+
+/// Number of ports tried when looking for a free one
+const SEARCH_RANGE: u16 = 100;
+
+/// Why a host port can't be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PortConflict {
+    /// Published by a flocker container, whether it is running or not
+    Reserved { container: String },
+    /// Published by another container or held by a host process
+    InUse,
+}
+
+impl fmt::Display for PortConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PortConflict::Reserved { container } => {
+                write!(f, "reserved by container '{}'", container)
+            }
+            PortConflict::InUse => write!(f, "in use by another container or process"),
+        }
+    }
+}
+
+/// Whether a process on this machine could listen on `port` of `address`
+///
+/// Only a port that is already bound counts as taken; other bind errors,
+/// e.g. for an address this machine doesn't have, are left for Docker to
+/// report.
+pub fn is_host_port_free(address: IpAddr, port: u16) -> bool {
+    match TcpListener::bind((address, port)) {
+        Ok(_) => true,
+        Err(e) => e.kind() != ErrorKind::AddrInUse,
+    }
+}
+
+/// The flocker container that publishes `port`, if any
+pub fn reserved_by(state: &State, port: u16) -> Option<&ContainerInfo> {
+    state.containers.values().find(|container| {
+        container.port == port
+            || container
+                .network
+                .ports
+                .iter()
+                .any(|mapping| mapping.host_port == port)
+    })
+}
+
+/// Check whether a host port can be used for a new container
+pub async fn port_conflict(
+    docker: &impl DockerOperations,
+    state: &State,
+    address: IpAddr,
+    port: u16,
+) -> Result<Option<PortConflict>> {
+    if let Some(container) = reserved_by(state, port) {
+        return Ok(Some(PortConflict::Reserved {
+            container: container.name.clone(),
+        }));
+    }
+    if docker.is_port_in_use(address, port).await? {
+        return Ok(Some(PortConflict::InUse));
+    }
+    Ok(None)
+}
+
+/// Fail if ports published on `address` can't be reached from this machine
+///
+/// `remote_host` is the daemon's host when it runs on another machine,
+/// where its loopback address is out of reach.
+pub fn check_reachable(address: IpAddr, remote_host: Option<&str>) -> Result<()> {
+    match remote_host {
+        Some(host) if address.is_loopback() => Err(FlockerError::Config(format!(
+            "Docker runs on {}, where ports bound to {} can't be reached from this machine; bind them to another address, e.g. --bind 0.0.0.0",
+            host, address
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Fail if any host port of a new container can't be used
+pub async fn check_ports(
+    docker: &impl DockerOperations,
+    state: &State,
+    config: &FlureeConfig,
+) -> Result<()> {
+    let address = config.network.bind_address;
+    check_reachable(address, docker.remote_host().as_deref())?;
+    let ports = std::iter::once(config.host_port)
+        .chain(config.network.ports.iter().map(|mapping| mapping.host_port));
+    for port in ports {
+        if let Some(conflict) = port_conflict(docker, state, address, port).await? {
+            return Err(FlockerError::Config(format!(
+                "Port {} is {}",
+                port, conflict
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Find the first port from `start` that a new container can use
+pub async fn next_free_port(
+    docker: &impl DockerOperations,
+    state: &State,
+    address: IpAddr,
+    start: u16,
+) -> Result<u16> {
+    for port in start..=start.saturating_add(SEARCH_RANGE - 1) {
+        if port_conflict(docker, state, address, port).await?.is_none() {
+            return Ok(port);
+        }
+    }
+    Err(FlockerError::Config(format!(
+        "No free port found between {} and {}",
+        start,
+        start.saturating_add(SEARCH_RANGE - 1)
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+
+    use super::*;
+    use crate::docker::DEFAULT_BIND_ADDRESS;
+    use crate::state::test_container;
+
+    #[test]
+    #[parallel]
+    fn test_host_port_probe() {
+        let listener = TcpListener::bind((DEFAULT_BIND_ADDRESS, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(!is_host_port_free(DEFAULT_BIND_ADDRESS, port));
+        drop(listener);
+        assert!(is_host_port_free(DEFAULT_BIND_ADDRESS, port));
+    }
+
+    #[test]
+    #[parallel]
+    fn test_check_reachable() {
+        let any = "0.0.0.0".parse().unwrap();
+        assert!(check_reachable(DEFAULT_BIND_ADDRESS, None).is_ok());
+        assert!(check_reachable(any, Some("build-box")).is_ok());
+        let error = check_reachable(DEFAULT_BIND_ADDRESS, Some("build-box"))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("Docker runs on build-box"), "{}", error);
+    }
+
+    #[test]
+    #[parallel]
+    fn test_reserved_by() {
+        let mut state = State::default();
+        let mut container = test_container("stopped");
+        container.network.ports = vec!["9000:9000".parse().unwrap()];
+        state.containers.insert(container.id.clone(), container);
+
+        assert_eq!(reserved_by(&state, 8090).unwrap().name, "stopped");
+        assert_eq!(reserved_by(&state, 9000).unwrap().name, "stopped");
+        assert!(reserved_by(&state, 8091).is_none());
+        assert_eq!(
+            PortConflict::Reserved {
+                container: "stopped".to_string()
+            }
+            .to_string(),
+            "reserved by container 'stopped'"
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! Container readiness probing.
+//!
+//! Docker reports a container as started as soon as its process is
+//! running, while the Fluree JVM may still be booting or about to exit on
+//! a bad configuration. This module polls the server until it answers, the
+//! container exits, or a timeout passes: over HTTP when the daemon runs on
+//! this machine, otherwise from inside the container.
+
+use futures_util::StreamExt;
+use std::time::{Duration, Instant};
+
+use super::{manager::DockerOperations, LogOptions};
+use crate::{ContainerStatus, Result};
+
+/// Path requested to check whether the server is accepting requests
+pub const READINESS_PATH: &str = "/fluree/health";
+
+/// Default time to wait for a container to become ready, in seconds
+pub const DEFAULT_READY_TIMEOUT_SECS: u64 = 60;
+
+/// Number of log lines reported when startup fails
+const FAILURE_LOG_LINES: usize = 20;
+
+/// Time between readiness probes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Time allowed for a single probe request
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Outcome of waiting for a container to become ready
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Readiness {
+    /// The server answered an HTTP request
+    Ready { elapsed: Duration },
+    /// The server didn't answer before the timeout
+    TimedOut {
+        timeout: Duration,
+        last_logs: Vec<String>,
+    },
+    /// The container stopped before the server answered
+    Exited {
+        exit_code: Option<i64>,
+        last_logs: Vec<String>,
+    },
+}
+
+/// How to ask the server whether it accepts requests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Probe {
+    /// Request a URL from this machine
+    Http(String),
+    /// Request the server from inside its container, for daemons on other
+    /// machines whose published ports may not be reachable from here
+    Exec,
+}
+
+/// URL probed for a server at `base_url`, e.g. `http://localhost:8090`
+pub fn readiness_url(base_url: &str) -> String {
+    format!("{}{}", base_url, READINESS_PATH)
+}
+
+/// Wait until the server in a container answers `probe`
+///
+/// Any HTTP response other than a server error counts as ready, so servers
+/// without the health endpoint are detected too. Log lines written since
+/// `since` (a Unix timestamp) are passed to `on_log` while waiting.
+pub async fn wait_until_ready(
+    docker: &impl DockerOperations,
+    container_id: &str,
+    probe: &Probe,
+    timeout: Duration,
+    since: Option<i64>,
+    mut on_log: impl FnMut(&str) + Send,
+) -> Result<Readiness> {
+    let started = Instant::now();
+    let client = reqwest::Client::builder()
+        .timeout(PROBE_TIMEOUT)
+        .build()
+        .unwrap_or_default();
+    let log_options = LogOptions {
+        tail: "all".to_string(),
+        since,
+        until: None,
+    };
+    let mut logs = docker
+        .follow_container_logs(container_id, &log_options)
+        .fuse();
+    let mut poll = tokio::time::interval(POLL_INTERVAL);
+    let deadline = tokio::time::sleep(timeout);
+    tokio::pin!(deadline);
+
+    loop {
+        tokio::select! {
+            Some(line) = logs.next() => {
+                if let Ok(line) = line {
+                    on_log(&line);
+                }
+            }
+            _ = poll.tick() => {
+                let answered = match probe {
+                    Probe::Http(url) => client
+                        .get(url)
+                        .send()
+                        .await
+                        .is_ok_and(|response| !response.status().is_server_error()),
+                    // Without an HTTP client in the image, a running
+                    // container is all that can be checked
+                    Probe::Exec => docker
+                        .probe_server(container_id)
+                        .await
+                        .is_ok_and(|answered| answered.unwrap_or(true)),
+                };
+                if answered {
+                    return Ok(Readiness::Ready {
+                        elapsed: started.elapsed(),
+                    });
+                }
+
+                let exit_code = match docker.get_container_status(container_id).await? {
+                    ContainerStatus::Exited { code, .. } => Some(code),
+                    ContainerStatus::Stopped { .. }
+                    | ContainerStatus::Dead { .. }
+                    | ContainerStatus::NotFound => None,
+                    _ => continue,
+                };
+                return Ok(Readiness::Exited {
+                    exit_code,
+                    last_logs: last_logs(docker, container_id).await,
+                });
+            }
+            _ = &mut deadline => {
+                return Ok(Readiness::TimedOut {
+                    timeout,
+                    last_logs: last_logs(docker, container_id).await,
+                });
+            }
+        }
+    }
+}
+
+/// The last few log lines of a container, for reporting a failed startup
+async fn last_logs(docker: &impl DockerOperations, container_id: &str) -> Vec<String> {
+    let options = LogOptions {
+        tail: FAILURE_LOG_LINES.to_string(),
+        ..Default::default()
+    };
+    docker
+        .get_container_logs(container_id, &options)
+        .await
+        .map(|logs| logs.lines().map(str::to_string).collect())
+        .unwrap_or_default()
+}
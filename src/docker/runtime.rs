@@ -0,0 +1,336 @@
+//! Container runtime selection.
+//!
+//! Flocker talks to Docker and Podman through the same Docker-compatible
+//! API. This module finds the Podman socket when asked to, detects which
+//! runtime an endpoint belongs to, and normalizes image references that
+//! Podman reports with registry prefixes.
+
+use clap::ValueEnum;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::endpoint::{
+    env_endpoint, resolve_endpoint, DockerEndpoint, EndpointEnv, EndpointSource, DEFAULT_HOST,
+};
+use crate::{FlockerError, Result};
+
+/// Container runtime behind the Docker-compatible API
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Runtime {
+    #[default]
+    Docker,
+    Podman,
+}
+
+impl fmt::Display for Runtime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Runtime::Docker => write!(f, "Docker"),
+            Runtime::Podman => write!(f, "Podman"),
+        }
+    }
+}
+
+impl Runtime {
+    /// Guess the runtime from the endpoint address
+    ///
+    /// Podman sockets live under a `podman` directory, and `CONTAINER_HOST`
+    /// is only used by Podman.
+    pub fn from_endpoint(endpoint: &DockerEndpoint) -> Self {
+        if endpoint.source == EndpointSource::Environment("CONTAINER_HOST")
+            || endpoint.host.contains("podman")
+        {
+            Runtime::Podman
+        } else {
+            Runtime::Docker
+        }
+    }
+}
+
+/// Podman API sockets to look for, rootless first
+pub fn podman_socket_candidates(env: &EndpointEnv) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Some(dir) = &env.xdg_runtime_dir {
+        candidates.push(dir.join("podman").join("podman.sock"));
+    }
+    candidates.push(PathBuf::from("/run/podman/podman.sock"));
+    candidates
+}
+
+/// Resolve the endpoint and runtime to use
+///
+/// With no runtime given, the Docker endpoint is used unless it is the
+/// default socket and that does not exist while a Podman socket does.
+pub fn resolve_runtime_endpoint(
+    host_flag: Option<&str>,
+    runtime: Option<Runtime>,
+    env: &EndpointEnv,
+) -> Result<(DockerEndpoint, Runtime)> {
+    match runtime {
+        Some(Runtime::Podman) => Ok((resolve_podman_endpoint(host_flag, env)?, Runtime::Podman)),
+        Some(Runtime::Docker) => Ok((resolve_endpoint(host_flag, env)?, Runtime::Docker)),
+        None => {
+            let endpoint = resolve_endpoint(host_flag, env)?;
+            if endpoint.source == EndpointSource::Default && !socket_exists(DEFAULT_HOST) {
+                if let Ok(podman) = resolve_podman_endpoint(None, env) {
+                    return Ok((podman, Runtime::Podman));
+                }
+            }
+            let runtime = Runtime::from_endpoint(&endpoint);
+            Ok((endpoint, runtime))
+        }
+    }
+}
+
+fn resolve_podman_endpoint(host_flag: Option<&str>, env: &EndpointEnv) -> Result<DockerEndpoint> {
+    if let Some(host) = host_flag {
+        return Ok(env_endpoint(host, EndpointSource::Flag, env));
+    }
+
+    if let Some(host) = &env.container_host {
+        return Ok(env_endpoint(
+            host,
+            EndpointSource::Environment("CONTAINER_HOST"),
+            env,
+        ));
+    }
+
+    let candidates = podman_socket_candidates(env);
+    match candidates.iter().find(|path| path.exists()) {
+        Some(path) => Ok(DockerEndpoint {
+            host: format!("unix://{}", path.display()),
+            tls: None,
+            source: EndpointSource::Socket,
+        }),
+        None => Err(FlockerError::Docker(format!(
+            "No Podman socket found (tried {}). Start it with `systemctl --user start podman.socket`",
+            candidates
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+fn socket_exists(host: &str) -> bool {
+    match host.strip_prefix("unix://") {
+        Some(path) => Path::new(path).exists(),
+        // Named pipes can't be checked this way; assume the default is there
+        None => true,
+    }
+}
+
+/// Strip the default registry prefix Podman adds to image references
+///
+/// `docker.io/fluree/server:latest` becomes `fluree/server:latest`. Locally
+/// built images keep their `localhost/` prefix, since Podman needs it to
+/// find them.
+pub fn normalize_image_reference(reference: &str) -> &str {
+    reference
+        .strip_prefix("docker.io/library/")
+        .or_else(|| reference.strip_prefix("docker.io/"))
+        .unwrap_or(reference)
+}
+
+/// The registry host of a repository name, if it names one
+///
+/// Like Docker, the first path component is a registry host if it contains
+/// a '.' or ':' or is `localhost`; otherwise the repository is on Docker Hub.
+pub fn registry_host(repository: &str) -> Option<&str> {
+    let (first, _) = repository.split_once('/')?;
+    (first.contains('.') || first.contains(':') || first == "localhost").then_some(first)
+}
+
+/// Check that a repository name is valid and has no tag or digest
+pub fn validate_repository(repository: &str) -> Result<()> {
+    let path = match registry_host(repository) {
+        Some(host) => &repository[host.len() + 1..],
+        None => repository,
+    };
+    let valid = !path.is_empty()
+        && path.split('/').all(|component| {
+            !component.is_empty()
+                && component.chars().all(|c| {
+                    c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '_' | '-')
+                })
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(FlockerError::UserInput(format!(
+            "Invalid image repository '{}': expected a name like fluree/server or registry.example.com/team/fluree, without a tag",
+            repository
+        )))
+    }
+}
+
+/// Check whether an image reference belongs to `repository`, ignoring
+/// Podman's registry prefixes
+pub fn is_repository_reference(reference: &str, repository: &str) -> bool {
+    let reference = normalize_image_reference(reference);
+    let reference = reference.strip_prefix("localhost/").unwrap_or(reference);
+    reference
+        .strip_prefix(repository)
+        .is_some_and(|rest| rest.starts_with(':'))
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    #[parallel]
+    fn test_runtime_from_endpoint() {
+        let endpoint = |host: &str, source| DockerEndpoint {
+            host: host.to_string(),
+            tls: None,
+            source,
+        };
+        assert_eq!(
+            Runtime::from_endpoint(&endpoint(
+                "unix:///run/user/1000/podman/podman.sock",
+                EndpointSource::Socket
+            )),
+            Runtime::Podman
+        );
+        assert_eq!(
+            Runtime::from_endpoint(&endpoint(
+                "tcp://10.0.0.5:8888",
+                EndpointSource::Environment("CONTAINER_HOST")
+            )),
+            Runtime::Podman
+        );
+        assert_eq!(
+            Runtime::from_endpoint(&endpoint(
+                "unix:///var/run/docker.sock",
+                EndpointSource::Default
+            )),
+            Runtime::Docker
+        );
+    }
+
+    #[test]
+    #[parallel]
+    fn test_resolve_podman_socket() {
+        let dir = tempdir().unwrap();
+        let env = EndpointEnv {
+            xdg_runtime_dir: Some(dir.path().to_path_buf()),
+            config_dir: dir.path().to_path_buf(),
+            ..Default::default()
+        };
+
+        // Without a socket the error lists where we looked
+        if !Path::new("/run/podman/podman.sock").exists() {
+            let error = resolve_runtime_endpoint(None, Some(Runtime::Podman), &env).unwrap_err();
+            assert!(error.to_string().contains("podman/podman.sock"));
+        }
+
+        std::fs::create_dir_all(dir.path().join("podman")).unwrap();
+        std::fs::write(dir.path().join("podman/podman.sock"), "").unwrap();
+        let (endpoint, runtime) =
+            resolve_runtime_endpoint(None, Some(Runtime::Podman), &env).unwrap();
+        assert_eq!(runtime, Runtime::Podman);
+        assert_eq!(
+            endpoint.host,
+            format!("unix://{}/podman/podman.sock", dir.path().display())
+        );
+
+        // An explicit host still wins
+        let (endpoint, _) =
+            resolve_runtime_endpoint(Some("tcp://podman-box:8080"), Some(Runtime::Podman), &env)
+                .unwrap();
+        assert_eq!(endpoint.host, "tcp://podman-box:8080");
+    }
+
+    #[test]
+    #[parallel]
+    fn test_container_host_selects_podman() {
+        let dir = tempdir().unwrap();
+        let env = EndpointEnv {
+            container_host: Some("unix:///tmp/podman.sock".to_string()),
+            config_dir: dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let (endpoint, runtime) =
+            resolve_runtime_endpoint(None, Some(Runtime::Podman), &env).unwrap();
+        assert_eq!(
+            endpoint.to_string(),
+            "unix:///tmp/podman.sock (from CONTAINER_HOST)"
+        );
+        assert_eq!(runtime, Runtime::Podman);
+    }
+
+    #[test]
+    #[parallel]
+    fn test_normalize_image_reference() {
+        assert_eq!(
+            normalize_image_reference("docker.io/fluree/server:latest"),
+            "fluree/server:latest"
+        );
+        assert_eq!(
+            normalize_image_reference("docker.io/library/alpine:3"),
+            "alpine:3"
+        );
+        assert_eq!(
+            normalize_image_reference("localhost/fluree/server:dev"),
+            "localhost/fluree/server:dev"
+        );
+        assert_eq!(
+            normalize_image_reference("fluree/server:latest"),
+            "fluree/server:latest"
+        );
+    }
+
+    #[test]
+    #[parallel]
+    fn test_registry_host() {
+        assert_eq!(registry_host("fluree/server"), None);
+        assert_eq!(registry_host("alpine"), None);
+        assert_eq!(
+            registry_host("registry.internal:5000/team/fluree"),
+            Some("registry.internal:5000")
+        );
+        assert_eq!(registry_host("localhost/fluree/server"), Some("localhost"));
+    }
+
+    #[test]
+    #[parallel]
+    fn test_validate_repository() {
+        assert!(validate_repository("fluree/server").is_ok());
+        assert!(validate_repository("registry.internal:5000/team/fluree-patched").is_ok());
+        assert!(validate_repository("fluree/server:latest").is_err());
+        assert!(validate_repository("Fluree/Server").is_err());
+        assert!(validate_repository("fluree//server").is_err());
+        assert!(validate_repository("").is_err());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_is_repository_reference() {
+        assert!(is_repository_reference(
+            "fluree/server:latest",
+            "fluree/server"
+        ));
+        assert!(is_repository_reference(
+            "docker.io/fluree/server:v3.0.0",
+            "fluree/server"
+        ));
+        assert!(is_repository_reference(
+            "localhost/fluree/server:dev",
+            "fluree/server"
+        ));
+        assert!(!is_repository_reference(
+            "fluree/server-lite:latest",
+            "fluree/server"
+        ));
+        assert!(!is_repository_reference("<none>:<none>", "fluree/server"));
+        assert!(is_repository_reference(
+            "registry.internal/team/fluree-patched:1.2",
+            "registry.internal/team/fluree-patched"
+        ));
+    }
+}
@@ -1,12 +1,29 @@
+use std::net::{IpAddr, Ipv4Addr};
 use std::path::PathBuf;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::cli::hub::Tag;
+use crate::{FlockerError, Result};
+
+/// Image repository used when none is configured
+pub const DEFAULT_IMAGE_REPOSITORY: &str = "fluree/server";
+
+/// Signal sent when killing a container unless another is given
+pub const DEFAULT_KILL_SIGNAL: &str = "SIGKILL";
+
+/// Environment variable JVM options are passed in unless another is given
+pub const DEFAULT_JAVA_OPTS_VAR: &str = "JAVA_OPTS";
+
+/// Host address published ports are bound to unless another is given
+pub const DEFAULT_BIND_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+/// Directory the Fluree server keeps its ledgers in, inside the container
+pub const CONTAINER_DATA_DIR: &str = "/opt/fluree-server/data";
 
 /// Represents a Fluree Docker image
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FlureeImage {
     pub tag: Tag,
     pub id: String,
@@ -15,15 +32,446 @@ pub struct FlureeImage {
 }
 
 /// Represents a Fluree ledger
+///
+/// The statistics are those of the default branch.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LedgerInfo {
     pub alias: String,
     pub last_commit_time: String,
     pub commit_count: u64,
     pub size: u64,
-    pub path: String,
+    /// Nameservice file in the container, for ledgers read from disk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
     pub flakes_count: String,
     pub last_index: Option<u64>,
+    /// All branches, the default branch first
+    #[serde(default)]
+    pub branches: Vec<BranchInfo>,
+}
+
+/// A branch of a Fluree ledger
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchInfo {
+    pub name: String,
+    pub last_commit_time: String,
+    pub commit_count: u64,
+    pub size: u64,
+    pub flakes_count: String,
+    pub last_index: Option<u64>,
+}
+
+/// Resource usage snapshot for a container
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContainerStats {
+    /// Container ID
+    pub id: String,
+    /// CPU usage as a percentage of a single core (may exceed 100% on multi-core hosts)
+    pub cpu_percent: f64,
+    /// Memory currently in use, in bytes
+    pub memory_usage: u64,
+    /// Memory limit, in bytes
+    pub memory_limit: u64,
+    /// Bytes received across all network interfaces
+    pub network_rx: u64,
+    /// Bytes sent across all network interfaces
+    pub network_tx: u64,
+    /// Bytes read from block devices
+    pub block_read: u64,
+    /// Bytes written to block devices
+    pub block_write: u64,
+    /// Number of processes or threads in the container
+    pub pids: u64,
+}
+
+impl ContainerStats {
+    /// Memory usage as a percentage of the limit
+    pub fn memory_percent(&self) -> f64 {
+        if self.memory_limit == 0 {
+            0.0
+        } else {
+            (self.memory_usage as f64 / self.memory_limit as f64) * 100.0
+        }
+    }
+}
+
+impl From<&bollard::container::Stats> for ContainerStats {
+    fn from(stats: &bollard::container::Stats) -> Self {
+        // CPU percentage is the container's share of the system delta since the
+        // previous sample, scaled by the number of online CPUs
+        let cpu_percent = match (
+            stats.cpu_stats.system_cpu_usage,
+            stats.precpu_stats.system_cpu_usage,
+        ) {
+            (Some(system_usage), Some(presystem_usage)) => {
+                let cpu_delta = stats.cpu_stats.cpu_usage.total_usage as f64
+                    - stats.precpu_stats.cpu_usage.total_usage as f64;
+                let system_delta = system_usage as f64 - presystem_usage as f64;
+                if system_delta > 0.0 && cpu_delta > 0.0 {
+                    (cpu_delta / system_delta)
+                        * 100.0
+                        * stats.cpu_stats.online_cpus.unwrap_or(1) as f64
+                } else {
+                    0.0
+                }
+            }
+            _ => 0.0,
+        };
+
+        let (network_rx, network_tx) = stats
+            .networks
+            .iter()
+            .flat_map(|networks| networks.values())
+            .fold((0, 0), |(rx, tx), net| {
+                (rx + net.rx_bytes, tx + net.tx_bytes)
+            });
+
+        let (block_read, block_write) = stats
+            .blkio_stats
+            .io_service_bytes_recursive
+            .iter()
+            .flatten()
+            .fold((0, 0), |(read, write), entry| {
+                match entry.op.to_ascii_lowercase().as_str() {
+                    "read" => (read + entry.value, write),
+                    "write" => (read, write + entry.value),
+                    _ => (read, write),
+                }
+            });
+
+        Self {
+            id: stats.id.clone(),
+            cpu_percent,
+            memory_usage: stats.memory_stats.usage.unwrap_or(0),
+            memory_limit: stats.memory_stats.limit.unwrap_or(0),
+            network_rx,
+            network_tx,
+            block_read,
+            block_write,
+            pids: stats.pids_stats.current.unwrap_or(0),
+        }
+    }
+}
+
+/// A progress update from an image pull
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PullProgress {
+    /// Layer ID, or `None` for messages about the whole image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    /// Status text, e.g. "Downloading" or "Pull complete"
+    pub status: String,
+    /// Bytes processed so far in the current step
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<u64>,
+    /// Total bytes in the current step
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+}
+
+/// Options for fetching container logs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogOptions {
+    /// Number of lines to return from the end of the logs, or "all"
+    pub tail: String,
+    /// Only return logs at or after this Unix timestamp
+    pub since: Option<i64>,
+    /// Only return logs before this Unix timestamp
+    pub until: Option<i64>,
+}
+
+impl Default for LogOptions {
+    fn default() -> Self {
+        Self {
+            tail: "1000".to_string(),
+            since: None,
+            until: None,
+        }
+    }
+}
+
+/// Resource limits for a container and the JVM running in it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    /// Memory limit in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<u64>,
+    /// Number of CPUs the container may use, e.g. 1.5
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpus: Option<f64>,
+    /// Maximum Java heap size in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub java_heap: Option<u64>,
+    /// Environment variable for JVM options, if not `JAVA_OPTS`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub java_opts_var: Option<String>,
+}
+
+impl ResourceLimits {
+    /// Whether no limits are set
+    pub fn is_empty(&self) -> bool {
+        self.memory.is_none() && self.cpus.is_none() && self.java_heap.is_none()
+    }
+
+    /// Environment entry (`NAME=value`) passing the heap size to the JVM
+    pub fn java_env(&self) -> Option<String> {
+        let heap = self.java_heap?;
+        Some(format!(
+            "{}=-Xmx{}",
+            self.java_opts_var
+                .as_deref()
+                .unwrap_or(DEFAULT_JAVA_OPTS_VAR),
+            jvm_size(heap)
+        ))
+    }
+
+    /// CPU limit in the billionths of a CPU the Docker API expects
+    pub fn nano_cpus(&self) -> Option<i64> {
+        self.cpus.map(|cpus| (cpus * 1e9).round() as i64)
+    }
+}
+
+/// Environment, extra server arguments and entrypoint for a container
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandOptions {
+    /// Environment variables as `NAME=value`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env: Vec<String>,
+    /// Arguments passed to the server, after `--config` if a config file is used
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Program to run instead of the image's entrypoint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entrypoint: Option<String>,
+}
+
+impl CommandOptions {
+    /// Whether nothing is customized
+    pub fn is_empty(&self) -> bool {
+        self.env.is_empty() && self.args.is_empty() && self.entrypoint.is_none()
+    }
+
+    /// Names of the environment variables that are set
+    pub fn env_names(&self) -> impl Iterator<Item = &str> {
+        self.env.iter().map(|entry| {
+            entry
+                .split_once('=')
+                .map_or(entry.as_str(), |(name, _)| name)
+        })
+    }
+}
+
+/// When the daemon restarts a container after it exits or the daemon starts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum RestartPolicy {
+    /// Never restart the container
+    #[default]
+    No,
+    /// Restart after a nonzero exit, at most this many times if given
+    OnFailure(Option<u32>),
+    /// Always restart, unless the container was stopped by the user
+    UnlessStopped,
+    /// Always restart, also when the daemon starts
+    Always,
+}
+
+impl RestartPolicy {
+    /// Whether this is the default policy of not restarting
+    pub fn is_no(&self) -> bool {
+        *self == RestartPolicy::No
+    }
+
+    /// The policy in the form the Docker API expects
+    pub fn to_docker(self) -> bollard::models::RestartPolicy {
+        use bollard::models::RestartPolicyNameEnum as Name;
+
+        let (name, maximum_retry_count) = match self {
+            RestartPolicy::No => (Name::NO, None),
+            RestartPolicy::OnFailure(retries) => (Name::ON_FAILURE, retries.map(i64::from)),
+            RestartPolicy::UnlessStopped => (Name::UNLESS_STOPPED, None),
+            RestartPolicy::Always => (Name::ALWAYS, None),
+        };
+        bollard::models::RestartPolicy {
+            name: Some(name),
+            maximum_retry_count,
+        }
+    }
+}
+
+impl std::str::FromStr for RestartPolicy {
+    type Err = FlockerError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let (name, retries) = match value.split_once(':') {
+            Some((name, retries)) => (name, Some(retries)),
+            None => (value, None),
+        };
+        match (name, retries) {
+            ("no", None) => Ok(RestartPolicy::No),
+            ("on-failure", None) => Ok(RestartPolicy::OnFailure(None)),
+            ("on-failure", Some(retries)) => retries
+                .parse()
+                .map(|retries| RestartPolicy::OnFailure(Some(retries)))
+                .map_err(|_| {
+                    FlockerError::UserInput(format!(
+                        "Invalid restart count '{}': expected a number",
+                        retries
+                    ))
+                }),
+            ("unless-stopped", None) => Ok(RestartPolicy::UnlessStopped),
+            ("always", None) => Ok(RestartPolicy::Always),
+            _ => Err(FlockerError::UserInput(format!(
+                "Invalid restart policy '{}': expected no, on-failure[:N], unless-stopped or always",
+                value
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for RestartPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RestartPolicy::No => write!(f, "no"),
+            RestartPolicy::OnFailure(None) => write!(f, "on-failure"),
+            RestartPolicy::OnFailure(Some(retries)) => write!(f, "on-failure:{}", retries),
+            RestartPolicy::UnlessStopped => write!(f, "unless-stopped"),
+            RestartPolicy::Always => write!(f, "always"),
+        }
+    }
+}
+
+impl TryFrom<String> for RestartPolicy {
+    type Error = FlockerError;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<RestartPolicy> for String {
+    fn from(policy: RestartPolicy) -> Self {
+        policy.to_string()
+    }
+}
+
+/// A container port published on the host, besides the Fluree port
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PortMapping {
+    pub host_port: u16,
+    pub container_port: u16,
+}
+
+impl std::str::FromStr for PortMapping {
+    type Err = FlockerError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let invalid = || {
+            FlockerError::UserInput(format!(
+                "Invalid port mapping '{}': expected HOST_PORT:CONTAINER_PORT",
+                value
+            ))
+        };
+        let (host_port, container_port) = value.split_once(':').ok_or_else(invalid)?;
+        match (host_port.parse(), container_port.parse()) {
+            (Ok(host_port), Ok(container_port)) if host_port > 0 && container_port > 0 => {
+                Ok(PortMapping {
+                    host_port,
+                    container_port,
+                })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl std::fmt::Display for PortMapping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.host_port, self.container_port)
+    }
+}
+
+impl TryFrom<String> for PortMapping {
+    type Error = FlockerError;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<PortMapping> for String {
+    fn from(mapping: PortMapping) -> Self {
+        mapping.to_string()
+    }
+}
+
+/// A Docker network a container is attached to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkAttachment {
+    /// Network name, created as a bridge network if it doesn't exist
+    pub name: String,
+    /// Extra names other containers on the network can reach the container by
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+}
+
+/// Host address, Docker networks and extra published ports for a container
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkOptions {
+    /// Host address published ports are bound to
+    pub bind_address: IpAddr,
+    /// Docker networks to attach the container to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub networks: Vec<NetworkAttachment>,
+    /// Ports published besides the Fluree port
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<PortMapping>,
+}
+
+impl Default for NetworkOptions {
+    fn default() -> Self {
+        Self {
+            bind_address: DEFAULT_BIND_ADDRESS,
+            networks: Vec::new(),
+            ports: Vec::new(),
+        }
+    }
+}
+
+impl NetworkOptions {
+    /// Whether only the defaults are used
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Host to reach published ports at from this machine
+    ///
+    /// `remote_host` is the daemon's host when it runs on another machine.
+    /// Ports bound to all of its addresses are reached there, while ports
+    /// bound to its loopback address can't be reached from here at all.
+    pub fn host(&self, remote_host: Option<&str>) -> Option<String> {
+        match (self.bind_address, remote_host) {
+            (address, Some(_)) if address.is_loopback() => None,
+            (address, Some(host)) if address.is_unspecified() => Some(host.to_string()),
+            (address, None) if address.is_unspecified() || address.is_loopback() => {
+                Some("localhost".to_string())
+            }
+            (IpAddr::V6(address), _) => Some(format!("[{}]", address)),
+            (address, _) => Some(address.to_string()),
+        }
+    }
+}
+
+/// Format a size in bytes the way JVM flags expect, e.g. `512m` or `2g`
+fn jvm_size(bytes: u64) -> String {
+    // Units are powers of two, so a size is a whole number of units when
+    // enough of its low bits are zero
+    [("g", 30), ("m", 20), ("k", 10)]
+        .into_iter()
+        .find(|(_, shift)| bytes.trailing_zeros() >= *shift)
+        .map(|(suffix, shift)| format!("{}{}", bytes >> shift, suffix))
+        .unwrap_or_else(|| bytes.to_string())
 }
 
 /// Represents container configuration options
@@ -34,6 +482,10 @@ pub struct ContainerConfig {
     pub data_mount_path: Option<PathBuf>,
     pub config_mount_path: Option<PathBuf>,
     pub config_file: Option<PathBuf>,
+    pub resources: ResourceLimits,
+    pub command: CommandOptions,
+    pub restart_policy: RestartPolicy,
+    pub network: NetworkOptions,
 }
 
 impl ContainerConfig {
@@ -55,6 +507,10 @@ impl From<&crate::config::FlureeConfig> for ContainerConfig {
             data_mount_path: config.data_mount.clone(),
             config_mount_path: config.config_mount.clone(),
             config_file: config.config_file.as_ref().map(PathBuf::from),
+            resources: config.resources.clone(),
+            command: config.command.clone(),
+            restart_policy: config.restart_policy,
+            network: config.network.clone(),
         }
     }
 }
@@ -67,6 +523,173 @@ impl Default for ContainerConfig {
             data_mount_path: None,
             config_mount_path: None,
             config_file: None,
+            resources: ResourceLimits::default(),
+            command: CommandOptions::default(),
+            restart_policy: RestartPolicy::default(),
+            network: NetworkOptions::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+
+    use super::*;
+
+    fn docker_stats() -> bollard::container::Stats {
+        serde_json::from_value(serde_json::json!({
+            "read": "2024-01-01T00:00:01Z",
+            "preread": "2024-01-01T00:00:00Z",
+            "num_procs": 0,
+            "name": "/dev",
+            "id": "abc123",
+            "pids_stats": { "current": 42 },
+            "networks": {
+                "eth0": {
+                    "rx_bytes": 1000, "rx_packets": 0, "rx_errors": 0, "rx_dropped": 0,
+                    "tx_bytes": 500, "tx_packets": 0, "tx_errors": 0, "tx_dropped": 0
+                },
+                "eth1": {
+                    "rx_bytes": 24, "rx_packets": 0, "rx_errors": 0, "rx_dropped": 0,
+                    "tx_bytes": 12, "tx_packets": 0, "tx_errors": 0, "tx_dropped": 0
+                }
+            },
+            "memory_stats": { "usage": 256, "limit": 1024 },
+            "blkio_stats": {
+                "io_service_bytes_recursive": [
+                    { "major": 8, "minor": 0, "op": "read", "value": 4096 },
+                    { "major": 8, "minor": 0, "op": "write", "value": 8192 },
+                    { "major": 8, "minor": 0, "op": "Read", "value": 4096 }
+                ]
+            },
+            "cpu_stats": {
+                "cpu_usage": { "total_usage": 300, "usage_in_usermode": 0, "usage_in_kernelmode": 0 },
+                "system_cpu_usage": 2000,
+                "online_cpus": 2,
+                "throttling_data": { "periods": 0, "throttled_periods": 0, "throttled_time": 0 }
+            },
+            "precpu_stats": {
+                "cpu_usage": { "total_usage": 100, "usage_in_usermode": 0, "usage_in_kernelmode": 0 },
+                "system_cpu_usage": 1000,
+                "throttling_data": { "periods": 0, "throttled_periods": 0, "throttled_time": 0 }
+            },
+            "storage_stats": {}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    #[parallel]
+    fn test_container_stats_from_docker() {
+        let stats = ContainerStats::from(&docker_stats());
+        assert_eq!(stats.id, "abc123");
+        assert!((stats.cpu_percent - 40.0).abs() < f64::EPSILON);
+        assert_eq!(stats.memory_usage, 256);
+        assert_eq!(stats.memory_limit, 1024);
+        assert!((stats.memory_percent() - 25.0).abs() < f64::EPSILON);
+        assert_eq!((stats.network_rx, stats.network_tx), (1024, 512));
+        assert_eq!((stats.block_read, stats.block_write), (8192, 8192));
+        assert_eq!(stats.pids, 42);
+    }
+
+    #[test]
+    #[parallel]
+    fn test_memory_percent_without_limit() {
+        let stats = ContainerStats {
+            memory_usage: 100,
+            ..Default::default()
+        };
+        assert_eq!(stats.memory_percent(), 0.0);
+    }
+
+    #[test]
+    #[parallel]
+    fn test_resource_limits() {
+        let limits = ResourceLimits {
+            memory: Some(2 * 1024 * 1024 * 1024),
+            cpus: Some(1.5),
+            java_heap: Some(1536 * 1024 * 1024),
+            java_opts_var: None,
+        };
+        assert_eq!(limits.java_env().as_deref(), Some("JAVA_OPTS=-Xmx1536m"));
+        assert_eq!(limits.nano_cpus(), Some(1_500_000_000));
+
+        let limits = ResourceLimits {
+            java_heap: Some(1024 * 1024 * 1024),
+            java_opts_var: Some("JAVA_TOOL_OPTIONS".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            limits.java_env().as_deref(),
+            Some("JAVA_TOOL_OPTIONS=-Xmx1g")
+        );
+        assert!(ResourceLimits::default().java_env().is_none());
+        assert!(ResourceLimits::default().is_empty());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_restart_policy() {
+        for policy in [
+            "no",
+            "on-failure",
+            "on-failure:5",
+            "unless-stopped",
+            "always",
+        ] {
+            assert_eq!(policy.parse::<RestartPolicy>().unwrap().to_string(), policy);
         }
+        assert_eq!(
+            "on-failure:3".parse::<RestartPolicy>().unwrap(),
+            RestartPolicy::OnFailure(Some(3))
+        );
+        assert!("sometimes".parse::<RestartPolicy>().is_err());
+        assert!("always:3".parse::<RestartPolicy>().is_err());
+        assert!("on-failure:x".parse::<RestartPolicy>().is_err());
+
+        let docker = RestartPolicy::OnFailure(Some(3)).to_docker();
+        assert_eq!(docker.maximum_retry_count, Some(3));
+        assert_eq!(
+            serde_json::to_value(RestartPolicy::UnlessStopped).unwrap(),
+            "unless-stopped"
+        );
+    }
+
+    #[test]
+    #[parallel]
+    fn test_network_options() {
+        let mapping: PortMapping = "9000:8080".parse().unwrap();
+        assert_eq!(
+            mapping,
+            PortMapping {
+                host_port: 9000,
+                container_port: 8080
+            }
+        );
+        assert_eq!(mapping.to_string(), "9000:8080");
+        assert!("9000".parse::<PortMapping>().is_err());
+        assert!("0:8080".parse::<PortMapping>().is_err());
+        assert!("9000:http".parse::<PortMapping>().is_err());
+
+        let mut network = NetworkOptions::default();
+        assert!(network.is_default());
+        assert_eq!(network.host(None).as_deref(), Some("localhost"));
+        // A remote daemon's loopback address can't be reached from here
+        assert_eq!(network.host(Some("build-box")), None);
+        network.bind_address = "0.0.0.0".parse().unwrap();
+        assert_eq!(network.host(None).as_deref(), Some("localhost"));
+        assert_eq!(
+            network.host(Some("build-box")).as_deref(),
+            Some("build-box")
+        );
+        network.bind_address = "192.168.1.20".parse().unwrap();
+        assert_eq!(network.host(None).as_deref(), Some("192.168.1.20"));
+        network.bind_address = "fd00::1".parse().unwrap();
+        assert_eq!(network.host(None).as_deref(), Some("[fd00::1]"));
+        assert_eq!(
+            network.host(Some("build-box")).as_deref(),
+            Some("[fd00::1]")
+        );
     }
 }
@@ -27,6 +27,20 @@ pub enum FlockerError {
     /// User interaction errors
     #[error("User input error: {0}")]
     UserInput(String),
+    /// Operations cancelled by the user
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+    /// Ledger backup, restore and copy errors
+    #[error("Ledger transfer error: {0}")]
+    Transfer(String),
+    /// Fluree HTTP API errors
+    #[error("Fluree API error: {message}")]
+    Fluree {
+        /// HTTP status, if the server answered
+        status: Option<u16>,
+        /// Error message
+        message: String,
+    },
 }
 
 impl From<std::io::Error> for FlockerError {
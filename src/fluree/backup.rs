@@ -0,0 +1,610 @@
+//! Ledger backups.
+//!
+//! A backup is a gzipped tar archive holding a manifest and the ledger's
+//! directory: its nameservice file and the commit, index and txn files next
+//! to it. Files are read from and written to the host data directory when
+//! the container has one, and copied with the Docker archive API otherwise.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::host::HostLedgerReader;
+use super::nameservice::NameserviceRecord;
+use crate::docker::{DockerOperations, LedgerInfo, CONTAINER_DATA_DIR};
+use crate::state::ContainerInfo;
+use crate::{FlockerError, Result};
+
+/// Name of the manifest in a backup archive
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Version of the backup format written by this version of flocker
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// Directory holding the ledger's files in a backup archive
+const LEDGER_ENTRY_DIR: &str = "ledger";
+
+/// Contents of ledger files by path relative to the ledger directory
+pub type LedgerFiles = BTreeMap<String, Vec<u8>>;
+
+/// Describes the contents of a backup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// Backup format version
+    pub version: u32,
+    /// Ledger alias
+    pub alias: String,
+    /// Ledger directory, relative to the server's data directory
+    pub ledger_dir: String,
+    /// Name of the container the ledger was backed up from
+    pub container: String,
+    /// Image tag of that container
+    pub image: String,
+    /// When the backup was made
+    pub created_at: String,
+    /// Every file of the ledger
+    pub files: Vec<BackupFile>,
+}
+
+/// A file in a backup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupFile {
+    /// Path relative to the ledger directory
+    pub path: String,
+    /// Size in bytes
+    pub size: u64,
+    /// Hex encoded SHA-256 of the contents
+    pub sha256: String,
+}
+
+impl BackupManifest {
+    /// Describe a backup of `files` from the ledger in `ledger_dir`
+    pub fn new(
+        alias: &str,
+        ledger_dir: &str,
+        container: &ContainerInfo,
+        files: &LedgerFiles,
+    ) -> Self {
+        Self {
+            version: BACKUP_FORMAT_VERSION,
+            alias: alias.to_string(),
+            ledger_dir: ledger_dir.to_string(),
+            container: container.name.clone(),
+            image: container.image_tag.clone(),
+            created_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            files: files
+                .iter()
+                .map(|(path, data)| BackupFile {
+                    path: path.clone(),
+                    size: data.len() as u64,
+                    sha256: sha256_hex(data),
+                })
+                .collect(),
+        }
+    }
+
+    /// Total size of the ledger's files
+    pub fn size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+}
+
+/// File name for a new backup of a ledger
+pub fn default_backup_file(alias: &str) -> PathBuf {
+    let timestamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+    PathBuf::from(format!("{}-{}.tar.gz", alias.replace('/', "-"), timestamp))
+}
+
+/// Back up a ledger of a container to an archive
+pub async fn backup_ledger(
+    docker: &impl DockerOperations,
+    container: &ContainerInfo,
+    alias: &str,
+    archive: &Path,
+) -> Result<BackupManifest> {
+    let (_, ledger_dir, files) = read_ledger(docker, container, alias).await?;
+    let manifest = BackupManifest::new(alias, &ledger_dir, container, &files);
+    write_backup(archive, &manifest, &files)?;
+    Ok(manifest)
+}
+
+/// Restore a ledger from an archive into a container
+///
+/// Nothing is written if the archive doesn't match its manifest, or the
+/// container already has a ledger with the same alias or directory.
+pub async fn restore_ledger(
+    docker: &impl DockerOperations,
+    container: &ContainerInfo,
+    archive: &Path,
+) -> Result<BackupManifest> {
+    let (manifest, files) = read_backup(archive)?;
+    write_ledger(
+        docker,
+        container,
+        &manifest.alias,
+        &manifest.ledger_dir,
+        &files,
+    )
+    .await?;
+    Ok(manifest)
+}
+
+/// Read the files of a ledger of a container
+///
+/// Returns the ledger as listed from disk, its directory relative to the
+/// data directory, and its files.
+pub(super) async fn read_ledger(
+    docker: &impl DockerOperations,
+    container: &ContainerInfo,
+    alias: &str,
+) -> Result<(LedgerInfo, String, LedgerFiles)> {
+    let host = HostLedgerReader::for_container(container);
+    let ledger = list_on_disk(docker, container)
+        .await?
+        .into_iter()
+        .find(|ledger| ledger.alias == alias)
+        .ok_or_else(|| {
+            FlockerError::Transfer(format!(
+                "Ledger '{}' not found in '{}'",
+                alias, container.name
+            ))
+        })?;
+    let path = ledger
+        .path
+        .as_deref()
+        .ok_or_else(|| FlockerError::Transfer(format!("Ledger '{}' has no directory", alias)))?;
+    let ledger_dir = ledger_dir(path)?;
+
+    let files = match &host {
+        Some(host) => read_files(&host.data_dir().join(&ledger_dir))?,
+        None => {
+            let path = format!("{}/{}", CONTAINER_DATA_DIR, ledger_dir);
+            let archive = docker.download_from_container(&container.id, &path).await?;
+            unpack_directory(&archive)?
+        }
+    };
+    Ok((ledger, ledger_dir, files))
+}
+
+/// Write the files of a ledger into a container
+///
+/// Fails without writing anything if the container already has a ledger
+/// with the same alias or directory.
+pub(super) async fn write_ledger(
+    docker: &impl DockerOperations,
+    container: &ContainerInfo,
+    alias: &str,
+    ledger_dir: &str,
+    files: &LedgerFiles,
+) -> Result<()> {
+    let ledgers = list_on_disk(docker, container).await?;
+    check_conflicts(alias, ledger_dir, &ledgers, container)?;
+
+    match HostLedgerReader::for_container(container) {
+        Some(host) => {
+            let dir = host.data_dir().join(ledger_dir);
+            if dir.exists() {
+                return Err(FlockerError::Transfer(format!(
+                    "{} already exists",
+                    dir.display()
+                )));
+            }
+            write_files(&dir, files)
+        }
+        None => {
+            let archive = pack_directory(ledger_dir, files)?;
+            docker
+                .upload_to_container(&container.id, CONTAINER_DATA_DIR, archive)
+                .await
+        }
+    }
+}
+
+/// List the ledgers of a container with their nameservice files
+pub(super) async fn list_on_disk(
+    docker: &impl DockerOperations,
+    container: &ContainerInfo,
+) -> Result<Vec<LedgerInfo>> {
+    match HostLedgerReader::for_container(container) {
+        Some(host) => host.list(),
+        None => docker.list_ledgers(&container.id).await,
+    }
+}
+
+/// Read a backup archive and check its files against the manifest
+pub fn read_backup(archive: &Path) -> Result<(BackupManifest, LedgerFiles)> {
+    let mut tar = tar::Archive::new(GzDecoder::new(File::open(archive)?));
+    let mut manifest = None;
+    let mut files = LedgerFiles::new();
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+
+        if name == MANIFEST_FILE {
+            manifest = Some(
+                serde_json::from_slice::<BackupManifest>(&data).map_err(|e| {
+                    FlockerError::Transfer(format!(
+                        "Invalid manifest in {}: {}",
+                        archive.display(),
+                        e
+                    ))
+                })?,
+            );
+        } else if let Some(path) = name.strip_prefix(&format!("{}/", LEDGER_ENTRY_DIR)) {
+            files.insert(path.to_string(), data);
+        } else {
+            return Err(FlockerError::Transfer(format!(
+                "Unexpected file {} in {}",
+                name,
+                archive.display()
+            )));
+        }
+    }
+
+    let manifest = manifest.ok_or_else(|| {
+        FlockerError::Transfer(format!("{} has no {}", archive.display(), MANIFEST_FILE))
+    })?;
+    check_manifest(&manifest, &files)?;
+    Ok((manifest, files))
+}
+
+/// Write a backup archive
+fn write_backup(archive: &Path, manifest: &BackupManifest, files: &LedgerFiles) -> Result<()> {
+    let manifest_json = serde_json::to_vec_pretty(manifest)
+        .map_err(|e| FlockerError::Transfer(format!("Failed to write manifest: {}", e)))?;
+
+    let encoder = GzEncoder::new(File::create(archive)?, Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    append_file(&mut builder, MANIFEST_FILE, &manifest_json)?;
+    for (path, data) in files {
+        append_file(
+            &mut builder,
+            &format!("{}/{}", LEDGER_ENTRY_DIR, path),
+            data,
+        )?;
+    }
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// Check that an archive's files are exactly those of its manifest
+fn check_manifest(manifest: &BackupManifest, files: &LedgerFiles) -> Result<()> {
+    if manifest.version > BACKUP_FORMAT_VERSION {
+        return Err(FlockerError::Transfer(format!(
+            "Backup format version {} is newer than this version of flocker supports ({})",
+            manifest.version, BACKUP_FORMAT_VERSION
+        )));
+    }
+    check_relative(&manifest.ledger_dir)?;
+
+    for file in &manifest.files {
+        check_relative(&file.path)?;
+        let data = files.get(&file.path).ok_or_else(|| {
+            FlockerError::Transfer(format!("{} is missing from the archive", file.path))
+        })?;
+        if data.len() as u64 != file.size || sha256_hex(data) != file.sha256 {
+            return Err(FlockerError::Transfer(format!(
+                "{} doesn't match the manifest",
+                file.path
+            )));
+        }
+    }
+    if let Some(extra) = files
+        .keys()
+        .find(|path| !manifest.files.iter().any(|file| &&file.path == path))
+    {
+        return Err(FlockerError::Transfer(format!(
+            "{} is not listed in the manifest",
+            extra
+        )));
+    }
+
+    // The nameservice file sits directly in the ledger directory
+    let has_nameservice = files.iter().any(|(path, data)| {
+        !path.contains('/')
+            && serde_json::from_slice::<NameserviceRecord>(data)
+                .is_ok_and(|record| record.ledger_alias == manifest.alias)
+    });
+    if !has_nameservice {
+        return Err(FlockerError::Transfer(format!(
+            "The archive has no nameservice file for ledger '{}'",
+            manifest.alias
+        )));
+    }
+    Ok(())
+}
+
+/// Fail if a container already has the ledger, or another in its directory
+fn check_conflicts(
+    alias: &str,
+    ledger_dir: &str,
+    ledgers: &[LedgerInfo],
+    container: &ContainerInfo,
+) -> Result<()> {
+    for ledger in ledgers {
+        if ledger.alias == alias {
+            return Err(FlockerError::Transfer(format!(
+                "'{}' already has a ledger named '{}'; drop it first to replace it",
+                container.name, alias
+            )));
+        }
+        let dir = ledger
+            .path
+            .as_deref()
+            .and_then(|path| self::ledger_dir(path).ok());
+        if dir.as_deref() == Some(ledger_dir) {
+            return Err(FlockerError::Transfer(format!(
+                "Ledger '{}' in '{}' already uses the directory {}",
+                ledger.alias, container.name, ledger_dir
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// The directory of a ledger relative to the data directory, from the path
+/// of its nameservice file in the container
+pub(super) fn ledger_dir(nameservice_path: &str) -> Result<String> {
+    nameservice_path
+        .strip_prefix(CONTAINER_DATA_DIR)
+        .and_then(|path| path.strip_prefix('/'))
+        .and_then(|path| path.rsplit_once('/'))
+        .map(|(dir, _)| dir.to_string())
+        .ok_or_else(|| {
+            FlockerError::Transfer(format!("{} is not in a ledger directory", nameservice_path))
+        })
+}
+
+/// Fail unless a path stays inside the directory it is relative to
+fn check_relative(path: &str) -> Result<()> {
+    let normal = Path::new(path)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    if path.is_empty() || !normal {
+        return Err(FlockerError::Transfer(format!(
+            "Invalid path in manifest: {}",
+            path
+        )));
+    }
+    Ok(())
+}
+
+/// Read every file below a host directory
+fn read_files(dir: &Path) -> Result<LedgerFiles> {
+    let mut files = LedgerFiles::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(current) = dirs.pop() {
+        for entry in fs::read_dir(&current)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else {
+                let relative = path.strip_prefix(dir).unwrap_or(&path);
+                let name: Vec<_> = relative
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect();
+                files.insert(name.join("/"), fs::read(&path)?);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Write files below a host directory
+fn write_files(dir: &Path, files: &LedgerFiles) -> Result<()> {
+    for (path, data) in files {
+        let target = dir.join(path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(target, data)?;
+    }
+    Ok(())
+}
+
+/// Files of a directory downloaded from a container, whose archive entries
+/// are prefixed with the directory's name
+fn unpack_directory(archive: &[u8]) -> Result<LedgerFiles> {
+    let mut files = LedgerFiles::new();
+    for entry in tar::Archive::new(archive).entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry.path()?.to_string_lossy().into_owned();
+        if let Some((_, path)) = name.split_once('/') {
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data)?;
+            files.insert(path.to_string(), data);
+        }
+    }
+    Ok(files)
+}
+
+/// Pack files into an archive that creates `dir` when extracted
+fn pack_directory(dir: &str, files: &LedgerFiles) -> Result<Vec<u8>> {
+    let mut builder = tar::Builder::new(Vec::new());
+    for (path, data) in files {
+        append_file(&mut builder, &format!("{}/{}", dir, path), data)?;
+    }
+    Ok(builder.into_inner()?)
+}
+
+fn append_file<W: std::io::Write>(
+    builder: &mut tar::Builder<W>,
+    path: &str,
+    data: &[u8],
+) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, path, data)?;
+    Ok(())
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::fluree::nameservice::fixtures::COOKBOOK_RECORD;
+    use crate::state::test_container;
+
+    fn ledger_files() -> LedgerFiles {
+        LedgerFiles::from([
+            ("main.json".to_string(), COOKBOOK_RECORD.as_bytes().to_vec()),
+            ("commit/abc.json".to_string(), b"{}".to_vec()),
+            ("index/root.json".to_string(), b"{}".to_vec()),
+        ])
+    }
+
+    #[test]
+    #[parallel]
+    fn test_backup_round_trip() {
+        let dir = tempdir().unwrap();
+        let archive = dir.path().join("cookbook.tar.gz");
+        let files = ledger_files();
+        let manifest =
+            BackupManifest::new("cookbook", "ns@v1/cookbook", &test_container("dev"), &files);
+        write_backup(&archive, &manifest, &files).unwrap();
+
+        let (read_manifest, read_files) = read_backup(&archive).unwrap();
+        assert_eq!(read_manifest, manifest);
+        assert_eq!(read_files, files);
+        assert_eq!(manifest.files.len(), 3);
+        assert_eq!(manifest.size(), COOKBOOK_RECORD.len() as u64 + 4);
+    }
+
+    #[test]
+    #[parallel]
+    fn test_archive_must_match_manifest() {
+        let dir = tempdir().unwrap();
+        let archive = dir.path().join("cookbook.tar.gz");
+        let files = ledger_files();
+        let manifest =
+            BackupManifest::new("cookbook", "ns@v1/cookbook", &test_container("dev"), &files);
+
+        let mut changed = files.clone();
+        changed.insert("commit/abc.json".to_string(), b"{ }".to_vec());
+        write_backup(&archive, &manifest, &changed).unwrap();
+        let error = read_backup(&archive).unwrap_err().to_string();
+        assert!(error.contains("commit/abc.json doesn't match"), "{}", error);
+
+        let mut extra = files.clone();
+        extra.insert("txn/x.json".to_string(), Vec::new());
+        write_backup(&archive, &manifest, &extra).unwrap();
+        let error = read_backup(&archive).unwrap_err().to_string();
+        assert!(error.contains("txn/x.json is not listed"), "{}", error);
+
+        let mut escaping = manifest.clone();
+        escaping.ledger_dir = "../outside".to_string();
+        write_backup(&archive, &escaping, &files).unwrap();
+        assert!(read_backup(&archive).is_err());
+
+        let mut other = manifest.clone();
+        other.alias = "recipes".to_string();
+        write_backup(&archive, &other, &files).unwrap();
+        let error = read_backup(&archive).unwrap_err().to_string();
+        assert!(error.contains("no nameservice file"), "{}", error);
+    }
+
+    #[test]
+    #[parallel]
+    fn test_ledger_dir() {
+        assert_eq!(
+            ledger_dir("/opt/fluree-server/data/ns@v1/cookbook/main.json").unwrap(),
+            "ns@v1/cookbook"
+        );
+        assert!(ledger_dir("/opt/fluree-server/data/cookbook.json").is_err());
+        assert!(ledger_dir("/elsewhere/cookbook/main.json").is_err());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_restore_conflicts() {
+        let ledger = |alias: &str, path: &str| LedgerInfo {
+            alias: alias.to_string(),
+            last_commit_time: String::new(),
+            commit_count: 0,
+            size: 0,
+            path: Some(path.to_string()),
+            flakes_count: "0".to_string(),
+            last_index: None,
+            branches: Vec::new(),
+        };
+
+        let other = ledger("recipes", "/opt/fluree-server/data/ns@v1/recipes/main.json");
+        let same_alias = ledger("cookbook", "/opt/fluree-server/data/ns@v1/moved/main.json");
+        let error = check_conflicts(
+            "cookbook",
+            "ns@v1/cookbook",
+            &[other.clone(), same_alias],
+            &test_container("dev"),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("already has a ledger named 'cookbook'"));
+        assert!(check_conflicts(
+            "cookbook",
+            "ns@v1/cookbook",
+            &[other],
+            &test_container("dev")
+        )
+        .is_ok());
+
+        let same_dir = ledger(
+            "renamed",
+            "/opt/fluree-server/data/ns@v1/cookbook/main.json",
+        );
+        assert!(check_conflicts(
+            "cookbook",
+            "ns@v1/cookbook",
+            &[same_dir],
+            &test_container("dev")
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_container_archives() {
+        let files = ledger_files();
+        let archive = pack_directory("ns@v1/cookbook", &files).unwrap();
+        // Docker names entries after the last component of the downloaded path
+        let mut entries: Vec<String> = tar::Archive::new(&archive[..])
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                entry
+                    .unwrap()
+                    .path()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        entries.sort();
+        assert_eq!(entries[0], "ns@v1/cookbook/commit/abc.json");
+
+        let downloaded = pack_directory("cookbook", &files).unwrap();
+        assert_eq!(unpack_directory(&downloaded).unwrap(), files);
+    }
+}
@@ -0,0 +1,270 @@
+//! Fluree HTTP API client.
+//!
+//! Talks to a Fluree server on the port its container publishes, so ledgers
+//! can be managed without knowing how the server stores them on disk.
+
+use reqwest::{Client, Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use super::nameservice::ledger_from_record;
+use crate::docker::LedgerInfo;
+use crate::state::ContainerInfo;
+use crate::{FlockerError, Result};
+
+/// Path listing the nameservice records of all ledgers
+pub const LEDGERS_PATH: &str = "/fluree/ledgers";
+
+/// Path returning the details of one ledger
+pub const LEDGER_INFO_PATH: &str = "/fluree/ledger-info";
+
+/// Path creating a ledger
+pub const CREATE_PATH: &str = "/fluree/create";
+
+/// Path dropping a ledger and its data
+pub const DROP_PATH: &str = "/fluree/drop";
+
+/// Time allowed for a single request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Fluree HTTP API client
+pub struct FlureeClient {
+    client: Client,
+    /// `None` if the server can't be reached from this machine
+    base_url: Option<String>,
+}
+
+impl FlureeClient {
+    /// Create a client for the server at `base_url`, e.g. `http://localhost:8090`
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            base_url: Some(base_url.into().trim_end_matches('/').to_string()),
+        }
+    }
+
+    /// Create a client for the server in a container
+    ///
+    /// `remote_host` is the daemon's host when it runs on another machine.
+    /// If the server's port is only published on that machine's loopback
+    /// address, every request fails as unavailable without being sent.
+    pub fn for_container(container: &ContainerInfo, remote_host: Option<&str>) -> Self {
+        match container.url(remote_host) {
+            Some(url) => Self::new(url),
+            None => Self {
+                base_url: None,
+                ..Self::new("")
+            },
+        }
+    }
+
+    /// List all ledgers
+    pub async fn list_ledgers(&self) -> Result<Vec<LedgerInfo>> {
+        let records: Vec<Value> = self.request(Method::GET, LEDGERS_PATH, None).await?;
+        Ok(records.into_iter().filter_map(ledger_from_record).collect())
+    }
+
+    /// Get the details of a ledger
+    pub async fn ledger_info(&self, alias: &str) -> Result<Value> {
+        self.request(
+            Method::POST,
+            LEDGER_INFO_PATH,
+            Some(json!({ "ledger": alias })),
+        )
+        .await
+    }
+
+    /// Create an empty ledger
+    pub async fn create_ledger(&self, alias: &str) -> Result<()> {
+        let body = json!({ "ledger": alias, "insert": [] });
+        self.request::<Value>(Method::POST, CREATE_PATH, Some(body))
+            .await
+            .map(|_| ())
+    }
+
+    /// Drop a ledger and delete its data
+    pub async fn drop_ledger(&self, alias: &str) -> Result<()> {
+        self.request::<Value>(Method::POST, DROP_PATH, Some(json!({ "ledger": alias })))
+            .await
+            .map(|_| ())
+    }
+
+    /// Send a request and parse the JSON response
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> Result<T> {
+        let Some(base_url) = &self.base_url else {
+            return Err(FlockerError::Fluree {
+                status: None,
+                message:
+                    "The server's port is only published on the Docker host's loopback address"
+                        .to_string(),
+            });
+        };
+        let url = format!("{}{}", base_url, path);
+        let mut request = self.client.request(method, &url);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request.send().await.map_err(|e| FlockerError::Fluree {
+            status: None,
+            message: format!("Failed to reach {}: {}", url, e),
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(FlockerError::Fluree {
+                status: Some(status.as_u16()),
+                message: format!("{} returned {}: {}", path, status, error_message(&body)),
+            });
+        }
+
+        // Some endpoints answer with an empty body
+        let text = response.text().await.unwrap_or_default();
+        let text = if text.trim().is_empty() {
+            "null"
+        } else {
+            &text
+        };
+        serde_json::from_str(text).map_err(|e| FlockerError::Fluree {
+            status: Some(status.as_u16()),
+            message: format!("Invalid response from {}: {}", path, e),
+        })
+    }
+}
+
+/// Whether an error means the server doesn't offer the API, so another way
+/// to reach the ledgers should be tried
+pub fn is_unavailable(error: &FlockerError) -> bool {
+    match error {
+        FlockerError::Fluree { status: None, .. } => true,
+        FlockerError::Fluree {
+            status: Some(status),
+            ..
+        } => matches!(
+            StatusCode::from_u16(*status),
+            Ok(StatusCode::NOT_FOUND
+                | StatusCode::METHOD_NOT_ALLOWED
+                | StatusCode::NOT_IMPLEMENTED)
+        ),
+        _ => false,
+    }
+}
+
+/// The message of a Fluree error response, or the raw body
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|json| {
+            json.get("error")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| body.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use mockito::Matcher;
+    use serial_test::parallel;
+
+    use super::*;
+    use crate::fluree::nameservice::fixtures::COOKBOOK_RECORD;
+
+    #[tokio::test]
+    #[parallel]
+    async fn test_list_ledgers() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", LEDGERS_PATH)
+            .with_body(format!(r#"[{}, {{"unrelated": true}}]"#, COOKBOOK_RECORD))
+            .create_async()
+            .await;
+
+        let ledgers = FlureeClient::new(server.url())
+            .list_ledgers()
+            .await
+            .unwrap();
+        assert_eq!(ledgers.len(), 1);
+        let ledger = &ledgers[0];
+        assert_eq!(ledger.alias, "cookbook");
+        assert_eq!(ledger.commit_count, 5);
+        assert_eq!(ledger.size, 2048);
+        assert_eq!(ledger.flakes_count, "1,200");
+        assert_eq!(ledger.last_index, Some(4));
+        assert!(ledger.path.is_none());
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    #[parallel]
+    async fn test_ledger_info_create_and_drop() {
+        let mut server = mockito::Server::new_async().await;
+        let info = server
+            .mock("POST", LEDGER_INFO_PATH)
+            .match_body(Matcher::Json(json!({ "ledger": "cookbook" })))
+            .with_body(r#"{"ledger": "cookbook", "t": 5}"#)
+            .create_async()
+            .await;
+        let create = server
+            .mock("POST", CREATE_PATH)
+            .match_body(Matcher::PartialJson(json!({ "ledger": "recipes" })))
+            .with_status(201)
+            .with_body(r#"{"ledger": "recipes", "t": 1}"#)
+            .create_async()
+            .await;
+        let dropped = server
+            .mock("POST", DROP_PATH)
+            .match_body(Matcher::Json(json!({ "ledger": "cookbook" })))
+            .create_async()
+            .await;
+
+        let client = FlureeClient::new(format!("{}/", server.url()));
+        let details = client.ledger_info("cookbook").await.unwrap();
+        assert_eq!(details["t"], 5);
+        client.create_ledger("recipes").await.unwrap();
+        client.drop_ledger("cookbook").await.unwrap();
+        info.assert_async().await;
+        create.assert_async().await;
+        dropped.assert_async().await;
+    }
+
+    #[tokio::test]
+    #[parallel]
+    async fn test_error_responses() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", CREATE_PATH)
+            .with_status(409)
+            .with_body(r#"{"error": "Ledger cookbook already exists"}"#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", LEDGERS_PATH)
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let client = FlureeClient::new(server.url());
+        let error = client.create_ledger("cookbook").await.unwrap_err();
+        assert!(error.to_string().contains("Ledger cookbook already exists"));
+        assert!(!is_unavailable(&error));
+
+        // Older servers don't have the endpoint
+        let error = client.list_ledgers().await.unwrap_err();
+        assert!(is_unavailable(&error));
+
+        let offline = FlureeClient::new("http://127.0.0.1:1");
+        assert!(is_unavailable(
+            &offline.drop_ledger("cookbook").await.unwrap_err()
+        ));
+    }
+}
@@ -0,0 +1,106 @@
+//! Copying ledgers between containers.
+//!
+//! The ledger's directory is read from the source container and written to
+//! the target the same way backups are, through host data directories or the
+//! Docker archive API. Copies keep their alias: commits refer to the ledger's
+//! alias and to the addresses of earlier commits, so renaming a copy would
+//! need the server to rewrite its history.
+
+use super::backup::{list_on_disk, read_ledger, write_ledger};
+use super::ledgers::LedgerOperations;
+use crate::docker::{DockerOperations, LedgerInfo};
+use crate::state::ContainerInfo;
+use crate::{ContainerStatus, FlockerError, Result};
+
+/// Copy a ledger from one container to another
+///
+/// Returns the copied ledger as the target lists it, once its branches show
+/// the same commits as the source. A running target is asked through its
+/// HTTP API, so a ledger its server can't load isn't reported as copied.
+pub async fn copy_ledger(
+    docker: &impl DockerOperations,
+    source: &ContainerInfo,
+    alias: &str,
+    target: &ContainerInfo,
+) -> Result<LedgerInfo> {
+    if source.id == target.id {
+        return Err(FlockerError::Transfer(format!(
+            "Ledger '{}' can't be copied into the container it is in",
+            alias
+        )));
+    }
+    let (ledger, ledger_dir, files) = read_ledger(docker, source, alias).await?;
+    write_ledger(docker, target, alias, &ledger_dir, &files).await?;
+
+    let listed = match docker.get_container_status(&target.id).await? {
+        ContainerStatus::Running { .. } => LedgerOperations::new(docker, target).list().await?,
+        _ => list_on_disk(docker, target).await?,
+    };
+    let copied = listed
+        .into_iter()
+        .find(|copied| copied.alias == alias)
+        .ok_or_else(|| {
+            FlockerError::Transfer(format!(
+                "Ledger '{}' was copied but '{}' doesn't list it",
+                alias, target.name
+            ))
+        })?;
+    check_commits(&ledger, &copied)?;
+    Ok(copied)
+}
+
+/// Fail unless every branch of the copy is at the same commit as the source
+fn check_commits(source: &LedgerInfo, copied: &LedgerInfo) -> Result<()> {
+    for branch in &source.branches {
+        let copied_t = copied
+            .branches
+            .iter()
+            .find(|copied| copied.name == branch.name)
+            .map(|copied| copied.commit_count);
+        if copied_t != Some(branch.commit_count) {
+            return Err(FlockerError::Transfer(format!(
+                "Branch {} of the copied ledger '{}' is at commit {}, expected {}",
+                branch.name,
+                copied.alias,
+                copied_t.map_or_else(|| "none".to_string(), |t| t.to_string()),
+                branch.commit_count
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+
+    use super::*;
+    use crate::docker::BranchInfo;
+
+    #[test]
+    #[parallel]
+    fn test_check_commits() {
+        let ledger = |t: u64| LedgerInfo {
+            alias: "cookbook".to_string(),
+            last_commit_time: String::new(),
+            commit_count: t,
+            size: 0,
+            path: None,
+            flakes_count: "0".to_string(),
+            last_index: None,
+            branches: vec![BranchInfo {
+                name: "main".to_string(),
+                last_commit_time: String::new(),
+                commit_count: t,
+                size: 0,
+                flakes_count: "0".to_string(),
+                last_index: None,
+            }],
+        };
+        assert!(check_commits(&ledger(5), &ledger(5)).is_ok());
+        let error = check_commits(&ledger(5), &ledger(4))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("is at commit 4, expected 5"), "{}", error);
+    }
+}
@@ -0,0 +1,179 @@
+//! Ledgers read from a data directory mounted from the host.
+//!
+//! A container created with a data directory keeps its nameservice files on
+//! the host, so they can be read without running commands in the container,
+//! and while the container is stopped.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::nameservice::ledger_from_file;
+use crate::docker::{LedgerInfo, CONTAINER_DATA_DIR};
+use crate::state::ContainerInfo;
+use crate::{FlockerError, Result};
+
+/// Directories holding commits and indexes rather than nameservice files
+const SKIPPED_DIRS: [&str; 3] = ["commit", "index", "txn"];
+
+/// Reads ledgers from a host data directory
+#[derive(Debug, Clone)]
+pub struct HostLedgerReader {
+    data_dir: PathBuf,
+}
+
+impl HostLedgerReader {
+    /// Read ledgers from `data_dir`
+    pub fn new(data_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            data_dir: data_dir.into(),
+        }
+    }
+
+    /// Read ledgers from the data directory mounted into a container
+    ///
+    /// Returns `None` if the container has no data directory, or it isn't on
+    /// this machine.
+    pub fn for_container(container: &ContainerInfo) -> Option<Self> {
+        container
+            .data_dir
+            .as_ref()
+            .map(|data_dir| &data_dir.absolute_path)
+            .filter(|path| path.is_dir())
+            .map(Self::new)
+    }
+
+    /// List all ledgers
+    ///
+    /// Ledger paths are given as seen inside the container.
+    pub fn list(&self) -> Result<Vec<LedgerInfo>> {
+        let mut ledgers = Vec::new();
+        for file in self.nameservice_files()? {
+            match fs::read_to_string(&file) {
+                Ok(content) => {
+                    ledgers.extend(ledger_from_file(&content, &self.container_path(&file)))
+                }
+                Err(e) => tracing::warn!("Failed to read {}: {}", file.display(), e),
+            }
+        }
+        Ok(ledgers)
+    }
+
+    /// Get the nameservice record of a ledger as pretty-printed JSON
+    pub fn details(&self, alias: &str) -> Result<String> {
+        let json = self
+            .nameservice_files()?
+            .iter()
+            .filter_map(|file| read_json(file))
+            .find(|json| json.get("ledgerAlias").and_then(|v| v.as_str()) == Some(alias))
+            .ok_or_else(|| FlockerError::Docker(format!("Ledger '{}' not found", alias)))?;
+        serde_json::to_string_pretty(&json)
+            .map_err(|e| FlockerError::Docker(format!("Failed to format JSON: {}", e)))
+    }
+
+    /// The data directory on the host
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// All JSON files outside commit and index directories
+    fn nameservice_files(&self) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        let mut dirs = vec![self.data_dir.clone()];
+        while let Some(dir) = dirs.pop() {
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    let skipped = path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| SKIPPED_DIRS.contains(&name));
+                    if !skipped {
+                        dirs.push(path);
+                    }
+                } else if path.extension().is_some_and(|ext| ext == "json") {
+                    files.push(path);
+                }
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    /// Where a file in the data directory is found inside the container
+    fn container_path(&self, file: &Path) -> String {
+        let relative = file.strip_prefix(&self.data_dir).unwrap_or(file);
+        let components: Vec<_> = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect();
+        format!("{}/{}", CONTAINER_DATA_DIR, components.join("/"))
+    }
+}
+
+/// Parse a JSON file, skipping files that can't be read or parsed
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| tracing::debug!("Failed to read {}: {}", path.display(), e))
+        .ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::fluree::nameservice::fixtures::COOKBOOK_RECORD;
+    use crate::state::test_container;
+
+    #[test]
+    #[parallel]
+    fn test_read_ledgers_from_host() {
+        let dir = tempdir().unwrap();
+        let ledger_dir = dir.path().join("ns@v1").join("cookbook");
+        fs::create_dir_all(ledger_dir.join("commit")).unwrap();
+        fs::write(ledger_dir.join("main.json"), COOKBOOK_RECORD).unwrap();
+        // Commit files are JSON too, but not nameservice records
+        fs::write(
+            ledger_dir.join("commit").join("abc.json"),
+            r#"{"ledgerAlias": "commit"}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("broken.json"), "{").unwrap();
+
+        let reader = HostLedgerReader::new(dir.path());
+        let ledgers = reader.list().unwrap();
+        assert_eq!(ledgers.len(), 1);
+        assert_eq!(ledgers[0].alias, "cookbook");
+        assert_eq!(ledgers[0].commit_count, 5);
+        assert_eq!(
+            ledgers[0].path.as_deref(),
+            Some("/opt/fluree-server/data/ns@v1/cookbook/main.json")
+        );
+
+        let details = reader.details("cookbook").unwrap();
+        assert!(details.contains("\"ledgerAlias\": \"cookbook\""));
+        assert!(reader.details("commit").is_err());
+    }
+
+    #[test]
+    #[parallel]
+    fn test_reader_for_container() {
+        let dir = tempdir().unwrap();
+        let mut container = test_container("dev");
+        assert!(HostLedgerReader::for_container(&container).is_none());
+
+        container.data_dir = Some(crate::state::DataDirConfig::new(
+            dir.path().join("missing"),
+            None,
+        ));
+        assert!(HostLedgerReader::for_container(&container).is_none());
+
+        container.data_dir = Some(crate::state::DataDirConfig::new(
+            dir.path().to_path_buf(),
+            None,
+        ));
+        assert!(HostLedgerReader::for_container(&container).is_some());
+    }
+}
@@ -0,0 +1,105 @@
+//! Ledger operations with a fallback for servers without the HTTP API.
+//!
+//! The HTTP API is tried first. When the server can't be reached on its
+//! published port, or doesn't offer an endpoint, ledgers are read from the
+//! container's data directory: directly from the host if it is mounted from
+//! there, otherwise with commands run inside the container.
+
+use super::client::{is_unavailable, FlureeClient};
+use super::host::HostLedgerReader;
+use crate::docker::{DockerOperations, LedgerInfo};
+use crate::state::ContainerInfo;
+use crate::{FlockerError, Result};
+
+/// Ledger operations on a running container
+pub struct LedgerOperations<'a, D: DockerOperations> {
+    docker: &'a D,
+    container_id: String,
+    client: FlureeClient,
+    host: Option<HostLedgerReader>,
+}
+
+impl<'a, D: DockerOperations> LedgerOperations<'a, D> {
+    /// Manage the ledgers of the server in `container`
+    pub fn new(docker: &'a D, container: &ContainerInfo) -> Self {
+        Self {
+            docker,
+            container_id: container.id.clone(),
+            client: FlureeClient::for_container(container, docker.remote_host().as_deref()),
+            host: HostLedgerReader::for_container(container),
+        }
+    }
+
+    /// Use a different client, e.g. for a server reached another way
+    pub fn with_client(mut self, client: FlureeClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// List all ledgers
+    pub async fn list(&self) -> Result<Vec<LedgerInfo>> {
+        match self.client.list_ledgers().await {
+            Err(e) if is_unavailable(&e) => {
+                tracing::debug!("Listing ledgers from disk: {}", e);
+                self.list_on_disk().await
+            }
+            result => result,
+        }
+    }
+
+    /// Get the details of a ledger as pretty-printed JSON
+    pub async fn details(&self, alias: &str) -> Result<String> {
+        match self.client.ledger_info(alias).await {
+            Ok(info) => serde_json::to_string_pretty(&info)
+                .map_err(|e| FlockerError::Docker(format!("Failed to format JSON: {}", e))),
+            Err(e) if is_unavailable(&e) => {
+                tracing::debug!("Reading ledger {} from disk: {}", alias, e);
+                if let Some(host) = &self.host {
+                    return host.details(alias);
+                }
+                let path = self.path_on_disk(alias).await?;
+                self.docker
+                    .get_ledger_details(&self.container_id, &path)
+                    .await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Create an empty ledger
+    ///
+    /// This needs the HTTP API; there is no fallback.
+    pub async fn create(&self, alias: &str) -> Result<()> {
+        self.client.create_ledger(alias).await
+    }
+
+    /// Drop a ledger and delete its data
+    pub async fn drop_ledger(&self, alias: &str) -> Result<()> {
+        match self.client.drop_ledger(alias).await {
+            Err(e) if is_unavailable(&e) => {
+                tracing::debug!("Deleting ledger {} from disk: {}", alias, e);
+                let path = self.path_on_disk(alias).await?;
+                self.docker.delete_ledger(&self.container_id, &path).await
+            }
+            result => result,
+        }
+    }
+
+    /// List ledgers from the data directory
+    async fn list_on_disk(&self) -> Result<Vec<LedgerInfo>> {
+        match &self.host {
+            Some(host) => host.list(),
+            None => self.docker.list_ledgers(&self.container_id).await,
+        }
+    }
+
+    /// The nameservice file of a ledger in the container
+    async fn path_on_disk(&self, alias: &str) -> Result<String> {
+        self.list_on_disk()
+            .await?
+            .into_iter()
+            .find(|ledger| ledger.alias == alias)
+            .and_then(|ledger| ledger.path)
+            .ok_or_else(|| FlockerError::Docker(format!("Ledger '{}' not found", alias)))
+    }
+}
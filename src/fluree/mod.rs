@@ -0,0 +1,22 @@
+//! Fluree server interactions.
+//!
+//! This module provides functionality for:
+//! - Parsing the nameservice records that describe ledgers and their branches
+//! - Listing, inspecting, creating and dropping ledgers over the HTTP API
+//! - Falling back to reading ledgers from the container's data directory
+//!   when the API can't be used
+//! - Reading ledgers from a data directory mounted from the host, also while
+//!   the container is stopped
+//! - Backing up ledgers to archives and restoring them
+//! - Copying ledgers between containers
+
+pub mod backup;
+mod client;
+pub mod copy;
+mod host;
+mod ledgers;
+pub mod nameservice;
+
+pub use client::{FlureeClient, CREATE_PATH, DROP_PATH, LEDGERS_PATH, LEDGER_INFO_PATH};
+pub use host::HostLedgerReader;
+pub use ledgers::LedgerOperations;
@@ -0,0 +1,225 @@
+//! Fluree nameservice records.
+//!
+//! A nameservice record names a ledger and points every branch at its latest
+//! commit. Records are stored as JSON files in the server's data directory
+//! and returned by the HTTP API in the same form.
+
+use num_format::{Locale, ToFormattedString};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::docker::{BranchInfo, LedgerInfo};
+
+/// Nameservice record of a ledger
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NameserviceRecord {
+    pub ledger_alias: String,
+    /// Branch used when none is given, usually `main`
+    #[serde(default)]
+    pub default_branch: Option<String>,
+    pub branches: Vec<BranchRecord>,
+}
+
+/// A branch and its latest commit
+#[derive(Debug, Clone, Deserialize)]
+pub struct BranchRecord {
+    pub name: String,
+    pub commit: CommitRecord,
+}
+
+/// Summary of a commit
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitRecord {
+    pub time: String,
+    pub data: CommitData,
+    /// Latest index, if the branch has been indexed
+    #[serde(default)]
+    pub index: Option<IndexRecord>,
+}
+
+/// Size of the ledger as of a commit
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitData {
+    pub t: u64,
+    pub size: u64,
+    pub flakes: u64,
+}
+
+/// Latest index of a branch
+#[derive(Debug, Clone, Deserialize)]
+pub struct IndexRecord {
+    pub data: IndexData,
+}
+
+/// The commit an index covers
+#[derive(Debug, Clone, Deserialize)]
+pub struct IndexData {
+    pub t: u64,
+}
+
+impl NameserviceRecord {
+    /// Summarize the record, with the default branch first
+    ///
+    /// The ledger's own statistics are those of its default branch, or of
+    /// the first branch if the record doesn't name one.
+    pub fn into_ledger_info(self, path: Option<&str>) -> LedgerInfo {
+        let mut branches: Vec<BranchInfo> = self
+            .branches
+            .into_iter()
+            .map(|branch| BranchInfo {
+                name: branch.name,
+                last_commit_time: branch.commit.time,
+                commit_count: branch.commit.data.t,
+                size: branch.commit.data.size,
+                flakes_count: branch.commit.data.flakes.to_formatted_string(&Locale::en),
+                last_index: branch.commit.index.map(|index| index.data.t),
+            })
+            .collect();
+        if let Some(default) = &self.default_branch {
+            if let Some(i) = branches.iter().position(|branch| &branch.name == default) {
+                branches[..=i].rotate_right(1);
+            }
+        }
+
+        let main = branches.first();
+        LedgerInfo {
+            alias: self.ledger_alias,
+            last_commit_time: main
+                .map(|branch| branch.last_commit_time.clone())
+                .unwrap_or_default(),
+            commit_count: main.map_or(0, |branch| branch.commit_count),
+            size: main.map_or(0, |branch| branch.size),
+            path: path.map(str::to_string),
+            flakes_count: main
+                .map(|branch| branch.flakes_count.clone())
+                .unwrap_or_else(|| "0".to_string()),
+            last_index: main.and_then(|branch| branch.last_index),
+            branches,
+        }
+    }
+}
+
+/// Read the ledger in a nameservice file
+///
+/// Files that aren't valid records are reported and skipped.
+pub fn ledger_from_file(content: &str, path: &str) -> Option<LedgerInfo> {
+    match serde_json::from_str::<NameserviceRecord>(content) {
+        Ok(record) => Some(record.into_ledger_info(Some(path))),
+        Err(e) => {
+            tracing::warn!("Skipping invalid nameservice file {}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Read the ledger in a nameservice record returned by the HTTP API
+///
+/// Records that aren't valid are reported and skipped.
+pub fn ledger_from_record(record: Value) -> Option<LedgerInfo> {
+    let alias = record
+        .get("ledgerAlias")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown")
+        .to_string();
+    match serde_json::from_value::<NameserviceRecord>(record) {
+        Ok(record) => Some(record.into_ledger_info(None)),
+        Err(e) => {
+            tracing::warn!("Skipping invalid nameservice record for {}: {}", alias, e);
+            None
+        }
+    }
+}
+
+/// Fixtures shared by the tests of modules that read ledgers
+#[cfg(test)]
+pub(crate) mod fixtures {
+    /// Nameservice record of a `cookbook` ledger with an indexed `main` branch
+    pub const COOKBOOK_RECORD: &str = r#"{
+        "ledgerAlias": "cookbook",
+        "branches": [{
+            "name": "main",
+            "commit": {
+                "address": "fluree:file://cookbook/main/commit/abc.json",
+                "time": "2024-02-08T21:56:23Z",
+                "data": { "t": 5, "size": 2048, "flakes": 1200 },
+                "index": { "data": { "t": 4 } }
+            }
+        }]
+    }"#;
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::parallel;
+
+    use super::*;
+
+    const RECORD: &str = r#"{
+        "@context": "https://ns.flur.ee/ledger/v1",
+        "ledgerAlias": "cookbook",
+        "defaultBranch": "main",
+        "branches": [
+            {
+                "name": "experiment",
+                "commit": {
+                    "time": "2024-02-09T10:00:00Z",
+                    "data": { "t": 7, "size": 4096, "flakes": 2500 }
+                }
+            },
+            {
+                "name": "main",
+                "commit": {
+                    "time": "2024-02-08T21:56:23Z",
+                    "data": { "t": 5, "size": 2048, "flakes": 1200 },
+                    "index": { "data": { "t": 4 } }
+                }
+            }
+        ]
+    }"#;
+
+    #[test]
+    #[parallel]
+    fn test_every_branch_is_listed() {
+        let ledger = ledger_from_file(RECORD, "/opt/fluree-server/data/ns@v1/cookbook.json")
+            .expect("valid record");
+        assert_eq!(ledger.alias, "cookbook");
+        assert_eq!(
+            ledger.path.as_deref(),
+            Some("/opt/fluree-server/data/ns@v1/cookbook.json")
+        );
+
+        // The default branch comes first and is summarized as the ledger
+        let names: Vec<_> = ledger.branches.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, ["main", "experiment"]);
+        assert_eq!(ledger.commit_count, 5);
+        assert_eq!(ledger.size, 2048);
+        assert_eq!(ledger.flakes_count, "1,200");
+        assert_eq!(ledger.last_index, Some(4));
+
+        let experiment = &ledger.branches[1];
+        assert_eq!(experiment.commit_count, 7);
+        assert_eq!(experiment.flakes_count, "2,500");
+        assert_eq!(experiment.last_index, None);
+    }
+
+    #[test]
+    #[parallel]
+    fn test_invalid_records_are_skipped() {
+        // Missing commit statistics are errors rather than zeros
+        let missing_size = r#"{
+            "ledgerAlias": "cookbook",
+            "branches": [{
+                "name": "main",
+                "commit": { "time": "2024-02-08T21:56:23Z", "data": { "t": 5, "flakes": 1 } }
+            }]
+        }"#;
+        assert!(ledger_from_file(missing_size, "cookbook.json").is_none());
+        assert!(serde_json::from_str::<NameserviceRecord>(missing_size)
+            .unwrap_err()
+            .to_string()
+            .contains("size"));
+        assert!(ledger_from_file("{", "broken.json").is_none());
+        assert!(ledger_from_record(serde_json::json!({ "unrelated": true })).is_none());
+    }
+}
@@ -5,21 +5,37 @@
 //! - Configure and run Fluree containers
 //! - Monitor container status and statistics
 //! - Manage container lifecycle
+//! - Manage ledgers through the Fluree HTTP API
 
 pub mod cli;
 pub(crate) mod config;
 pub mod docker;
 pub(crate) mod error;
+pub mod fluree;
 pub mod state;
 
 // Re-export commonly used types
 pub use cli::{Cli, CliState};
 pub use docker::manager::{DockerManager, DockerOperations};
 use error::FlockerError;
+use serde::Serialize;
 pub type Result<T> = std::result::Result<T, FlockerError>;
 
+/// Result of a container's health check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Health {
+    /// The health check hasn't passed yet
+    Starting,
+    /// The last health check passed
+    Healthy,
+    /// The health check failed too many times in a row
+    Unhealthy,
+}
+
 /// Container status information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
 pub enum ContainerStatus {
     /// Container is running
     Running {
@@ -35,8 +51,29 @@ pub enum ContainerStatus {
         config_dir: Option<String>,
         /// Last start time
         started_at: Option<String>,
+        /// Health check result, if the image defines a health check
+        #[serde(skip_serializing_if = "Option::is_none")]
+        health: Option<Health>,
+    },
+    /// Container processes are paused
+    Paused {
+        /// Container ID
+        id: String,
+        /// Container name
+        name: String,
+        /// Last start time
+        started_at: Option<String>,
     },
-    /// Container exists but is not running
+    /// Container is being restarted by its restart policy
+    Restarting {
+        /// Container ID
+        id: String,
+        /// Container name
+        name: String,
+        /// Number of restarts so far
+        restart_count: i64,
+    },
+    /// Container was created but hasn't run yet
     Stopped {
         /// Container ID
         id: String,
@@ -45,6 +82,28 @@ pub enum ContainerStatus {
         /// Last start time before stopping
         last_start: Option<String>,
     },
+    /// Container ran and exited
+    Exited {
+        /// Container ID
+        id: String,
+        /// Container name
+        name: String,
+        /// Exit code of the last run
+        code: i64,
+        /// Whether the container was killed for running out of memory
+        oom_killed: bool,
+        /// When the container exited
+        finished_at: Option<String>,
+        /// Last start time before exiting
+        last_start: Option<String>,
+    },
+    /// Container couldn't be stopped or removed and is defunct
+    Dead {
+        /// Container ID
+        id: String,
+        /// Container name
+        name: String,
+    },
     /// No container found
     NotFound,
 }
@@ -60,9 +119,52 @@ impl ContainerStatus {
         //     ContainerStatus::NotFound => style(truncate_fn("not found")).red(),
         // }
         match self {
+            ContainerStatus::Running {
+                health: Some(health),
+                ..
+            } => truncate_fn(&format!("running ({})", health.label())),
             ContainerStatus::Running { .. } => truncate_fn("running"),
+            ContainerStatus::Paused { .. } => truncate_fn("paused"),
+            ContainerStatus::Restarting { .. } => truncate_fn("restarting"),
             ContainerStatus::Stopped { .. } => truncate_fn("stopped"),
+            ContainerStatus::Exited {
+                code,
+                oom_killed: true,
+                ..
+            } => truncate_fn(&format!("exited ({}, out of memory)", code)),
+            ContainerStatus::Exited { code, .. } => truncate_fn(&format!("exited ({})", code)),
+            ContainerStatus::Dead { .. } => truncate_fn("dead"),
             ContainerStatus::NotFound => truncate_fn("not found"),
         }
     }
+
+    /// Whether the container stopped because of a failure
+    ///
+    /// A container stopped with `docker stop` exits with 143 (SIGTERM) or
+    /// 137 (SIGKILL after the stop timeout), which isn't treated as a failure
+    /// unless the kernel killed it for running out of memory.
+    pub fn is_failure(&self) -> bool {
+        match self {
+            ContainerStatus::Running {
+                health: Some(Health::Unhealthy),
+                ..
+            } => true,
+            ContainerStatus::Exited {
+                code, oom_killed, ..
+            } => *oom_killed || !matches!(code, 0 | 137 | 143),
+            ContainerStatus::Dead { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+impl Health {
+    /// Lowercase name of the health state
+    pub fn label(&self) -> &'static str {
+        match self {
+            Health::Starting => "starting",
+            Health::Healthy => "healthy",
+            Health::Unhealthy => "unhealthy",
+        }
+    }
 }
@@ -6,8 +6,9 @@
 use clap::Parser;
 use console::style;
 use flocker::{
-    cli::{Cli, CliState},
+    cli::{args::WaitArgs, commands, output::OutputFormat, Cli, CliState},
     docker::{DockerManager, DockerOperations},
+    state::ContainerInfo,
 };
 use tracing::debug;
 
@@ -38,10 +39,33 @@ async fn main() -> flocker::Result<()> {
     debug!("Initializing Docker manager");
 
     // Create Docker manager
-    let docker = DockerManager::new().await?;
+    let docker =
+        match DockerManager::connect(cli_arg_state.host.as_deref(), cli_arg_state.runtime).await {
+            Ok(docker) => docker,
+            Err(e) => {
+                eprintln!("{} {}", style("ERROR:").red(), e);
+                std::process::exit(1);
+            }
+        };
+
+    // Run a single command non-interactively if one was given
+    if let Some(command) = cli_arg_state.command {
+        if let Err(e) = commands::run(
+            command,
+            cli_arg_state.output,
+            cli_arg_state.repository.as_deref(),
+            &docker,
+        )
+        .await
+        {
+            eprintln!("{} {}", style("ERROR:").red(), e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
 
     // Create CLI state
-    let mut cli = CliState::new();
+    let mut cli = CliState::new().with_repository(cli_arg_state.repository);
     debug!("CLI state initialized");
 
     // Main application loop
@@ -55,19 +79,35 @@ async fn main() -> flocker::Result<()> {
         // Create new container
         debug!("Creating new container");
         let (image, config, name) = cli.get_config(&docker).await?;
+        let since = chrono::Utc::now().timestamp();
         let container = match docker
             .create_and_start_container(&image.tag, &config.clone().into_docker_config(), &name)
             .await
         {
-            Ok(container) => container,
+            Ok(container) => ContainerInfo {
+                autostart: config.autostart,
+                ..container
+            },
             Err(e) => {
                 println!("{} {}", style("ERROR:").red(), e);
                 continue;
             }
         };
 
-        // Add container to state and display success
+        // Add container to state, wait for Fluree and display success
         cli.add_container(container.clone())?;
-        cli.display_success(&container);
+        if let Err(e) = commands::wait_for_ready(
+            &docker,
+            &container,
+            WaitArgs::default(),
+            since,
+            OutputFormat::Table,
+        )
+        .await
+        {
+            println!("{} {}", style("ERROR:").red(), e);
+            continue;
+        }
+        cli.display_success(&container, docker.remote_host().as_deref());
     }
 }
@@ -8,6 +8,10 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::docker::{
+    runtime::validate_repository, CommandOptions, NetworkAttachment, NetworkOptions,
+    ResourceLimits, RestartPolicy, DEFAULT_IMAGE_REPOSITORY,
+};
 use crate::error::FlockerError;
 use crate::Result;
 
@@ -82,6 +86,30 @@ pub struct ContainerInfo {
     pub image_tag: String,
     /// Last start time
     pub last_start: Option<String>,
+    /// Memory, CPU and Java heap limits the container was created with
+    #[serde(default, skip_serializing_if = "ResourceLimits::is_empty")]
+    pub resources: ResourceLimits,
+    /// Environment, server arguments and entrypoint the container was created with
+    #[serde(default, skip_serializing_if = "CommandOptions::is_empty")]
+    pub command: CommandOptions,
+    /// Restart policy the container was created with
+    #[serde(default, skip_serializing_if = "RestartPolicy::is_no")]
+    pub restart_policy: RestartPolicy,
+    /// Whether `flocker up` starts the container
+    #[serde(default)]
+    pub autostart: bool,
+    /// Bind address, Docker networks and extra ports of the container
+    #[serde(default = "legacy_network")]
+    pub network: NetworkOptions,
+}
+
+/// Network settings of containers recorded before they were configurable,
+/// which published their port on all interfaces
+fn legacy_network() -> NetworkOptions {
+    NetworkOptions {
+        bind_address: std::net::Ipv4Addr::UNSPECIFIED.into(),
+        ..Default::default()
+    }
 }
 
 impl ContainerInfo {
@@ -103,8 +131,47 @@ impl ContainerInfo {
             config_dir,
             image_tag,
             last_start,
+            resources: ResourceLimits::default(),
+            command: CommandOptions::default(),
+            restart_policy: RestartPolicy::default(),
+            autostart: false,
+            network: NetworkOptions::default(),
         }
     }
+
+    /// Record the resource limits the container was created with
+    pub fn with_resources(mut self, resources: ResourceLimits) -> Self {
+        self.resources = resources;
+        self
+    }
+
+    /// Record the environment, arguments and entrypoint the container was created with
+    pub fn with_command(mut self, command: CommandOptions) -> Self {
+        self.command = command;
+        self
+    }
+
+    /// Record the restart policy the container was created with
+    pub fn with_restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        self.restart_policy = restart_policy;
+        self
+    }
+
+    /// Record the bind address, networks and extra ports the container was created with
+    pub fn with_network(mut self, network: NetworkOptions) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Base URL of the Fluree server, as reached from this machine
+    ///
+    /// `remote_host` is the daemon's host when it runs on another machine.
+    /// Returns `None` if the server can't be reached from here.
+    pub fn url(&self, remote_host: Option<&str>) -> Option<String> {
+        self.network
+            .host(remote_host)
+            .map(|host| format!("http://{}:{}", host, self.port))
+    }
 }
 
 /// Persistent state for the Flocker application
@@ -112,6 +179,9 @@ impl ContainerInfo {
 pub struct State {
     /// Known containers, mapped by ID
     pub containers: std::collections::HashMap<String, ContainerInfo>,
+    /// Default image repository for new containers, if not `fluree/server`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_repository: Option<String>,
 }
 
 impl State {
@@ -218,6 +288,27 @@ impl State {
         self.containers.get(container_id)
     }
 
+    /// Find a container by exact name, falling back to a full or prefix ID match
+    pub fn find_container(&self, name_or_id: &str) -> Option<&ContainerInfo> {
+        if name_or_id.is_empty() {
+            return None;
+        }
+        self.containers
+            .values()
+            .find(|c| c.name == name_or_id)
+            .or_else(|| self.containers.get(name_or_id))
+            .or_else(|| {
+                let mut matches = self
+                    .containers
+                    .values()
+                    .filter(|c| c.id.starts_with(name_or_id));
+                match (matches.next(), matches.next()) {
+                    (Some(container), None) => Some(container),
+                    _ => None,
+                }
+            })
+    }
+
     /// Get all known containers
     pub fn get_containers(&self) -> Vec<&ContainerInfo> {
         let mut containers: Vec<&ContainerInfo> = self.containers.values().collect();
@@ -236,6 +327,35 @@ impl State {
         self.save()
     }
 
+    /// Set whether `flocker up` starts a container
+    pub fn set_autostart(&mut self, container_id: &str, autostart: bool) -> Result<()> {
+        let container = self.containers.get_mut(container_id).ok_or_else(|| {
+            FlockerError::Config(format!("Container {} not found in state", container_id))
+        })?;
+        container.autostart = autostart;
+        self.save()
+    }
+
+    /// Record that a container was attached to a network, replacing the
+    /// aliases of an earlier attachment to the same network
+    pub fn add_network(&mut self, container_id: &str, attachment: NetworkAttachment) -> Result<()> {
+        let container = self.containers.get_mut(container_id).ok_or_else(|| {
+            FlockerError::Config(format!("Container {} not found in state", container_id))
+        })?;
+        let networks = &mut container.network.networks;
+        networks.retain(|network| network.name != attachment.name);
+        networks.push(attachment);
+        self.save()
+    }
+
+    /// Get all containers that `flocker up` starts
+    pub fn autostart_containers(&self) -> Vec<&ContainerInfo> {
+        let mut containers: Vec<&ContainerInfo> =
+            self.containers.values().filter(|c| c.autostart).collect();
+        containers.sort_by(|a, b| a.name.cmp(&b.name));
+        containers
+    }
+
     /// Update container status
     pub fn update_container_status(
         &mut self,
@@ -260,11 +380,27 @@ impl State {
             .unwrap_or((8090, None))
     }
 
-    /// Get the path to the config file
-    fn config_path() -> Result<PathBuf> {
+    /// Get the default image repository for new containers
+    pub fn image_repository(&self) -> &str {
+        self.image_repository
+            .as_deref()
+            .unwrap_or(DEFAULT_IMAGE_REPOSITORY)
+    }
+
+    /// Set the default image repository, or reset it to `fluree/server`
+    pub fn set_image_repository(&mut self, repository: Option<String>) -> Result<()> {
+        if let Some(repository) = &repository {
+            validate_repository(repository)?;
+        }
+        self.image_repository = repository.filter(|r| r != DEFAULT_IMAGE_REPOSITORY);
+        self.save()
+    }
+
+    /// Get the flocker config directory
+    pub fn config_dir() -> Result<PathBuf> {
         // Check for test environment variable first
         if let Ok(test_config_dir) = std::env::var("XDG_CONFIG_HOME") {
-            return Ok(PathBuf::from(test_config_dir).join("config.json"));
+            return Ok(PathBuf::from(test_config_dir));
         }
 
         // Use default config path for normal operation
@@ -272,8 +408,27 @@ impl State {
             FlockerError::Config("Failed to determine config directory".to_string())
         })?;
 
-        Ok(proj_dirs.config_dir().join("config.json"))
+        Ok(proj_dirs.config_dir().to_path_buf())
     }
+
+    /// Get the path to the config file
+    fn config_path() -> Result<PathBuf> {
+        Ok(Self::config_dir()?.join("config.json"))
+    }
+}
+
+/// A container on port 8090 without data or config directories, with its
+/// name as its ID
+#[cfg(test)]
+pub(crate) fn test_container(name: &str) -> ContainerInfo {
+    ContainerInfo::new(
+        name.to_string(),
+        name.to_string(),
+        8090,
+        None,
+        None,
+        "latest".to_string(),
+    )
 }
 
 #[cfg(test)]
@@ -331,6 +486,32 @@ mod tests {
         drop(temp_dir);
     }
 
+    #[test]
+    #[serial]
+    fn test_image_repository_setting() {
+        let temp_dir = tempdir().unwrap();
+        env::set_var("XDG_CONFIG_HOME", temp_dir.path());
+        State::clear().unwrap();
+
+        let mut state = State::default();
+        assert_eq!(state.image_repository(), "fluree/server");
+
+        state
+            .set_image_repository(Some("registry.internal/team/fluree".to_string()))
+            .unwrap();
+        assert_eq!(
+            State::load().unwrap().image_repository(),
+            "registry.internal/team/fluree"
+        );
+
+        assert!(state
+            .set_image_repository(Some("fluree/server:latest".to_string()))
+            .is_err());
+
+        state.set_image_repository(None).unwrap();
+        assert!(State::load().unwrap().image_repository.is_none());
+    }
+
     #[test]
     #[parallel]
     fn test_container_management() {
@@ -370,6 +551,90 @@ mod tests {
         assert!(state.remove_container("test1").is_err());
     }
 
+    #[test]
+    #[serial]
+    fn test_autostart() {
+        let temp_dir = tempdir().unwrap();
+        env::set_var("XDG_CONFIG_HOME", temp_dir.path());
+        State::clear().unwrap();
+
+        let mut state = State::default();
+        for (id, name) in [("b1", "beta"), ("a1", "alpha"), ("c1", "gamma")] {
+            let container = ContainerInfo::new(
+                id.to_string(),
+                name.to_string(),
+                8090,
+                None,
+                None,
+                "latest".to_string(),
+            );
+            state.add_container(container).unwrap();
+        }
+        state.set_autostart("b1", true).unwrap();
+        state.set_autostart("a1", true).unwrap();
+        assert!(state.set_autostart("missing", true).is_err());
+
+        let state = State::load().unwrap();
+        let names: Vec<&str> = state
+            .autostart_containers()
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["alpha", "beta"]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_container_networks() {
+        let temp_dir = tempdir().unwrap();
+        env::set_var("XDG_CONFIG_HOME", temp_dir.path());
+        State::clear().unwrap();
+
+        // Containers saved before networking was configurable listened on all interfaces
+        let legacy: ContainerInfo = serde_json::from_value(serde_json::json!({
+            "id": "old1",
+            "name": "old",
+            "port": 8090,
+            "data_dir": null,
+            "config_dir": null,
+            "image_tag": "latest",
+            "last_start": null
+        }))
+        .unwrap();
+        assert!(legacy.network.bind_address.is_unspecified());
+        assert_eq!(legacy.url(None).as_deref(), Some("http://localhost:8090"));
+        assert_eq!(
+            legacy.url(Some("build-box")).as_deref(),
+            Some("http://build-box:8090")
+        );
+
+        let mut state = State::default();
+        let container = ContainerInfo::new(
+            "n1".to_string(),
+            "net".to_string(),
+            8090,
+            None,
+            None,
+            "latest".to_string(),
+        );
+        assert!(container.network.bind_address.is_loopback());
+        state.add_container(container).unwrap();
+
+        let attachment = |aliases: &[&str]| NetworkAttachment {
+            name: "app".to_string(),
+            aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+        };
+        state.add_network("n1", attachment(&["fluree"])).unwrap();
+        state.add_network("n1", attachment(&["db"])).unwrap();
+        assert!(state.add_network("missing", attachment(&[])).is_err());
+
+        let state = State::load().unwrap();
+        assert_eq!(
+            state.containers["n1"].network.networks,
+            vec![attachment(&["db"])]
+        );
+    }
+
     #[test]
     #[parallel]
     fn test_container_name_uniqueness() {
@@ -430,4 +695,31 @@ mod tests {
         assert_eq!(found.len(), 1);
         assert_eq!(found[0].id, "test1");
     }
+
+    #[test]
+    #[parallel]
+    fn test_find_container_by_name_or_id() {
+        let mut state = State::default();
+        for (id, name) in [("abc123", "dev"), ("abd456", "dev-2")] {
+            let container = ContainerInfo::new(
+                id.to_string(),
+                name.to_string(),
+                8090,
+                None,
+                None,
+                "latest".to_string(),
+            );
+            state.containers.insert(container.id.clone(), container);
+        }
+
+        // Exact name match wins over substring matches
+        assert_eq!(state.find_container("dev").unwrap().id, "abc123");
+        // Full and unique prefix IDs resolve
+        assert_eq!(state.find_container("abd456").unwrap().name, "dev-2");
+        assert_eq!(state.find_container("abc").unwrap().name, "dev");
+        // Ambiguous prefixes and unknown names do not
+        assert!(state.find_container("ab").is_none());
+        assert!(state.find_container("missing").is_none());
+        assert!(state.find_container("").is_none());
+    }
 }
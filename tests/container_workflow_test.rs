@@ -1,24 +1,44 @@
 use async_trait::async_trait;
 use flocker::{
     cli::{hub::Tag, CliState},
-    docker::{ContainerConfig, DockerOperations, FlureeImage},
-    state::ContainerInfo,
+    docker::{
+        ports::{next_free_port, port_conflict, PortConflict, DEFAULT_PORT},
+        readiness::{wait_until_ready, Probe, Readiness, READINESS_PATH},
+        ContainerConfig, ContainerStats, DockerOperations, FlureeImage, LedgerInfo, LogOptions,
+        NetworkAttachment, PullProgress,
+    },
+    fluree::{
+        backup::{backup_ledger, read_backup, restore_ledger},
+        copy::copy_ledger,
+        FlureeClient, HostLedgerReader, LedgerOperations, LEDGERS_PATH, LEDGER_INFO_PATH,
+    },
+    state::{ContainerInfo, DataDirConfig, State},
     ContainerStatus, Result,
 };
+use futures_util::stream::{self, BoxStream, StreamExt};
+use std::net::IpAddr;
+use std::time::Duration;
 use tempfile::tempdir;
 
 // Mock DockerManager for testing
 #[derive(Clone)]
 struct MockDockerManager {
     container_status: ContainerStatus,
+    remote_host: Option<String>,
 }
 
 impl MockDockerManager {
     fn new(status: ContainerStatus) -> Self {
         Self {
             container_status: status,
+            remote_host: None,
         }
     }
+
+    fn on_remote_host(mut self, host: &str) -> Self {
+        self.remote_host = Some(host.to_string());
+        self
+    }
 }
 
 #[async_trait]
@@ -31,7 +51,23 @@ impl DockerOperations for MockDockerManager {
         Ok(())
     }
 
-    async fn stop_container(&self, _container_id: &str) -> Result<()> {
+    async fn stop_container(&self, _container_id: &str, _timeout: Option<u64>) -> Result<()> {
+        Ok(())
+    }
+
+    async fn restart_container(&self, _container_id: &str, _timeout: Option<u64>) -> Result<()> {
+        Ok(())
+    }
+
+    async fn pause_container(&self, _container_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    async fn unpause_container(&self, _container_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    async fn kill_container(&self, _container_id: &str, _signal: &str) -> Result<()> {
         Ok(())
     }
 
@@ -39,6 +75,18 @@ impl DockerOperations for MockDockerManager {
         Ok(())
     }
 
+    async fn is_port_in_use(&self, _address: IpAddr, _port: u16) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn remote_host(&self) -> Option<String> {
+        self.remote_host.clone()
+    }
+
+    async fn probe_server(&self, _container_id: &str) -> Result<Option<bool>> {
+        Ok(Some(true))
+    }
+
     async fn create_and_start_container(
         &self,
         image_tag: &Tag,
@@ -55,8 +103,25 @@ impl DockerOperations for MockDockerManager {
         ))
     }
 
-    async fn list_ledgers(&self, _container_id: &str) -> Result<Vec<flocker::docker::LedgerInfo>> {
-        Ok(Vec::new())
+    async fn connect_network(
+        &self,
+        _container_id: &str,
+        _network: &NetworkAttachment,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn list_ledgers(&self, _container_id: &str) -> Result<Vec<LedgerInfo>> {
+        Ok(vec![LedgerInfo {
+            alias: "cookbook".to_string(),
+            last_commit_time: "2024-02-08T21:56:23Z".to_string(),
+            commit_count: 5,
+            size: 2048,
+            path: Some("/opt/fluree-server/data/ns@v1/cookbook/main.json".to_string()),
+            flakes_count: "1,200".to_string(),
+            last_index: Some(4),
+            branches: Vec::new(),
+        }])
     }
 
     async fn get_ledger_details(&self, _container_id: &str, _path: &str) -> Result<String> {
@@ -67,25 +132,84 @@ impl DockerOperations for MockDockerManager {
         Ok(())
     }
 
-    async fn pull_image(&self, _tag: &str) -> Result<()> {
+    async fn download_from_container(&self, _container_id: &str, _path: &str) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    async fn upload_to_container(
+        &self,
+        _container_id: &str,
+        _path: &str,
+        _archive: Vec<u8>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn pull_image(
+        &self,
+        _repository: &str,
+        _tag: &str,
+        _progress: &(dyn Fn(PullProgress) + Send + Sync),
+    ) -> Result<()> {
         Ok(())
     }
 
-    async fn get_image_by_tag(&self, _tag_str: &str) -> Result<FlureeImage> {
+    async fn get_image_by_tag(&self, _repository: &str, _tag_str: &str) -> Result<FlureeImage> {
         unimplemented!("Not needed for these tests")
     }
 
-    async fn list_local_images(&self) -> Result<Vec<FlureeImage>> {
+    async fn list_local_images(&self, _repository: &str) -> Result<Vec<FlureeImage>> {
         unimplemented!("Not needed for these tests")
     }
 
-    async fn get_container_stats(&self, _container_id: &str) -> Result<String> {
-        Ok("CONTAINER ID        CPU %               MEM USAGE / LIMIT     MEM %\ntest-container      0.00%               10.0MB / 100.0MB      10.00%".to_string())
+    async fn get_container_stats(&self, container_id: &str) -> Result<ContainerStats> {
+        Ok(ContainerStats {
+            id: container_id.to_string(),
+            cpu_percent: 0.5,
+            memory_usage: 10 * 1024 * 1024,
+            memory_limit: 100 * 1024 * 1024,
+            network_rx: 2048,
+            network_tx: 1024,
+            block_read: 4096,
+            block_write: 8192,
+            pids: 42,
+        })
+    }
+
+    fn stream_container_stats(
+        &self,
+        container_id: &str,
+    ) -> BoxStream<'static, Result<ContainerStats>> {
+        let id = container_id.to_string();
+        stream::once(async move {
+            Ok(ContainerStats {
+                id,
+                cpu_percent: 0.5,
+                ..Default::default()
+            })
+        })
+        .boxed()
     }
 
-    async fn get_container_logs(&self, _container_id: &str, _tail: Option<&str>) -> Result<String> {
+    async fn get_container_logs(
+        &self,
+        _container_id: &str,
+        _options: &LogOptions,
+    ) -> Result<String> {
         Ok("Mock container logs for testing".to_string())
     }
+
+    fn follow_container_logs(
+        &self,
+        _container_id: &str,
+        _options: &LogOptions,
+    ) -> BoxStream<'static, Result<String>> {
+        stream::iter(vec![
+            Ok("Mock container logs for testing".to_string()),
+            Ok("Mock followed log line".to_string()),
+        ])
+        .boxed()
+    }
 }
 
 fn create_test_container(id: &str, name: &str, port: u16) -> ContainerInfo {
@@ -120,6 +244,7 @@ async fn test_container_workflow() {
         data_dir: None,
         config_dir: None,
         started_at: Some("2024-01-01T00:00:00Z".to_string()),
+        health: None,
     };
     let docker = MockDockerManager::new(running_status);
 
@@ -163,3 +288,328 @@ async fn test_empty_state_workflow() {
     let result = cli.try_running_existing_container(&docker).await.unwrap();
     assert!(result.is_none());
 }
+
+#[tokio::test]
+async fn test_next_free_port_skips_reserved_ports() {
+    let docker = MockDockerManager::new(ContainerStatus::NotFound);
+    let address = "127.0.0.1".parse().unwrap();
+
+    // Stopped containers keep their ports reserved
+    let mut state = State::default();
+    for (id, port) in [("a", DEFAULT_PORT), ("b", DEFAULT_PORT + 1)] {
+        let container = create_test_container(id, id, port);
+        state.containers.insert(container.id.clone(), container);
+    }
+
+    assert_eq!(
+        port_conflict(&docker, &state, address, DEFAULT_PORT)
+            .await
+            .unwrap(),
+        Some(PortConflict::Reserved {
+            container: "a".to_string()
+        })
+    );
+    assert_eq!(
+        next_free_port(&docker, &state, address, DEFAULT_PORT)
+            .await
+            .unwrap(),
+        DEFAULT_PORT + 2
+    );
+}
+
+fn running_status() -> ContainerStatus {
+    ContainerStatus::Running {
+        id: "test1".to_string(),
+        name: "test-container".to_string(),
+        port: 8090,
+        data_dir: None,
+        config_dir: None,
+        started_at: Some("2024-01-01T00:00:00Z".to_string()),
+        health: None,
+    }
+}
+
+#[tokio::test]
+async fn test_wait_until_ready() {
+    let mut server = mockito::Server::new_async().await;
+    let health = server
+        .mock("GET", READINESS_PATH)
+        .with_status(200)
+        .create_async()
+        .await;
+    let docker = MockDockerManager::new(running_status());
+
+    let readiness = wait_until_ready(
+        &docker,
+        "test1",
+        &Probe::Http(format!("{}{}", server.url(), READINESS_PATH)),
+        Duration::from_secs(5),
+        None,
+        |_| {},
+    )
+    .await
+    .unwrap();
+    assert!(matches!(readiness, Readiness::Ready { .. }));
+    health.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_wait_until_ready_on_remote_daemon() {
+    // The server is asked from inside the container, since the remote
+    // daemon's published ports may not be reachable
+    let docker = MockDockerManager::new(running_status()).on_remote_host("build-box");
+    let readiness = wait_until_ready(
+        &docker,
+        "test1",
+        &Probe::Exec,
+        Duration::from_secs(5),
+        None,
+        |_| {},
+    )
+    .await
+    .unwrap();
+    assert!(matches!(readiness, Readiness::Ready { .. }));
+}
+
+#[tokio::test]
+async fn test_ledger_operations_on_remote_loopback() {
+    // A port on the remote daemon's loopback address can't be reached, so
+    // ledgers are read from disk without trying the HTTP API
+    let docker = MockDockerManager::new(running_status()).on_remote_host("build-box");
+    let container = create_test_container("test1", "test-container", 8090);
+    assert!(container.network.bind_address.is_loopback());
+    assert_eq!(container.url(Some("build-box")), None);
+
+    let ledgers = LedgerOperations::new(&docker, &container)
+        .list()
+        .await
+        .unwrap();
+    assert_eq!(ledgers[0].alias, "cookbook");
+}
+
+#[tokio::test]
+async fn test_wait_until_ready_reports_early_exit() {
+    let docker = MockDockerManager::new(ContainerStatus::Exited {
+        id: "test1".to_string(),
+        name: "test-container".to_string(),
+        code: 3,
+        oom_killed: false,
+        finished_at: None,
+        last_start: None,
+    });
+
+    // Nothing listens on port 1, so only the container status can end the wait
+    let readiness = wait_until_ready(
+        &docker,
+        "test1",
+        &Probe::Http("http://127.0.0.1:1/fluree/health".to_string()),
+        Duration::from_secs(5),
+        None,
+        |_| {},
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        readiness,
+        Readiness::Exited {
+            exit_code: Some(3),
+            last_logs: vec!["Mock container logs for testing".to_string()],
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_wait_until_ready_times_out() {
+    let docker = MockDockerManager::new(running_status());
+
+    let readiness = wait_until_ready(
+        &docker,
+        "test1",
+        &Probe::Http("http://127.0.0.1:1/fluree/health".to_string()),
+        Duration::from_millis(1200),
+        None,
+        |_| {},
+    )
+    .await
+    .unwrap();
+    assert!(matches!(readiness, Readiness::TimedOut { .. }));
+}
+
+#[tokio::test]
+async fn test_ledger_operations_fall_back_to_disk() {
+    let docker = MockDockerManager::new(running_status());
+    let container = ContainerInfo::new(
+        "test1".to_string(),
+        "test-container".to_string(),
+        8090,
+        None,
+        None,
+        "latest".to_string(),
+    );
+
+    // Server without the ledger endpoints
+    let mut server = mockito::Server::new_async().await;
+    let missing = server
+        .mock("GET", LEDGERS_PATH)
+        .with_status(404)
+        .create_async()
+        .await;
+    let ledgers = LedgerOperations::new(&docker, &container)
+        .with_client(FlureeClient::new(server.url()))
+        .list()
+        .await
+        .unwrap();
+    assert_eq!(ledgers.len(), 1);
+    assert_eq!(ledgers[0].alias, "cookbook");
+    missing.assert_async().await;
+
+    // Unreachable server
+    let offline = LedgerOperations::new(&docker, &container)
+        .with_client(FlureeClient::new("http://127.0.0.1:1"));
+    assert_eq!(offline.details("cookbook").await.unwrap(), "{}");
+    offline.drop_ledger("cookbook").await.unwrap();
+    assert!(offline.details("missing").await.is_err());
+    assert!(offline.create("recipes").await.is_err());
+
+    // Errors from a server with the API are not hidden
+    server
+        .mock("POST", LEDGER_INFO_PATH)
+        .with_status(400)
+        .with_body(r#"{"error": "Ledger missing does not exist"}"#)
+        .create_async()
+        .await;
+    let error = LedgerOperations::new(&docker, &container)
+        .with_client(FlureeClient::new(server.url()))
+        .details("missing")
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("does not exist"));
+}
+
+/// A container whose data directory is `dir/name` on the host
+fn host_mounted_container(dir: &std::path::Path, name: &str) -> ContainerInfo {
+    let data_dir = dir.join(name);
+    std::fs::create_dir_all(&data_dir).unwrap();
+    ContainerInfo::new(
+        format!("{}-id", name),
+        name.to_string(),
+        8090,
+        Some(DataDirConfig::new(data_dir, None)),
+        None,
+        "latest".to_string(),
+    )
+}
+
+/// Write a `cookbook` ledger at commit 5 into a host data directory
+fn write_cookbook_ledger(data_dir: &std::path::Path) {
+    let ledger_dir = data_dir.join("ns@v1").join("cookbook");
+    std::fs::create_dir_all(ledger_dir.join("commit")).unwrap();
+    std::fs::write(
+        ledger_dir.join("main.json"),
+        r#"{
+            "ledgerAlias": "cookbook",
+            "branches": [{
+                "name": "main",
+                "commit": {
+                    "address": "fluree:file://cookbook/main/commit/abc.json",
+                    "time": "2024-02-08T21:56:23Z",
+                    "data": { "t": 5, "size": 2048, "flakes": 1200 }
+                }
+            }]
+        }"#,
+    )
+    .unwrap();
+    std::fs::write(ledger_dir.join("commit").join("abc.json"), "{}").unwrap();
+}
+
+#[tokio::test]
+async fn test_backup_and_restore_through_host_mount() {
+    let docker = MockDockerManager::new(ContainerStatus::NotFound);
+    let dir = tempdir().unwrap();
+    let source = host_mounted_container(dir.path(), "source");
+    let target = host_mounted_container(dir.path(), "target");
+    write_cookbook_ledger(&dir.path().join("source"));
+
+    let archive = dir.path().join("cookbook.tar.gz");
+    let manifest = backup_ledger(&docker, &source, "cookbook", &archive)
+        .await
+        .unwrap();
+    assert_eq!(manifest.ledger_dir, "ns@v1/cookbook");
+    assert_eq!(manifest.container, "source");
+    assert_eq!(manifest.files.len(), 2);
+    assert_eq!(read_backup(&archive).unwrap().0, manifest);
+
+    restore_ledger(&docker, &target, &archive).await.unwrap();
+    let restored = HostLedgerReader::for_container(&target)
+        .unwrap()
+        .list()
+        .unwrap();
+    assert_eq!(restored.len(), 1);
+    assert_eq!(restored[0].alias, "cookbook");
+    assert!(dir
+        .path()
+        .join("target/ns@v1/cookbook/commit/abc.json")
+        .is_file());
+
+    // Restoring again would overwrite the ledger
+    let error = restore_ledger(&docker, &target, &archive)
+        .await
+        .unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("already has a ledger named 'cookbook'"));
+
+    assert!(backup_ledger(&docker, &source, "missing", &archive)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_copy_ledger_between_containers() {
+    let docker = MockDockerManager::new(ContainerStatus::NotFound);
+    let dir = tempdir().unwrap();
+    let source = host_mounted_container(dir.path(), "source");
+    let target = host_mounted_container(dir.path(), "target");
+    write_cookbook_ledger(&dir.path().join("source"));
+
+    let copied = copy_ledger(&docker, &source, "cookbook", &target)
+        .await
+        .unwrap();
+    assert_eq!(copied.alias, "cookbook");
+    assert_eq!(copied.commit_count, 5);
+    let commit =
+        std::fs::read_to_string(dir.path().join("target/ns@v1/cookbook/commit/abc.json")).unwrap();
+    assert_eq!(commit, "{}");
+
+    // The same ledger can't be copied twice, or into its own container
+    assert!(copy_ledger(&docker, &source, "cookbook", &target)
+        .await
+        .is_err());
+    assert!(copy_ledger(&docker, &source, "cookbook", &source)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_copy_ledger_is_confirmed_by_running_target() {
+    // A running target's server has to list the copy, not just its disk
+    let mut server = mockito::Server::new_async().await;
+    let ledgers = server
+        .mock("GET", LEDGERS_PATH)
+        .with_body("[]")
+        .create_async()
+        .await;
+    let docker = MockDockerManager::new(running_status());
+    let dir = tempdir().unwrap();
+    let source = host_mounted_container(dir.path(), "source");
+    let mut target = host_mounted_container(dir.path(), "target");
+    target.port = server.socket_address().port();
+    write_cookbook_ledger(&dir.path().join("source"));
+
+    let error = copy_ledger(&docker, &source, "cookbook", &target)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("'target' doesn't list it"), "{}", error);
+    ledgers.assert_async().await;
+}